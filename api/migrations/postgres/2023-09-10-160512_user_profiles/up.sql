@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE user_profiles (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id),
+    display_name VARCHAR(64) NULL,
+    bio TEXT NULL,
+    avatar VARCHAR(255) NULL,
+    links TEXT NOT NULL DEFAULT '[]'
+);
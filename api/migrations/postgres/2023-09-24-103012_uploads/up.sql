@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE uploads (
+    id SERIAL PRIMARY KEY,
+    path VARCHAR(255) NOT NULL,
+    content_type VARCHAR(64) NOT NULL,
+    size INTEGER NOT NULL,
+    user_id INTEGER NULL REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT current_timestamp
+);
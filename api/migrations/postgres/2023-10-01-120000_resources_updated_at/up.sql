@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE resources ADD COLUMN updated_at TIMESTAMP NOT NULL DEFAULT current_timestamp;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX resources_search_pl;
+DROP INDEX resources_search_en;
+DROP VIEW search_documents;
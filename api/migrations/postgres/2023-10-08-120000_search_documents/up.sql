@@ -0,0 +1,15 @@
+-- Your SQL goes here
+-- Everything the search looks through, one row per language of each content,
+-- content tables added later are appended with UNION ALL.
+-- Postgres ships no Polish dictionary, so the Polish text is only split into words.
+CREATE VIEW search_documents AS
+    SELECT 'resource'::TEXT AS kind, key, 'en'::TEXT AS lang, en AS body,
+        to_tsvector('english', en) AS document
+    FROM resources
+    UNION ALL
+    SELECT 'resource'::TEXT, key, 'pl'::TEXT, COALESCE(pl, en),
+        to_tsvector('simple', COALESCE(pl, en))
+    FROM resources;
+
+CREATE INDEX resources_search_en ON resources USING GIN (to_tsvector('english', en));
+CREATE INDEX resources_search_pl ON resources USING GIN (to_tsvector('simple', COALESCE(pl, en)));
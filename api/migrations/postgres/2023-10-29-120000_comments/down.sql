@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE comment_bans;
+DROP TABLE comments;
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+-- SQLite can't add a column with a non-constant default, so the table is rebuilt.
+CREATE TABLE resources_new (
+    key VARCHAR(64) PRIMARY KEY NOT NULL,
+    en TEXT NOT NULL,
+    pl TEXT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT current_timestamp
+);
+INSERT INTO resources_new (key, en, pl) SELECT key, en, pl FROM resources;
+DROP TABLE resources;
+ALTER TABLE resources_new RENAME TO resources;
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+-- Everything the search looks through, one row per language of each content,
+-- content tables added later are appended with UNION ALL.
+CREATE VIEW search_documents AS
+    SELECT 'resource' AS kind, key, 'en' AS lang, en AS body
+    FROM resources
+    UNION ALL
+    SELECT 'resource', key, 'pl', COALESCE(pl, en)
+    FROM resources;
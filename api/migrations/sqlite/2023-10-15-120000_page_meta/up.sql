@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE TABLE page_meta (
+    key VARCHAR(64) NOT NULL REFERENCES resources(key) ON DELETE CASCADE,
+    lang VARCHAR(2) NOT NULL,
+    title VARCHAR(128) NULL,
+    description TEXT NULL,
+    canonical VARCHAR(255) NULL,
+    image VARCHAR(255) NULL,
+    PRIMARY KEY (key, lang)
+);
@@ -0,0 +1,21 @@
+-- Your SQL goes here
+CREATE TABLE comments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    key VARCHAR(64) NOT NULL REFERENCES resources(key) ON DELETE CASCADE,
+    parent_id INTEGER NULL REFERENCES comments(id) ON DELETE SET NULL,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    status INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT current_timestamp,
+    edited_at TIMESTAMP NULL,
+    deleted_at TIMESTAMP NULL
+);
+
+CREATE INDEX comments_key_idx ON comments (key);
+CREATE INDEX comments_status_idx ON comments (status);
+
+CREATE TABLE comment_bans (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    banned_by INTEGER NULL REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT current_timestamp
+);
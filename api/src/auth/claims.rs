@@ -1,68 +1,125 @@
-use super::{error::AuthError, token::validate_token};
+use super::{
+    cookie::{verify_csrf, TOKEN_COOKIE},
+    error::AuthError,
+    keys::JwtKeys,
+    token::validate_token,
+};
 use crate::{
+    error::Error,
     models::{role::Role, user::User},
-    Secrets,
+    repositories::user::repo::UserRepo,
 };
-use rocket::{http::Status, outcome::Outcome, request::FromRequest, Request};
+use deref_derive::Deref;
+use rocket::{http::Status, outcome::Outcome, request::FromRequest, serde::json::Value, Request};
 use std::{collections::BTreeMap, str::FromStr};
 
 #[derive(Clone)]
 pub struct Claims {
     pub sub: i32,
     pub exp: u64,
+    pub iat: u64,
+    pub nbf: u64,
+    pub iss: String,
+    pub aud: String,
     pub acs: Role,
 }
 
+pub const ISSUER: &str = "petompp-web-api";
+pub const AUDIENCE: &str = "petompp-web";
+/// Allowed clock difference between the servers, in seconds.
+const LEEWAY: i64 = 60;
+
 const SUB_CLAIM: &str = "sub";
 const EXP_CLAIM: &str = "exp";
+const IAT_CLAIM: &str = "iat";
+const NBF_CLAIM: &str = "nbf";
+const ISS_CLAIM: &str = "iss";
+const AUD_CLAIM: &str = "aud";
 const ACS_CLAIM: &str = "acs";
 
-impl From<Claims> for BTreeMap<String, String> {
+impl From<Claims> for BTreeMap<String, Value> {
     fn from(val: Claims) -> Self {
         let mut map = BTreeMap::new();
-        map.insert(SUB_CLAIM.to_string(), val.sub.to_string());
-        map.insert(EXP_CLAIM.to_string(), val.exp.to_string());
-        map.insert(ACS_CLAIM.to_string(), val.acs.to_string());
+        map.insert(SUB_CLAIM.to_string(), val.sub.to_string().into());
+        map.insert(EXP_CLAIM.to_string(), val.exp.into());
+        map.insert(IAT_CLAIM.to_string(), val.iat.into());
+        map.insert(NBF_CLAIM.to_string(), val.nbf.into());
+        map.insert(ISS_CLAIM.to_string(), val.iss.into());
+        map.insert(AUD_CLAIM.to_string(), val.aud.into());
+        map.insert(ACS_CLAIM.to_string(), val.acs.to_string().into());
         map
     }
 }
 
-impl TryFrom<BTreeMap<String, String>> for Claims {
+impl TryFrom<BTreeMap<String, Value>> for Claims {
     type Error = AuthError;
 
-    fn try_from(value: BTreeMap<String, String>) -> Result<Self, Self::Error> {
-        let sub = get_claim_value(&value, SUB_CLAIM)?;
-        let exp = get_claim_value(&value, EXP_CLAIM)?;
-        match (exp as i64) - chrono::Utc::now().timestamp() {
-            ref x if x < &0 => Err(AuthError::TokenExpiredS(-*x)),
-            _ => Ok(Self {
-                sub,
-                exp,
-                acs: get_claim_value(&value, ACS_CLAIM)?,
-            }),
+    fn try_from(value: BTreeMap<String, Value>) -> Result<Self, Self::Error> {
+        let now = chrono::Utc::now().timestamp();
+        let exp: u64 = get_claim_value(&value, EXP_CLAIM)?;
+        if (exp as i64) + LEEWAY < now {
+            return Err(AuthError::TokenExpiredS(now - exp as i64));
+        }
+        let nbf: u64 = get_claim_value(&value, NBF_CLAIM)?;
+        if (nbf as i64) - LEEWAY > now {
+            return Err(AuthError::TokenNotYetValidS(nbf as i64 - now));
         }
+        let iss: String = get_claim_value(&value, ISS_CLAIM)?;
+        if iss != ISSUER {
+            return Err(AuthError::InvalidClaim(ISS_CLAIM.to_string()));
+        }
+        // The audience can be either a single value or an array.
+        let aud = match value.get(AUD_CLAIM) {
+            Some(Value::Array(values)) => values
+                .iter()
+                .filter_map(|v| v.as_str())
+                .find(|v| *v == AUDIENCE)
+                .map(|v| v.to_string()),
+            Some(Value::String(v)) => Some(v.clone()).filter(|v| v == AUDIENCE),
+            Some(_) => return Err(AuthError::InvalidFormat(AUD_CLAIM.to_string())),
+            None => return Err(AuthError::MissingClaim(AUD_CLAIM.to_string())),
+        }
+        .ok_or(AuthError::InvalidClaim(AUD_CLAIM.to_string()))?;
+        Ok(Self {
+            sub: get_claim_value(&value, SUB_CLAIM)?,
+            exp,
+            iat: get_claim_value(&value, IAT_CLAIM)?,
+            nbf,
+            iss,
+            aud,
+            acs: get_claim_value(&value, ACS_CLAIM)?,
+        })
     }
 }
 
 fn get_claim_value<T: FromStr>(
-    claims: &BTreeMap<String, String>,
+    claims: &BTreeMap<String, Value>,
     claim: &str,
 ) -> Result<T, AuthError> {
-    claims
+    let value = match claims
         .get(claim)
         .ok_or(AuthError::MissingClaim(claim.to_string()))?
+    {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    };
+    value
         .parse::<T>()
         .map_err(|_| AuthError::InvalidFormat(claim.to_string()))
 }
 
-impl TryFrom<User> for Claims {
-    type Error = AuthError;
-
-    fn try_from(value: User) -> Result<Self, Self::Error> {
+impl Claims {
+    /// Creates claims for the user, valid for `lifetime` seconds.
+    pub fn new(value: &User, lifetime: u64) -> Result<Self, AuthError> {
+        let now = chrono::Utc::now().timestamp() as u64;
         match value.id {
             Some(id) => Ok(Self {
                 sub: id,
-                exp: chrono::Utc::now().timestamp() as u64 + 60 * 60,
+                exp: now + lifetime,
+                iat: now,
+                nbf: now,
+                iss: ISSUER.to_string(),
+                aud: AUDIENCE.to_string(),
                 acs: value.role,
             }),
             None => Err(AuthError::InvalidFormat("User id".to_string())),
@@ -75,20 +132,41 @@ impl<'r> FromRequest<'r> for Claims {
     type Error = ();
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, (Status, Self::Error), ()> {
-        let secrets = request.rocket().state::<Secrets>().unwrap();
-        let Some(token) = request.headers().get_one("Authorization") else {
-            return Outcome::Failure((Status::Unauthorized, ()));
+        let keys = request.rocket().state::<JwtKeys>().unwrap();
+        let header_token = request
+            .headers()
+            .get_one("Authorization")
+            .and_then(|t| t.strip_prefix("Bearer "))
+            .filter(|t| !t.is_empty());
+        let token = match header_token {
+            Some(token) => token.to_string(),
+            None => {
+                let Some(cookie) = request.cookies().get(TOKEN_COOKIE) else {
+                    return Outcome::Failure((Status::Unauthorized, ()));
+                };
+                // Cookies are attached by the browser automatically, so the CSRF token has to be checked.
+                if !verify_csrf(request) {
+                    return Outcome::Failure((Status::Forbidden, ()));
+                }
+                cookie.value().to_string()
+            }
         };
-        let Some(token) = token.strip_prefix("Bearer ") else {
+        let Ok(claims) = validate_token(keys, &token) else {
             return Outcome::Failure((Status::Unauthorized, ()));
         };
-        let Ok(claims) = validate_token(secrets, token) else {
-            return Outcome::Failure((Status::Unauthorized, ()));
+        let Outcome::Success(users) = request.guard::<&dyn UserRepo>().await else {
+            return Outcome::Failure((Status::InternalServerError, ()));
         };
-        Outcome::Success(claims)
+        // The token outlives a deleted account, it has to stop working right away.
+        match users.get_by_id(claims.sub).await {
+            Ok(user) if user.deleted_at.is_none() => Outcome::Success(claims),
+            Ok(_) | Err(Error::UserNotFound(_)) => Outcome::Failure((Status::Unauthorized, ())),
+            Err(_) => Outcome::Failure((Status::InternalServerError, ())),
+        }
     }
 }
 
+#[derive(Deref)]
 pub struct AdminClaims(Claims);
 
 #[rocket::async_trait]
@@ -96,11 +174,14 @@ impl<'r> FromRequest<'r> for AdminClaims {
     type Error = ();
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, (Status, Self::Error), ()> {
-        let Outcome::Success(claims) = request.guard::<Claims>().await else {
-            return Outcome::Failure((Status::Unauthorized, ()));
+        let claims = match request.guard::<Claims>().await {
+            Outcome::Success(claims) => claims,
+            Outcome::Failure(failure) => return Outcome::Failure(failure),
+            Outcome::Forward(forward) => return Outcome::Forward(forward),
         };
+        // Authenticated, just not allowed, the session stays valid.
         if claims.acs != Role::Admin {
-            return Outcome::Failure((Status::Unauthorized, ()));
+            return Outcome::Failure((Status::Forbidden, ()));
         }
         Outcome::Success(Self(claims))
     }
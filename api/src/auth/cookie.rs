@@ -0,0 +1,75 @@
+use super::claims::Claims;
+use rocket::{
+    http::{Cookie, CookieJar, Method, SameSite},
+    time::{Duration, OffsetDateTime},
+    Request,
+};
+use serde::{Deserialize, Serialize};
+
+pub const TOKEN_COOKIE: &str = "petompp_token";
+pub const CSRF_COOKIE: &str = "petompp_csrf";
+pub const CSRF_HEADER: &str = "X-CSRF-Token";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMode {
+    /// The token is returned in the login response and sent back in the `Authorization` header.
+    #[default]
+    Header,
+    /// The token is stored in an `HttpOnly` cookie, never exposed to the scripts.
+    Cookie,
+}
+
+/// Stores the token and a fresh CSRF token in the cookies.
+/// Returns the CSRF token, that needs to be sent back in the `X-CSRF-Token` header.
+pub fn set_auth_cookies(cookies: &CookieJar<'_>, token: String, claims: &Claims) -> String {
+    let expires = OffsetDateTime::from_unix_timestamp(claims.exp as i64)
+        .unwrap_or_else(|_| OffsetDateTime::now_utc() + Duration::hours(1));
+    let csrf = generate_csrf_token();
+    cookies.add(
+        Cookie::build(TOKEN_COOKIE, token)
+            .path("/")
+            .http_only(true)
+            .secure(true)
+            .same_site(SameSite::Strict)
+            .expires(expires)
+            .finish(),
+    );
+    cookies.add(
+        Cookie::build(CSRF_COOKIE, csrf.clone())
+            .path("/")
+            .secure(true)
+            .same_site(SameSite::Strict)
+            .expires(expires)
+            .finish(),
+    );
+    csrf
+}
+
+pub fn remove_auth_cookies(cookies: &CookieJar<'_>) {
+    cookies.remove(Cookie::named(TOKEN_COOKIE));
+    cookies.remove(Cookie::named(CSRF_COOKIE));
+}
+
+/// Double-submit check, the CSRF header must match the CSRF cookie on state-changing requests.
+pub fn verify_csrf(request: &Request<'_>) -> bool {
+    if matches!(
+        request.method(),
+        Method::Get | Method::Head | Method::Options
+    ) {
+        return true;
+    }
+    let Some(cookie) = request.cookies().get(CSRF_COOKIE) else {
+        return false;
+    };
+    request
+        .headers()
+        .get_one(CSRF_HEADER)
+        .is_some_and(|header| !header.is_empty() && header == cookie.value())
+}
+
+fn generate_csrf_token() -> String {
+    let mut rng = urandom::csprng();
+    let token: [u8; 32] = rng.next();
+    token.iter().map(|x| format!("{:02x}", x)).collect()
+}
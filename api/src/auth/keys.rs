@@ -0,0 +1,146 @@
+use super::error::AuthError;
+use crate::config::JwtConfig;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ed25519_dalek::{pkcs8::DecodePublicKey, VerifyingKey};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use rsa::{pkcs8::DecodePublicKey as _, traits::PublicKeyParts, RsaPublicKey};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// The key used to sign the new tokens.
+pub struct SigningKey {
+    pub kid: String,
+    pub algorithm: Algorithm,
+    pub key: EncodingKey,
+}
+
+/// A key accepted when validating the tokens.
+/// Old keys stay here for a while after the rotation, so issued tokens remain valid.
+pub struct VerificationKey {
+    pub kid: String,
+    pub algorithm: Algorithm,
+    pub key: DecodingKey,
+    pub jwk: Jwk,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    pub kid: String,
+    pub alg: String,
+    #[serde(rename = "use")]
+    pub key_use: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+pub struct JwtKeys {
+    pub signing: SigningKey,
+    pub verification: Vec<VerificationKey>,
+}
+
+impl JwtKeys {
+    /// Loads the keys from PEM files.
+    /// The verification keys have to contain the public part of the signing key.
+    pub fn new(config: &JwtConfig) -> Result<Self, AuthError> {
+        let verification = config
+            .verification_keys
+            .iter()
+            .map(|k| VerificationKey::from_pem(&k.kid, &read_pem(&k.path)?))
+            .collect::<Result<Vec<_>, _>>()?;
+        let kid = &config.signing_key_id;
+        let Some(algorithm) = verification
+            .iter()
+            .find(|k| &k.kid == kid)
+            .map(|k| k.algorithm)
+        else {
+            return Err(AuthError::UnknownKey(kid.clone()));
+        };
+        let signing = SigningKey::from_pem(kid, algorithm, &read_pem(&config.signing_key)?)?;
+        Ok(Self {
+            signing,
+            verification,
+        })
+    }
+
+    pub fn get(&self, kid: &str) -> Option<&VerificationKey> {
+        self.verification.iter().find(|k| k.kid == kid)
+    }
+
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self.verification.iter().map(|k| k.jwk.clone()).collect(),
+        }
+    }
+}
+
+impl SigningKey {
+    fn from_pem(kid: &str, algorithm: Algorithm, pem: &[u8]) -> Result<Self, AuthError> {
+        let key = match algorithm {
+            Algorithm::EdDSA => EncodingKey::from_ed_pem(pem),
+            _ => EncodingKey::from_rsa_pem(pem),
+        }
+        .map_err(|_| AuthError::InvalidKey(kid.to_string()))?;
+        Ok(Self {
+            kid: kid.to_string(),
+            algorithm,
+            key,
+        })
+    }
+}
+
+impl VerificationKey {
+    /// Ed25519 keys are used with EdDSA, RSA keys with RS256.
+    fn from_pem(kid: &str, pem: &[u8]) -> Result<Self, AuthError> {
+        let invalid = || AuthError::InvalidKey(kid.to_string());
+        let pem_str = std::str::from_utf8(pem).map_err(|_| invalid())?;
+        if let Ok(public) = VerifyingKey::from_public_key_pem(pem_str) {
+            return Ok(Self {
+                kid: kid.to_string(),
+                algorithm: Algorithm::EdDSA,
+                key: DecodingKey::from_ed_pem(pem).map_err(|_| invalid())?,
+                jwk: Jwk {
+                    kty: "OKP".to_string(),
+                    kid: kid.to_string(),
+                    alg: "EdDSA".to_string(),
+                    key_use: "sig".to_string(),
+                    crv: Some("Ed25519".to_string()),
+                    x: Some(URL_SAFE_NO_PAD.encode(public.as_bytes())),
+                    n: None,
+                    e: None,
+                },
+            });
+        }
+        let public = RsaPublicKey::from_public_key_pem(pem_str).map_err(|_| invalid())?;
+        Ok(Self {
+            kid: kid.to_string(),
+            algorithm: Algorithm::RS256,
+            key: DecodingKey::from_rsa_pem(pem).map_err(|_| invalid())?,
+            jwk: Jwk {
+                kty: "RSA".to_string(),
+                kid: kid.to_string(),
+                alg: "RS256".to_string(),
+                key_use: "sig".to_string(),
+                crv: None,
+                x: None,
+                n: Some(URL_SAFE_NO_PAD.encode(public.n().to_bytes_be())),
+                e: Some(URL_SAFE_NO_PAD.encode(public.e().to_bytes_be())),
+            },
+        })
+    }
+}
+
+fn read_pem(path: &str) -> Result<Vec<u8>, AuthError> {
+    fs::read(path.trim()).map_err(|_| AuthError::InvalidKey(path.to_string()))
+}
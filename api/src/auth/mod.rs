@@ -1,3 +1,5 @@
 pub mod claims;
+pub mod cookie;
 pub mod error;
+pub mod keys;
 pub mod token;
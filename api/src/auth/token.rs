@@ -1,19 +1,36 @@
-use super::{claims::Claims, error::AuthError};
-use crate::{models::user::User, Secrets};
-use hmac::{digest::KeyInit, Hmac};
-use jwt::{SignWithKey, VerifyWithKey};
-use sha2::Sha256;
-use std::collections::BTreeMap;
+use super::{claims::Claims, error::AuthError, keys::JwtKeys};
+use jsonwebtoken::{decode, decode_header, encode, Header, Validation};
+use rocket::serde::json::Value;
+use std::collections::{BTreeMap, HashSet};
 
-pub fn create_token(secrets: &Secrets, user: &User) -> Result<String, AuthError> {
-    let key: Hmac<Sha256> = Hmac::new_from_slice(secrets.api_secret.as_bytes()).unwrap();
-    let claims: BTreeMap<String, String> = Claims::try_from(user.clone())?.into();
-    Ok(claims.sign_with_key(&key)?)
+pub fn create_token(keys: &JwtKeys, claims: &Claims) -> Result<String, AuthError> {
+    let mut header = Header::new(keys.signing.algorithm);
+    header.kid = Some(keys.signing.kid.clone());
+    let claims: BTreeMap<String, Value> = claims.clone().into();
+    encode(&header, &claims, &keys.signing.key).map_err(jwt_error)
 }
 
-pub fn validate_token(secrets: &Secrets, token: &str) -> Result<Claims, AuthError> {
-    let key: Hmac<Sha256> = Hmac::new_from_slice(secrets.api_secret.as_bytes()).unwrap();
-    let token_data: BTreeMap<String, String> = token.verify_with_key(&key)?;
+pub fn validate_token(keys: &JwtKeys, token: &str) -> Result<Claims, AuthError> {
+    let header = decode_header(token).map_err(jwt_error)?;
+    let kid = header
+        .kid
+        .ok_or(AuthError::MissingClaim("kid".to_string()))?;
+    let key = keys.get(&kid).ok_or(AuthError::UnknownKey(kid))?;
+    if header.alg != key.algorithm {
+        return Err(AuthError::InvalidFormat("alg".to_string()));
+    }
+    // Only the signature is checked here, the claims are validated by `Claims`.
+    let mut validation = Validation::new(key.algorithm);
+    validation.required_spec_claims = HashSet::new();
+    validation.validate_exp = false;
+    validation.validate_nbf = false;
+    validation.validate_aud = false;
+    let token_data =
+        decode::<BTreeMap<String, Value>>(token, &key.key, &validation).map_err(jwt_error)?;
 
-    Claims::try_from(token_data)
+    Claims::try_from(token_data.claims)
+}
+
+fn jwt_error(e: jsonwebtoken::errors::Error) -> AuthError {
+    AuthError::JwtError(e.to_string())
 }
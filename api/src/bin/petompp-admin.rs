@@ -0,0 +1,276 @@
+use clap::{Parser, Subcommand};
+use diesel::{migration::MigrationSource, Connection};
+use diesel_migrations::MigrationHarness;
+use petompp_web_api::{
+    config::AppConfig,
+    get_connection_pool,
+    models::{password::Password, resource_data::ResourceData, role::Role, user::User},
+    repositories::{
+        resources::repo::ResourcesRepo,
+        transaction::{atomically, UnitOfWork},
+        user::repo::UserRepo,
+    },
+    Db, DbConnection, DbPool, MIGRATIONS,
+};
+use rocket::serde::json::serde_json;
+use std::{collections::HashSet, error::Error, fs, io::BufRead, str::FromStr};
+
+/// Maintenance tasks for the petompp-web API.
+/// Reads the same configuration as the API and refuses to run when it is invalid.
+#[derive(Parser)]
+#[command(name = "petompp-admin")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Manage the users
+    #[command(subcommand)]
+    User(UserCommand),
+    /// Manage the database migrations
+    #[command(subcommand)]
+    Migrations(MigrationsCommand),
+    /// Export or import the resources
+    #[command(subcommand)]
+    Resources(ResourcesCommand),
+}
+
+#[derive(Subcommand)]
+enum UserCommand {
+    /// Create an activated user, the password is read from stdin when not provided
+    Create {
+        name: String,
+        #[arg(long)]
+        password: Option<String>,
+        #[arg(long, default_value = "User")]
+        role: String,
+    },
+    /// Change the user's role
+    Promote {
+        name: String,
+        #[arg(long, default_value = "Admin")]
+        role: String,
+    },
+    /// Set a new password, read from stdin when not provided
+    ResetPassword {
+        name: String,
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Permanently remove users deleted at least `older_than_days` ago,
+    /// the configured `deletion_grace_days` by default
+    Purge {
+        #[arg(long)]
+        older_than_days: Option<i64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrationsCommand {
+    /// List all migrations with their state
+    List,
+    /// Apply the pending migrations
+    Apply,
+    /// Revert the last applied migration
+    Revert,
+}
+
+#[derive(Subcommand)]
+enum ResourcesCommand {
+    /// Write all resources as JSON to the file or stdout
+    Export {
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Read resources from a JSON file, existing keys are skipped unless `overwrite` is set
+    Import {
+        file: String,
+        #[arg(long)]
+        overwrite: bool,
+    },
+}
+
+type CliResult = Result<(), Box<dyn Error + Send + Sync>>;
+
+#[rocket::main]
+async fn main() {
+    let cli = Cli::parse();
+    let config = match AppConfig::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let result = match cli.command {
+        Command::User(command) => match get_pool(&config.database_url) {
+            Ok(pool) => run_user(command, &pool, &config).await,
+            Err(e) => Err(e),
+        },
+        Command::Migrations(command) => run_migrations(command, &config.database_url),
+        Command::Resources(command) => match get_pool(&config.database_url) {
+            Ok(pool) => run_resources(command, &pool, &pool).await,
+            Err(e) => Err(e),
+        },
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn get_pool(database_url: &str) -> Result<DbPool, Box<dyn Error + Send + Sync>> {
+    let mut conn = DbConnection::establish(database_url)?;
+    if !conn
+        .pending_migrations(MIGRATIONS)
+        .unwrap_or_default()
+        .is_empty()
+    {
+        eprintln!("Warning: there are pending migrations, run `migrations apply` first.");
+    }
+    Ok(get_connection_pool(database_url))
+}
+
+async fn run_user(command: UserCommand, pool: &dyn UserRepo, config: &AppConfig) -> CliResult {
+    let requirements = &config.password;
+    match command {
+        UserCommand::Create {
+            name,
+            password,
+            role,
+        } => {
+            let password = password.map_or_else(read_password, Ok)?;
+            let mut user = User::new(name, password, parse_role(&role)?, requirements)?;
+            user.confirmed = true;
+            let user = pool.create(&user).await?;
+            println!("Created user {} ({})", *user.name, user.role);
+        }
+        UserCommand::Promote { name, role } => {
+            let user = pool.get_by_name(name.to_lowercase()).await?;
+            let user = pool
+                .set_role(user.id.unwrap_or_default(), parse_role(&role)?)
+                .await?;
+            println!("User {} is now {}", *user.name, user.role);
+        }
+        UserCommand::ResetPassword { name, password } => {
+            let user = pool.get_by_name(name.to_lowercase()).await?;
+            let password = password.map_or_else(read_password, Ok)?;
+            let password = Password::new(password, requirements)?;
+            let user = pool
+                .set_password(user.id.unwrap_or_default(), &password)
+                .await?;
+            println!("Password of {} has been reset", *user.name);
+        }
+        UserCommand::Purge { older_than_days } => {
+            // The same grace period the API waits before purging.
+            let older_than_days = older_than_days.unwrap_or(config.deletion_grace_days);
+            let deleted_before =
+                chrono::Utc::now().naive_utc() - chrono::Duration::days(older_than_days);
+            let purged = pool.purge_deleted(deleted_before).await?;
+            println!("Purged {} users", purged);
+        }
+    }
+    Ok(())
+}
+
+fn run_migrations(command: MigrationsCommand, database_url: &str) -> CliResult {
+    let mut conn = DbConnection::establish(database_url)?;
+    match command {
+        MigrationsCommand::List => {
+            let applied = conn
+                .applied_migrations()?
+                .into_iter()
+                .map(|v| v.to_string())
+                .collect::<HashSet<_>>();
+            for migration in MigrationSource::<Db>::migrations(&MIGRATIONS)? {
+                let name = migration.name();
+                let state = match applied.contains(&name.version().to_string()) {
+                    true => "applied",
+                    false => "pending",
+                };
+                println!("[{}] {}", state, name);
+            }
+        }
+        MigrationsCommand::Apply => {
+            let applied = conn.run_pending_migrations(MIGRATIONS)?;
+            println!("Applied {} migrations", applied.len());
+            for version in applied {
+                println!("  {}", version);
+            }
+        }
+        MigrationsCommand::Revert => {
+            let version = conn.revert_last_migration(MIGRATIONS)?;
+            println!("Reverted {}", version);
+        }
+    }
+    Ok(())
+}
+
+async fn run_resources(
+    command: ResourcesCommand,
+    pool: &dyn ResourcesRepo,
+    unit: &dyn UnitOfWork,
+) -> CliResult {
+    match command {
+        ResourcesCommand::Export { output } => {
+            let resources = pool.get_all().await?;
+            let json = serde_json::to_string_pretty(&resources)?;
+            match output {
+                Some(path) => {
+                    fs::write(&path, json)?;
+                    eprintln!("Exported {} resources to {}", resources.len(), path);
+                }
+                None => println!("{}", json),
+            }
+        }
+        ResourcesCommand::Import { file, overwrite } => {
+            let resources: Vec<ResourceData> = serde_json::from_str(&fs::read_to_string(file)?)?;
+            let existing = pool
+                .get_all()
+                .await?
+                .into_iter()
+                .filter_map(|r| r.key)
+                .collect::<HashSet<_>>();
+            let total = resources.len();
+            let (created, updated): (Vec<_>, Vec<_>) = resources
+                .into_iter()
+                .filter(|r| match &r.key {
+                    Some(key) => overwrite || !existing.contains(key),
+                    None => false,
+                })
+                .partition(|r| r.key.as_ref().is_some_and(|k| !existing.contains(k)));
+            // Nothing is imported when any of the resources fails.
+            let (created, updated) = atomically(unit, move |repos| {
+                Box::pin(async move {
+                    for resource in &created {
+                        repos.resources.create(resource).await?;
+                    }
+                    for resource in &updated {
+                        repos.resources.update(resource).await?;
+                    }
+                    Ok((created.len(), updated.len()))
+                })
+            })
+            .await?;
+            let skipped = total - created - updated;
+            println!(
+                "Created {}, updated {}, skipped {} resources",
+                created, updated, skipped
+            );
+        }
+    }
+    Ok(())
+}
+
+fn parse_role(role: &str) -> Result<Role, Box<dyn Error + Send + Sync>> {
+    Role::from_str(role).map_err(|_| format!("Unknown role {}, use User or Admin", role).into())
+}
+
+fn read_password() -> Result<String, Box<dyn Error + Send + Sync>> {
+    eprint!("Password: ");
+    let mut password = String::new();
+    std::io::stdin().lock().read_line(&mut password)?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
@@ -0,0 +1,270 @@
+use crate::{
+    auth::cookie::AuthMode,
+    models::{password::PasswordRequirements, registration::RegistrationPolicy, sitemap::LANGS},
+    services::azure_blob::AzureBlobSecrets,
+};
+use rocket::figment::{providers::Env, Figment};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt::Display;
+
+/// The whole API configuration.
+/// Values come from `Rocket.toml` (selected by `ROCKET_PROFILE`), `ROCKET_` env variables
+/// and `PETOMPP_` env variables, where `__` separates the nested keys,
+/// e.g. `PETOMPP_JWT__SIGNING_KEY=/keys/2023-09.pem`.
+/// `DATABASE_URL` is also accepted, as it is shared with the diesel CLI.
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub database_url: String,
+    pub jwt: JwtConfig,
+    pub password: PasswordRequirements,
+    pub storage: StorageConfig,
+    pub cors_origins: Vec<String>,
+    pub registration: RegistrationPolicy,
+    pub auth_mode: AuthMode,
+    /// Days a deleted account is kept before it is purged.
+    pub deletion_grace_days: i64,
+    /// `Cache-Control` of the public resource reads.
+    pub resources_cache_control: String,
+    pub resources_cache: ResourcesCacheConfig,
+    /// Origin the front is served at, the sitemap links to it.
+    pub site_url: String,
+    /// Languages the front prerenders its pages in, the only ones in the sitemap.
+    pub site_langs: Vec<String>,
+    /// Public URL the API is reached at, the feeds link to themselves with it.
+    pub api_url: String,
+    pub robots: RobotsConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct JwtConfig {
+    pub signing_key_id: String,
+    /// Path to the PEM private key.
+    pub signing_key: String,
+    pub verification_keys: Vec<VerificationKeyConfig>,
+    /// Access token lifetime, in seconds.
+    pub token_lifetime: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationKeyConfig {
+    pub kid: String,
+    /// Path to the PEM public key.
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourcesCacheConfig {
+    /// Values kept at most, the cache is disabled with 0.
+    pub capacity: usize,
+    /// Seconds a value is served from the cache.
+    pub ttl: u64,
+}
+
+impl Default for ResourcesCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 256,
+            ttl: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RobotsConfig {
+    /// Whether the crawlers are let in, turned off for the staging sites.
+    pub indexed: bool,
+    /// Paths kept from the crawlers next to the admin and editor ones.
+    pub disallow: Vec<String>,
+}
+
+impl Default for RobotsConfig {
+    fn default() -> Self {
+        Self {
+            indexed: true,
+            disallow: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum StorageConfig {
+    Azure(AzureBlobSecrets),
+}
+
+/// Every missing or invalid configuration field.
+#[derive(Debug)]
+pub struct ConfigError(pub Vec<String>);
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Invalid configuration:")?;
+        for error in &self.0 {
+            writeln!(f, "  - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl AppConfig {
+    pub const DEFAULT_TOKEN_LIFETIME: u64 = 60 * 60;
+    pub const DEFAULT_DELETION_GRACE_DAYS: i64 = 30;
+    pub const DEFAULT_RESOURCES_CACHE_CONTROL: &'static str = "public, no-cache";
+    pub const DEFAULT_SITE_URL: &'static str = "http://localhost:8080";
+    pub const DEFAULT_API_URL: &'static str = "http://localhost:16969";
+
+    pub fn figment() -> Figment {
+        rocket::Config::figment()
+            .merge(Env::raw().only(&["DATABASE_URL"]).global())
+            .merge(Env::prefixed("PETOMPP_").split("__").global())
+    }
+
+    pub fn load() -> Result<Self, ConfigError> {
+        Self::from_figment(&Self::figment())
+    }
+
+    /// Reads every field separately, so all problems are reported at once.
+    pub fn from_figment(figment: &Figment) -> Result<Self, ConfigError> {
+        let mut loader = Loader {
+            figment,
+            errors: Vec::new(),
+        };
+        let database_url = loader.required::<String>("database_url");
+        let signing_key_id = loader.required::<String>("jwt.signing_key_id");
+        let signing_key = loader.required::<String>("jwt.signing_key");
+        let verification_keys =
+            loader.required::<Vec<VerificationKeyConfig>>("jwt.verification_keys");
+        let token_lifetime = loader.optional("jwt.token_lifetime", Self::DEFAULT_TOKEN_LIFETIME);
+        let password = loader.optional("password", PasswordRequirements::default());
+        let storage = loader.required::<StorageConfig>("storage");
+        let cors_origins = loader.optional("cors_origins", Vec::<String>::new());
+        let registration = loader.optional("registration", RegistrationPolicy::default());
+        let auth_mode = loader.optional("auth_mode", AuthMode::default());
+        let deletion_grace_days =
+            loader.optional("deletion_grace_days", Self::DEFAULT_DELETION_GRACE_DAYS);
+        let resources_cache = loader.optional("resources_cache", ResourcesCacheConfig::default());
+        let resources_cache_control = loader.optional(
+            "resources_cache_control",
+            Self::DEFAULT_RESOURCES_CACHE_CONTROL.to_string(),
+        );
+        let site_url = loader.optional("site_url", Self::DEFAULT_SITE_URL.to_string());
+        let site_langs = loader.optional("site_langs", vec![LANGS[0].to_string()]);
+        let api_url = loader.optional("api_url", Self::DEFAULT_API_URL.to_string());
+        let robots = loader.optional("robots", RobotsConfig::default());
+
+        if token_lifetime == 0 {
+            loader.invalid("jwt.token_lifetime", "must be greater than 0");
+        }
+        if let (Some(kid), Some(keys)) = (&signing_key_id, &verification_keys) {
+            if !keys.iter().any(|k| &k.kid == kid) {
+                loader.invalid(
+                    "jwt.verification_keys",
+                    &format!("must contain the signing key `{}`", kid),
+                );
+            }
+        }
+        if deletion_grace_days < 0 {
+            loader.invalid("deletion_grace_days", "must not be negative");
+        }
+        if password.min_length < 1 {
+            loader.invalid("password.min_length", "must be greater than 0");
+        }
+        if !(0..=4).contains(&password.passes_required) {
+            loader.invalid("password.passes_required", "must be between 0 and 4");
+        }
+        if let Some(origin) = cors_origins
+            .iter()
+            .find(|o| !o.starts_with("http://") && !o.starts_with("https://"))
+        {
+            loader.invalid("cors_origins", &format!("`{}` is not an origin", origin));
+        }
+        if !site_url.starts_with("http://") && !site_url.starts_with("https://") {
+            loader.invalid("site_url", &format!("`{}` is not an origin", site_url));
+        }
+        if !api_url.starts_with("http://") && !api_url.starts_with("https://") {
+            loader.invalid("api_url", &format!("`{}` is not a URL", api_url));
+        }
+        if site_langs.is_empty() {
+            loader.invalid("site_langs", "must not be empty");
+        }
+        if let Some(lang) = site_langs.iter().find(|l| !LANGS.contains(&l.as_str())) {
+            loader.invalid("site_langs", &format!("`{}` is not a language", lang));
+        }
+        if let Some(path) = robots.disallow.iter().find(|p| !p.starts_with('/')) {
+            loader.invalid("robots.disallow", &format!("`{}` is not a path", path));
+        }
+
+        match (
+            database_url,
+            signing_key_id,
+            signing_key,
+            verification_keys,
+            storage,
+        ) {
+            (
+                Some(database_url),
+                Some(signing_key_id),
+                Some(signing_key),
+                Some(verification_keys),
+                Some(storage),
+            ) if loader.errors.is_empty() => Ok(Self {
+                database_url,
+                jwt: JwtConfig {
+                    signing_key_id,
+                    signing_key,
+                    verification_keys,
+                    token_lifetime,
+                },
+                password,
+                storage,
+                cors_origins,
+                registration,
+                auth_mode,
+                deletion_grace_days,
+                resources_cache_control,
+                resources_cache,
+                site_url,
+                site_langs,
+                api_url,
+                robots,
+            }),
+            _ => Err(ConfigError(loader.errors)),
+        }
+    }
+}
+
+struct Loader<'a> {
+    figment: &'a Figment,
+    errors: Vec<String>,
+}
+
+impl Loader<'_> {
+    fn required<T: DeserializeOwned>(&mut self, key: &str) -> Option<T> {
+        if self.figment.find_value(key).is_err() {
+            self.errors.push(format!("`{}` is missing", key));
+            return None;
+        }
+        match self.figment.extract_inner(key) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.invalid(key, &e.to_string());
+                None
+            }
+        }
+    }
+
+    fn optional<T: DeserializeOwned>(&mut self, key: &str, default: T) -> T {
+        if self.figment.find_value(key).is_err() {
+            return default;
+        }
+        self.required(key).unwrap_or(default)
+    }
+
+    fn invalid(&mut self, key: &str, reason: &str) {
+        self.errors.push(format!("`{}` is invalid: {}", key, reason));
+    }
+}
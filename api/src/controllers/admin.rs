@@ -0,0 +1,277 @@
+use super::{
+    controller::Controller,
+    response::{ApiResponse, Format, Negotiated},
+};
+use crate::{
+    auth::claims::AdminClaims,
+    error::{ApiError, Error, ResourceDataValidationError, ValidationError},
+    models::{
+        bundle::{Bundle, BundleBlob, ConflictStrategy, ImportSummary, Resolution, BUNDLE_VERSION},
+        resource_data::ResourceData,
+    },
+    repositories::resources::{cache::CacheMetrics, repo::ResourcesRepo},
+    services::blob_store::BlobStore,
+    state::AppState,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use regex::Regex;
+use rocket::{
+    data::{self, FromData, ToByteUnit},
+    get,
+    http::Status,
+    outcome::Outcome,
+    post, routes, Data, Request, State,
+};
+use std::collections::{BTreeSet, HashMap};
+
+pub struct AdminController;
+
+impl Controller for AdminController {
+    fn path(&self) -> &'static str {
+        "/admin"
+    }
+
+    fn routes(&self) -> Vec<rocket::Route> {
+        routes![export, import, cache]
+    }
+}
+
+#[utoipa::path(
+    operation_id = "export_bundle",
+    context_path = "/api/v1/admin",
+    tag = "admin",
+    responses(
+        (status = 200, description = "The content bundle", body = BundleResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 500, description = "`Status`, `DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[get("/export")]
+async fn export<'a>(
+    _claims: AdminClaims,
+    pool: &dyn ResourcesRepo,
+    blob_service: &dyn BlobStore,
+) -> Result<Negotiated<ApiResponse<'a, Bundle>>, ApiError<'a>> {
+    let resources = pool.get_all().await?;
+    let storage_url = blob_service.url();
+    let pattern = Regex::new(&format!(r"{}([\w\-./]+)", regex::escape(&storage_url))).unwrap();
+    let paths = resources
+        .iter()
+        .flat_map(|r| [&r.en, &r.pl])
+        .flatten()
+        .flat_map(|text| {
+            pattern
+                .captures_iter(text)
+                .map(|c| c[1].trim_end_matches('.').to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect::<BTreeSet<_>>();
+    let mut blobs = Vec::new();
+    let mut missing_blobs = Vec::new();
+    for path in paths {
+        match blob_service.download(path.clone()).await {
+            Ok((data, content_type)) => blobs.push(BundleBlob {
+                path,
+                content_type,
+                data: STANDARD.encode(data),
+            }),
+            // A stale link doesn't cost the whole export.
+            Err(Error::Status(404, _)) => missing_blobs.push(path),
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(Negotiated(ApiResponse::ok(Bundle {
+        version: BUNDLE_VERSION,
+        created_at: chrono::Utc::now().naive_utc(),
+        storage_url,
+        resources,
+        blobs,
+        missing_blobs,
+    })))
+}
+
+#[utoipa::path(
+    operation_id = "import_bundle",
+    context_path = "/api/v1/admin",
+    tag = "admin",
+    request_body = Bundle,
+    responses(
+        (status = 200, description = "What was, or would be with `dry_run`, changed", body = ImportSummaryResponse),
+        (status = 400, description = "`BundleInvalid`, `BundleVersionUnsupported`, `ValidationError`", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 413, description = "`Status`", body = ErrorResponse),
+        (status = 415, description = "`Status`", body = ErrorResponse),
+        (status = 500, description = "`Status`, `DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[post("/import?<dry_run>&<strategy>", data = "<data>")]
+async fn import<'a>(
+    _claims: AdminClaims,
+    dry_run: Option<bool>,
+    strategy: Option<ConflictStrategy>,
+    pool: &dyn ResourcesRepo,
+    blob_service: &dyn BlobStore,
+    state: &State<AppState>,
+    data: Result<BundleData, Error>,
+) -> Result<Negotiated<ApiResponse<'a, ImportSummary>>, ApiError<'a>> {
+    let dry_run = dry_run.unwrap_or_default();
+    let strategy = strategy.unwrap_or_default();
+    let BundleData(bundle) = data?;
+    if bundle.version != BUNDLE_VERSION {
+        return Err(Error::BundleVersionUnsupported(bundle.version).into());
+    }
+    // Everything is validated before the first write.
+    let blobs = bundle
+        .blobs
+        .into_iter()
+        .map(|b| {
+            if b.path.split('/').any(|p| p.is_empty() || p == "..") {
+                return Err(Error::BundleInvalid(b.path));
+            }
+            match STANDARD.decode(&b.data) {
+                Ok(data) => Ok((b.path, b.content_type, data)),
+                Err(_) => Err(Error::BundleInvalid(b.path)),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let storage_url = blob_service.url();
+    let rewrite = |text: Option<String>| match bundle.storage_url.is_empty() {
+        true => text,
+        false => text.map(|t| t.replace(&bundle.storage_url, &storage_url)),
+    };
+    let mut resources = Vec::new();
+    for resource in bundle.resources {
+        let Some(key) = resource.key else {
+            return Err(Error::ValidationError(ValidationError::ResourceData(
+                ResourceDataValidationError::KeyMissing,
+            ))
+            .into());
+        };
+        resources.push(ResourceData {
+            key: Some(key),
+            en: rewrite(resource.en),
+            pl: rewrite(resource.pl),
+            updated_at: None,
+        });
+    }
+    let summary = ImportSummary {
+        dry_run,
+        blobs: blobs.len(),
+        ..Default::default()
+    };
+    if dry_run {
+        let (summary, _) = resolve(strategy, pool.get_all().await?, resources, summary)?;
+        return Ok(Negotiated(ApiResponse::ok(summary)));
+    }
+    // Either every resource is written or none of them,
+    // the conflicts are resolved against what the transaction sees.
+    let summary = state
+        .transaction(move |repos| {
+            Box::pin(async move {
+                let existing = repos.resources.get_all().await?;
+                let (summary, changes) = resolve(strategy, existing, resources, summary)?;
+                for change in changes {
+                    match change {
+                        Resolution::Create(r) => repos.resources.create(&r).await?,
+                        Resolution::Update(r) => repos.resources.update(&r).await?,
+                        Resolution::Skip => continue,
+                    };
+                }
+                Ok(summary)
+            })
+        })
+        .await?;
+    // Uploaded only after the commit, a rolled back import leaves no blobs behind.
+    for (path, content_type, data) in blobs {
+        blob_service.upload_to(path, data, content_type).await?;
+    }
+    Ok(Negotiated(ApiResponse::ok(summary)))
+}
+
+/// Decides what happens with each of the `resources`, adding them to the `summary`.
+fn resolve(
+    strategy: ConflictStrategy,
+    existing: Vec<ResourceData>,
+    resources: Vec<ResourceData>,
+    mut summary: ImportSummary,
+) -> Result<(ImportSummary, Vec<Resolution>), Error> {
+    let existing = existing
+        .into_iter()
+        .filter_map(|r| Some((r.key.clone()?, r)))
+        .collect::<HashMap<_, _>>();
+    let mut changes = Vec::new();
+    for resource in resources {
+        let key = resource.key.clone().unwrap_or_default();
+        match strategy.resolve(existing.get(&key), resource) {
+            Resolution::Create(r) if r.en.is_none() => {
+                return Err(Error::ValidationError(ValidationError::ResourceData(
+                    ResourceDataValidationError::ValueMissing,
+                )));
+            }
+            Resolution::Create(r) => {
+                summary.created.push(key);
+                changes.push(Resolution::Create(r));
+            }
+            Resolution::Update(r) => {
+                summary.updated.push(key);
+                changes.push(Resolution::Update(r));
+            }
+            Resolution::Skip => summary.skipped.push(key),
+        }
+    }
+    Ok((summary, changes))
+}
+
+/// [`Bundle`] in the format of the `Content-Type`, read up to the `bundle` limit.
+struct BundleData(Bundle);
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for BundleData {
+    type Error = Error;
+
+    async fn from_data(request: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let Some(format) = Format::of_body(request) else {
+            return Outcome::Failure((
+                Status::UnsupportedMediaType,
+                Status::UnsupportedMediaType.into(),
+            ));
+        };
+        let limit = request.limits().get("bundle").unwrap_or(64.mebibytes());
+        let bytes = match data.open(limit).into_bytes().await {
+            Ok(bytes) if bytes.is_complete() => bytes.into_inner(),
+            Ok(_) => {
+                return Outcome::Failure((Status::PayloadTooLarge, Status::PayloadTooLarge.into()))
+            }
+            Err(e) => {
+                return Outcome::Failure((Status::BadRequest, Error::Status(400, e.to_string())))
+            }
+        };
+        match format.deserialize(&bytes) {
+            Ok(bundle) => Outcome::Success(BundleData(bundle)),
+            Err(e) => Outcome::Failure((Status::BadRequest, Error::BundleInvalid(e))),
+        }
+    }
+}
+
+#[utoipa::path(
+    operation_id = "resources_cache_metrics",
+    context_path = "/api/v1/admin",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Hits and misses of the resources cache, `null` when it is disabled", body = CacheMetricsResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[get("/cache")]
+async fn cache<'a>(
+    _claims: AdminClaims,
+    pool: &dyn ResourcesRepo,
+) -> Negotiated<ApiResponse<'a, Option<CacheMetrics>>> {
+    Negotiated(ApiResponse::ok(pool.cache_metrics()))
+}
@@ -0,0 +1,276 @@
+use super::{
+    controller::Controller,
+    response::{ApiResponse, Negotiated},
+};
+use crate::{
+    auth::claims::{AdminClaims, Claims},
+    error::{ApiError, CommentValidationError, Error, ValidationError},
+    models::{
+        comment::{Comment, CommentRequest, CommentStatus, ModerationAction, ThreadComment},
+        role::Role,
+    },
+    repositories::{
+        comment::repo::CommentRepo, resources::repo::ResourcesRepo, user::repo::UserRepo,
+    },
+};
+use rocket::{delete, get, http::Status, post, put, routes};
+
+pub struct CommentsController;
+
+impl Controller for CommentsController {
+    fn path(&self) -> &'static str {
+        "/comments"
+    }
+
+    fn routes(&self) -> Vec<rocket::Route> {
+        routes![get_thread, create, update, delete, get_queue, moderate]
+    }
+}
+
+#[utoipa::path(
+    operation_id = "get_comments",
+    context_path = "/api/v1/comments",
+    tag = "comments",
+    responses(
+        (status = 200, description = "Approved comments on the page, oldest first, with the pending ones of the user when a valid token is sent", body = CommentsResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    )
+)]
+#[get("/<key>")]
+async fn get_thread<'a>(
+    claims: Option<Claims>,
+    key: &'a str,
+    pool: &'a dyn CommentRepo,
+) -> Result<Negotiated<ApiResponse<'a, Vec<ThreadComment>>>, ApiError<'a>> {
+    let comments = pool
+        .get_thread(key, claims.map(|c| c.sub))
+        .await?
+        .into_iter()
+        .map(|(comment, author)| ThreadComment::new(comment, author))
+        .collect();
+    Ok(Negotiated(ApiResponse::ok(comments)))
+}
+
+#[utoipa::path(
+    operation_id = "create_comment",
+    context_path = "/api/v1/comments",
+    tag = "comments",
+    request_body = CommentRequest,
+    responses(
+        (status = 200, description = "The posted comment, approved right away only for the admins", body = CommentResponse),
+        (status = 400, description = "`ValidationError`", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 402, description = "`UserNotConfirmed`", body = ErrorResponse),
+        (status = 403, description = "`CommentingBanned`, invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "No resource with the key, `UserNotFound`, `Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[post("/<key>", data = "<request>")]
+async fn create<'a>(
+    claims: Claims,
+    key: &'a str,
+    request: Negotiated<CommentRequest>,
+    pool: &'a dyn CommentRepo,
+    users: &'a dyn UserRepo,
+    resources: &'a dyn ResourcesRepo,
+) -> Result<Negotiated<ApiResponse<'a, ThreadComment>>, ApiError<'a>> {
+    let user = users.get_by_id(claims.sub).await?;
+    if !user.confirmed {
+        return Err(Error::UserNotConfirmed(user.name.to_string()).into());
+    }
+    if pool.is_banned(claims.sub).await? {
+        return Err(Error::CommentingBanned.into());
+    }
+    if !resources.exists(key).await? {
+        return Err(Error::from(Status::NotFound).into());
+    }
+    let request = request.into_inner();
+    let comment = Comment {
+        key: key.to_string(),
+        parent_id: request.parent_id,
+        user_id: claims.sub,
+        body: Comment::validate_body(&request)?,
+        status: match claims.acs {
+            Role::Admin => CommentStatus::Approved,
+            Role::User => CommentStatus::Pending,
+        },
+        ..Default::default()
+    };
+    if let Some(parent_id) = comment.parent_id {
+        let parent = match pool.get(parent_id).await {
+            Ok(parent) => Some(parent),
+            Err(Error::CommentNotFound(_)) => None,
+            Err(e) => return Err(e.into()),
+        };
+        if !parent.is_some_and(|p| comment.can_reply_to(&p)) {
+            return Err(Error::ValidationError(ValidationError::Comment(
+                CommentValidationError::ParentInvalid(parent_id),
+            ))
+            .into());
+        }
+    }
+    let comment = pool.create(&comment).await?;
+    Ok(Negotiated(ApiResponse::ok(ThreadComment::new(
+        comment, user.name,
+    ))))
+}
+
+#[utoipa::path(
+    operation_id = "update_comment",
+    context_path = "/api/v1/comments",
+    tag = "comments",
+    request_body = CommentRequest,
+    responses(
+        (status = 200, description = "The edited comment, moderated again unless edited by an admin", body = CommentResponse),
+        (status = 400, description = "`ValidationError`", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not the author, `CommentEditWindowClosed`, `CommentingBanned`, invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "`CommentNotFound`, `UserNotFound`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[put("/<id>", data = "<request>")]
+async fn update<'a>(
+    claims: Claims,
+    id: i32,
+    request: Negotiated<CommentRequest>,
+    pool: &'a dyn CommentRepo,
+    users: &'a dyn UserRepo,
+) -> Result<Negotiated<ApiResponse<'a, ThreadComment>>, ApiError<'a>> {
+    let comment = pool.get(id).await?;
+    if comment.user_id != claims.sub {
+        return Err(Error::from(Status::Forbidden).into());
+    }
+    let now = chrono::Utc::now().naive_utc();
+    if !comment.is_editable(now) {
+        return Err(Error::CommentEditWindowClosed(Comment::EDIT_WINDOW_MINUTES).into());
+    }
+    if pool.is_banned(claims.sub).await? {
+        return Err(Error::CommentingBanned.into());
+    }
+    let status = match (claims.acs, comment.status) {
+        (Role::User, CommentStatus::Approved) => CommentStatus::Pending,
+        (_, status) => status,
+    };
+    let comment = pool
+        .update(&Comment {
+            body: Comment::validate_body(&request)?,
+            status,
+            edited_at: Some(now),
+            ..comment
+        })
+        .await?;
+    let author = users.get_by_id(claims.sub).await?.name;
+    Ok(Negotiated(ApiResponse::ok(ThreadComment::new(
+        comment, author,
+    ))))
+}
+
+#[utoipa::path(
+    operation_id = "delete_comment",
+    context_path = "/api/v1/comments",
+    tag = "comments",
+    responses(
+        (status = 200, description = "The deleted comment, it stays in the thread without the content", body = CommentResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Neither the author nor an admin, invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "`CommentNotFound`, `UserNotFound`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[delete("/<id>")]
+async fn delete<'a>(
+    claims: Claims,
+    id: i32,
+    pool: &'a dyn CommentRepo,
+    users: &'a dyn UserRepo,
+) -> Result<Negotiated<ApiResponse<'a, ThreadComment>>, ApiError<'a>> {
+    let comment = pool.get(id).await?;
+    if comment.user_id != claims.sub && claims.acs != Role::Admin {
+        return Err(Error::from(Status::Forbidden).into());
+    }
+    let comment = match comment.is_deleted() {
+        true => comment,
+        false => {
+            pool.update(&Comment {
+                deleted_at: Some(chrono::Utc::now().naive_utc()),
+                ..comment
+            })
+            .await?
+        }
+    };
+    let author = users.get_by_id(comment.user_id).await?.name;
+    Ok(Negotiated(ApiResponse::ok(ThreadComment::new(
+        comment, author,
+    ))))
+}
+
+#[utoipa::path(
+    operation_id = "get_comment_queue",
+    context_path = "/api/v1/comments",
+    tag = "comments",
+    responses(
+        (status = 200, description = "Comments waiting for the moderation, oldest first", body = CommentsResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[get("/moderation/queue")]
+async fn get_queue<'a>(
+    _claims: AdminClaims,
+    pool: &'a dyn CommentRepo,
+) -> Result<Negotiated<ApiResponse<'a, Vec<ThreadComment>>>, ApiError<'a>> {
+    let comments = pool
+        .get_pending()
+        .await?
+        .into_iter()
+        .map(|(comment, author)| ThreadComment::new(comment, author))
+        .collect();
+    Ok(Negotiated(ApiResponse::ok(comments)))
+}
+
+#[utoipa::path(
+    operation_id = "moderate_comment",
+    context_path = "/api/v1/comments",
+    tag = "comments",
+    params(
+        ("action" = ModerationAction, Query, description = "`approve`, `reject`, or `ban`, which also rejects the comment and every pending one of its author")
+    ),
+    responses(
+        (status = 200, description = "The moderated comment", body = CommentResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "`CommentNotFound`, `UserNotFound`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[post("/<id>/moderate?<action>")]
+async fn moderate<'a>(
+    claims: AdminClaims,
+    id: i32,
+    action: ModerationAction,
+    pool: &'a dyn CommentRepo,
+    users: &'a dyn UserRepo,
+) -> Result<Negotiated<ApiResponse<'a, ThreadComment>>, ApiError<'a>> {
+    let comment = pool.get(id).await?;
+    let status = match action {
+        ModerationAction::Approve => CommentStatus::Approved,
+        ModerationAction::Reject => CommentStatus::Rejected,
+        ModerationAction::Ban => {
+            pool.ban(comment.user_id, claims.sub).await?;
+            CommentStatus::Rejected
+        }
+    };
+    let comment = pool.update(&Comment { status, ..comment }).await?;
+    let author = users.get_by_id(comment.user_id).await?.name;
+    Ok(Negotiated(ApiResponse::ok(ThreadComment::new(
+        comment, author,
+    ))))
+}
@@ -3,6 +3,26 @@ use rocket::{Build, Rocket};
 pub trait Controller {
     fn path(&self) -> &'static str;
     fn routes(&self) -> Vec<rocket::Route>;
+
+    /// Path the routes are mounted at, under the API prefix.
+    fn mount_path(&self) -> String {
+        const PATH: &str = "/api/v1";
+        let path = self.path();
+        match path.starts_with('/') {
+            true => format!("{}{}", PATH, path),
+            false => format!("{}/{}", PATH, path),
+        }
+    }
+}
+
+impl<C: Controller + ?Sized> Controller for Box<C> {
+    fn path(&self) -> &'static str {
+        (**self).path()
+    }
+
+    fn routes(&self) -> Vec<rocket::Route> {
+        (**self).routes()
+    }
 }
 
 pub trait ControllerRegisterer {
@@ -11,12 +31,6 @@ pub trait ControllerRegisterer {
 
 impl ControllerRegisterer for Rocket<Build> {
     fn add(self, controller: impl Controller) -> Self {
-        const PATH: &str = "/api/v1";
-        let path = controller.path();
-        let path = match path.starts_with('/') {
-            true => format!("{}{}", PATH, path),
-            false => format!("{}/{}", PATH, path),
-        };
-        self.mount(path, controller.routes())
+        self.mount(controller.mount_path(), controller.routes())
     }
 }
@@ -0,0 +1,89 @@
+use super::{
+    controller::Controller,
+    response::{Cached, Document},
+};
+use crate::{
+    config::AppConfig,
+    error::{ApiError, Error},
+    models::{
+        feed::{self as feeds, Feed, FeedEntry, FeedFormat},
+        sitemap::{LANGS, PUBLIC_PAGES},
+    },
+    repositories::resources::repo::ResourcesRepo,
+};
+use rocket::{get, http::ContentType, routes, State};
+
+pub struct FeedsController;
+
+impl Controller for FeedsController {
+    fn path(&self) -> &'static str {
+        "/feeds"
+    }
+
+    fn routes(&self) -> Vec<rocket::Route> {
+        routes![feed]
+    }
+}
+
+#[utoipa::path(
+    context_path = "/api/v1/feeds",
+    tag = "feeds",
+    params(
+        ("lang" = String, Path, description = "Language of the content, `en` or `pl`"),
+        ("file" = String, Path, description = "`atom.xml`, `rss.xml` or `feed.json`")
+    ),
+    responses(
+        (status = 200, description = "The recently updated pages, with their `ETag`, `Last-Modified` and `Cache-Control`", body = String, content_type = "application/atom+xml"),
+        (status = 304, description = "The cached feed is still valid"),
+        (status = 404, description = "Unknown language or format, `Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    )
+)]
+#[get("/<lang>/<file>")]
+async fn feed<'a>(
+    lang: &'a str,
+    file: &'a str,
+    pool: &dyn ResourcesRepo,
+    config: &State<AppConfig>,
+) -> Result<Cached<Document>, ApiError<'a>> {
+    let (Some(format), true) = (FeedFormat::from_file_name(file), LANGS.contains(&lang)) else {
+        return Err(Error::Status(404, "Not Found".to_string()).into());
+    };
+    let resources = pool.get_all().await?;
+    let mut entries = vec![];
+    for page in PUBLIC_PAGES {
+        let Some(resource) = page
+            .reskey
+            .and_then(|key| resources.iter().find(|r| r.key.as_deref() == Some(key)))
+        else {
+            continue;
+        };
+        let content = match lang {
+            "pl" => resource.pl.as_ref().or(resource.en.as_ref()),
+            _ => resource.en.as_ref(),
+        };
+        let (Some(key), Some(content)) = (&resource.key, content) else {
+            continue;
+        };
+        let meta = pool.get_meta(key, lang).await?;
+        entries.push(FeedEntry {
+            path: page.path.to_string(),
+            title: meta
+                .title
+                .or_else(|| feeds::title(content))
+                .unwrap_or_else(|| key.clone()),
+            summary: feeds::summary(content),
+            updated: resource.updated_at.unwrap_or_default(),
+        });
+    }
+    let feed = Feed::new(lang, &config.site_url, &config.api_url, entries);
+    Ok(Cached {
+        last_modified: feed.updated(),
+        cache_control: config.resources_cache_control.clone(),
+        body: Document {
+            content_type: ContentType::parse_flexible(format.media_type())
+                .unwrap_or(ContentType::XML),
+            body: feed.render(format),
+        },
+    })
+}
@@ -1,15 +1,18 @@
-use super::{controller::Controller, response::ApiResponse};
+use super::{
+    controller::Controller,
+    response::{ApiResponse, Negotiated},
+};
 use crate::{
     auth::claims::Claims,
     error::{ApiError, Error},
-    services::{azure_blob::AzureBlobService, filename::FilenameService},
+    models::upload::Upload,
+    repositories::upload::repo::UploadRepo,
+    services::{blob_store::BlobStore, filename::FilenameService},
 };
 use rocket::{
     data::{Limits, ToByteUnit},
     http::{ContentType, Status},
-    put, routes,
-    serde::json::Json,
-    Data, State,
+    put, routes, Data, State,
 };
 
 pub struct ImageController;
@@ -24,16 +27,32 @@ impl Controller for ImageController {
     }
 }
 
+#[utoipa::path(
+    operation_id = "upload_image",
+    context_path = "/api/v1/img",
+    tag = "images",
+    request_body(content = String, description = "PNG, JPEG or BMP image", content_type = "image/png"),
+    responses(
+        (status = 200, description = "The name of the uploaded file", body = StringResponse),
+        (status = 400, description = "Unsupported image type or invalid folder, `Status`", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 413, description = "`Status`", body = ErrorResponse),
+        (status = 500, description = "`Status`, `DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
 #[put("/?<folder>", data = "<img>")]
 async fn upload<'a>(
-    _claims: Claims,
+    claims: Claims,
     content_type: &ContentType,
     limits: &Limits,
-    blob_service: &State<AzureBlobService>,
+    upload_pool: &dyn UploadRepo,
+    blob_service: &dyn BlobStore,
     filename_service: &State<FilenameService>,
     folder: String,
     img: Data<'a>,
-) -> Result<Json<ApiResponse<'a, String>>, ApiError<'a>> {
+) -> Result<Negotiated<ApiResponse<'a, String>>, ApiError<'a>> {
     if !content_type.is_jpeg() && !content_type.is_png() && !content_type.is_bmp() {
         println!("Invalid media type");
         return Err(Error::from(Status::BadRequest).into());
@@ -67,5 +86,14 @@ async fn upload<'a>(
             content_type.to_string(),
         )
         .await?;
-    Ok(Json(ApiResponse::ok(filename)))
+    upload_pool
+        .create(&Upload {
+            path: format!("{}/{}", folder, filename),
+            content_type: content_type.to_string(),
+            size: data.len() as i32,
+            user_id: Some(claims.sub),
+            ..Default::default()
+        })
+        .await?;
+    Ok(Negotiated(ApiResponse::ok(filename)))
 }
@@ -0,0 +1,92 @@
+use super::{
+    controller::Controller,
+    response::{ApiResponse, Negotiated},
+};
+use crate::{
+    auth::claims::AdminClaims,
+    error::ApiError,
+    models::invite::{Invite, InviteRequest},
+    repositories::invite::repo::InviteRepo,
+};
+use rocket::{delete, get, post, routes};
+
+pub struct InvitesController;
+
+impl Controller for InvitesController {
+    fn path(&self) -> &'static str {
+        "/invites"
+    }
+
+    fn routes(&self) -> Vec<rocket::Route> {
+        routes![get_all, create, delete]
+    }
+}
+
+#[utoipa::path(
+    operation_id = "get_invites",
+    context_path = "/api/v1/invites",
+    tag = "invites",
+    responses(
+        (status = 200, description = "All invites", body = InvitesResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[get("/")]
+async fn get_all(
+    _claims: AdminClaims,
+    pool: &dyn InviteRepo,
+) -> Result<Negotiated<ApiResponse<Vec<Invite>>>, ApiError> {
+    let invites = pool.get_all().await?;
+    Ok(Negotiated(ApiResponse::ok(invites)))
+}
+
+#[utoipa::path(
+    operation_id = "create_invite",
+    context_path = "/api/v1/invites",
+    tag = "invites",
+    request_body = InviteRequest,
+    responses(
+        (status = 200, description = "The created invite", body = InviteResponse),
+        (status = 400, description = "`ValidationError`", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[post("/", data = "<request>")]
+async fn create(
+    claims: AdminClaims,
+    request: Negotiated<InviteRequest>,
+    pool: &dyn InviteRepo,
+) -> Result<Negotiated<ApiResponse<Invite>>, ApiError> {
+    let invite = Invite::new(request.into_inner(), claims.sub)?;
+    let invite = pool.create(&invite).await?;
+    Ok(Negotiated(ApiResponse::ok(invite)))
+}
+
+#[utoipa::path(
+    operation_id = "delete_invite",
+    context_path = "/api/v1/invites",
+    tag = "invites",
+    responses(
+        (status = 200, description = "The removed invite", body = InviteResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "`InviteNotFound`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[delete("/<id>")]
+async fn delete(
+    _claims: AdminClaims,
+    id: i32,
+    pool: &dyn InviteRepo,
+) -> Result<Negotiated<ApiResponse<Invite>>, ApiError> {
+    let invite = pool.delete(id).await?;
+    Ok(Negotiated(ApiResponse::ok(invite)))
+}
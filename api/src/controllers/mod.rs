@@ -1,5 +1,13 @@
+pub mod admin;
+pub mod comments;
 pub mod controller;
+pub mod feeds;
 pub mod image;
+pub mod invites;
+pub mod openapi;
 pub mod resources;
 pub mod response;
+pub mod search;
+pub mod sitemap;
 pub mod users;
+pub mod well_known;
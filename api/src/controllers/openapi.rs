@@ -0,0 +1,185 @@
+use super::{
+    admin, comments, feeds, image, invites, resources, response::*, search, sitemap, users,
+    well_known,
+};
+use crate::{
+    auth::{cookie::TOKEN_COOKIE, error::AuthError},
+    error::*,
+    models::{
+        bundle::{Bundle, BundleBlob, ConflictStrategy, ImportSummary},
+        comment::{Comment, CommentRequest, CommentStatus, ModerationAction, ThreadComment},
+        credentials::Credentials,
+        invite::{Invite, InviteRequest},
+        page_meta::PageMeta,
+        password::PasswordRequirements,
+        profile::{Profile, ProfileLinks, PublicProfile},
+        registration::RegistrationPolicy,
+        resource_data::ResourceData,
+        role::Role,
+        search::SearchHit,
+        upload::Upload,
+        user::User,
+        user_export::{DeleteAccountRequest, UserExport},
+        user_name::UserName,
+    },
+    repositories::resources::cache::CacheMetrics,
+};
+use rocket::Route;
+use utoipa::{
+    openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+use utoipa_redoc::{Redoc, Servable};
+use utoipa_swagger_ui::SwaggerUi;
+
+pub const OPENAPI_PATH: &str = "/api/v1/openapi.json";
+
+/// The OpenAPI document, built from the route handlers and the response types.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "petompp-web API",
+        description = "Bodies are JSON by default, `application/msgpack` and `application/cbor` are used when requested with `Accept` or sent as the `Content-Type`. Errors are sent as `ErrorResponse`, or as `Problem` details (RFC 7807) when `application/problem+json` is the preferred `Accept` type."
+    ),
+    paths(
+        users::create,
+        users::registration,
+        users::login,
+        users::logout,
+        users::get_self,
+        users::get_all,
+        users::activate,
+        users::delete,
+        users::export_self,
+        users::delete_self,
+        users::get_profile,
+        users::update_profile,
+        users::get_public_profile,
+        resources::get,
+        resources::get_all_keys,
+        resources::create,
+        resources::update,
+        resources::delete,
+        resources::get_meta,
+        resources::update_meta,
+        search::search,
+        feeds::feed,
+        comments::get_thread,
+        comments::create,
+        comments::update,
+        comments::delete,
+        comments::get_queue,
+        comments::moderate,
+        image::upload,
+        invites::get_all,
+        invites::create,
+        invites::delete,
+        admin::export,
+        admin::import,
+        admin::cache,
+        well_known::jwks,
+        sitemap::sitemap,
+        sitemap::robots,
+    ),
+    components(schemas(
+        EmptyResponse,
+        StringResponse,
+        StringsResponse,
+        UserResponse,
+        UsersResponse,
+        LoginResponse,
+        RegistrationPolicyResponse,
+        ProfileResponse,
+        PublicProfileResponse,
+        UserExportResponse,
+        ResourceDataResponse,
+        PageMetaResponse,
+        SearchHitsResponse,
+        CommentResponse,
+        CommentsResponse,
+        InviteResponse,
+        InvitesResponse,
+        BundleResponse,
+        ImportSummaryResponse,
+        CacheMetricsResponse,
+        CacheMetrics,
+        ErrorResponse,
+        Problem,
+        users::LoginData,
+        Credentials,
+        User,
+        UserName,
+        Role,
+        RegistrationPolicy,
+        Profile,
+        ProfileLinks,
+        PublicProfile,
+        UserExport,
+        DeleteAccountRequest,
+        Upload,
+        ResourceData,
+        PageMeta,
+        SearchHit,
+        Comment,
+        CommentRequest,
+        CommentStatus,
+        ModerationAction,
+        ThreadComment,
+        Invite,
+        InviteRequest,
+        Bundle,
+        BundleBlob,
+        ConflictStrategy,
+        ImportSummary,
+        PasswordRequirements,
+        Error,
+        AuthError,
+        ValidationError,
+        UsernameValidationError,
+        QueryValidationError,
+        ResourceDataValidationError,
+        ProfileValidationError,
+        InviteValidationError,
+        SearchValidationError,
+        PageMetaValidationError,
+        CommentValidationError,
+    )),
+    modifiers(&SecurityAddon)
+)]
+pub struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let Some(components) = openapi.components.as_mut() else {
+            return;
+        };
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+        // Used with `auth_mode = "cookie"`, requests also need the `X-CSRF-Token` header.
+        components.add_security_scheme(
+            "cookie",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new(TOKEN_COOKIE))),
+        );
+    }
+}
+
+/// The document with the Swagger UI at `/api/v1/docs` and Redoc at `/api/v1/redoc`.
+pub fn docs_routes() -> Vec<Route> {
+    let mut routes: Vec<Route> = SwaggerUi::new("/api/v1/docs/<_..>")
+        .url(OPENAPI_PATH, ApiDoc::openapi())
+        .into();
+    routes.extend(Into::<Vec<Route>>::into(Redoc::with_url(
+        "/api/v1/redoc",
+        ApiDoc::openapi(),
+    )));
+    routes
+}
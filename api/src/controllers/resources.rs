@@ -1,13 +1,14 @@
 use crate::{
     auth::claims::AdminClaims,
-    controllers::response::ApiResponse,
+    config::AppConfig,
+    controllers::response::{ApiResponse, Cached, Negotiated},
     error::{ApiError, Error, ResourceDataValidationError, ValidationError},
-    models::resource_data::ResourceData,
+    models::{page_meta::PageMeta, resource_data::ResourceData},
     repositories::resources::repo::ResourcesRepo,
 };
 
 use super::controller::Controller;
-use rocket::{delete, get, post, put, routes, serde::json::Json};
+use rocket::{delete, get, http::Status, post, put, routes, State};
 
 pub struct ResourcesController;
 
@@ -17,52 +18,119 @@ impl Controller for ResourcesController {
     }
 
     fn routes(&self) -> Vec<rocket::Route> {
-        routes![get, get_all_keys, create, update, delete]
+        routes![
+            get,
+            get_all_keys,
+            create,
+            update,
+            delete,
+            get_meta,
+            update_meta
+        ]
     }
 }
 
+#[utoipa::path(
+    operation_id = "get_resource",
+    context_path = "/api/v1/res",
+    tag = "resources",
+    params(
+        ("If-None-Match" = Option<String>, Header, description = "`ETag` of the cached value"),
+        ("If-Modified-Since" = Option<String>, Header, description = "`Last-Modified` of the cached value")
+    ),
+    responses(
+        (status = 200, description = "The resource value in the language, with its `ETag`, `Last-Modified` and `Cache-Control`", body = StringResponse),
+        (status = 304, description = "The cached value is still valid"),
+        (status = 404, description = "`Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    )
+)]
 #[get("/<key>?<lang>")]
 async fn get<'a>(
     key: &'a str,
     lang: &'a str,
     pool: &dyn ResourcesRepo,
-) -> Result<Json<ApiResponse<'a, String>>, ApiError<'a>> {
-    Ok(Json(ApiResponse::ok(pool.get(key, lang)?)))
+    config: &State<AppConfig>,
+) -> Result<Cached<ApiResponse<'a, String>>, ApiError<'a>> {
+    let (value, updated_at) = pool.get(key, lang).await?;
+    Ok(Cached {
+        body: ApiResponse::ok(value),
+        last_modified: updated_at,
+        cache_control: config.resources_cache_control.clone(),
+    })
 }
 
+#[utoipa::path(
+    context_path = "/api/v1/res",
+    tag = "resources",
+    responses(
+        (status = 200, description = "All resource keys", body = StringsResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    )
+)]
 #[get("/keys")]
 async fn get_all_keys<'a>(
     pool: &dyn ResourcesRepo,
-) -> Result<Json<ApiResponse<'a, Vec<String>>>, ApiError<'a>> {
-    Ok(Json(ApiResponse::ok(
-        pool.get_all()?
+) -> Result<Negotiated<ApiResponse<'a, Vec<String>>>, ApiError<'a>> {
+    Ok(Negotiated(ApiResponse::ok(
+        pool.get_all()
+            .await?
             .iter()
             .map(|x| x.key.clone().unwrap())
             .collect(),
     )))
 }
 
+#[utoipa::path(
+    operation_id = "create_resource",
+    context_path = "/api/v1/res",
+    tag = "resources",
+    request_body = ResourceData,
+    responses(
+        (status = 200, description = "The created resource", body = ResourceDataResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
 #[put("/<key>", data = "<value>")]
 async fn create<'a>(
     _admin_claims: AdminClaims,
     key: &'a str,
-    value: Json<ResourceData>,
+    value: Negotiated<ResourceData>,
     pool: &dyn ResourcesRepo,
-) -> Result<Json<ApiResponse<'a, ResourceData>>, ApiError<'a>> {
+) -> Result<Negotiated<ApiResponse<'a, ResourceData>>, ApiError<'a>> {
     let value = ResourceData {
         key: Some(key.to_string()),
+        updated_at: None,
         ..value.into_inner()
     };
-    Ok(Json(ApiResponse::ok(pool.create(&value)?)))
+    Ok(Negotiated(ApiResponse::ok(pool.create(&value).await?)))
 }
 
+#[utoipa::path(
+    operation_id = "update_resource",
+    context_path = "/api/v1/res",
+    tag = "resources",
+    request_body = ResourceData,
+    responses(
+        (status = 200, description = "The updated resource", body = ResourceDataResponse),
+        (status = 400, description = "`ValidationError`", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "`Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
 #[post("/<key>", data = "<value>")]
 async fn update<'a>(
     _admin_claims: AdminClaims,
     key: &'a str,
-    value: Json<ResourceData>,
+    value: Negotiated<ResourceData>,
     pool: &dyn ResourcesRepo,
-) -> Result<Json<ApiResponse<'a, ResourceData>>, ApiError<'a>> {
+) -> Result<Negotiated<ApiResponse<'a, ResourceData>>, ApiError<'a>> {
     if key != value.key.as_ref().unwrap().as_str() {
         return Err(Error::ValidationError(ValidationError::ResourceData(
             ResourceDataValidationError::KeyMismatch(
@@ -74,17 +142,80 @@ async fn update<'a>(
     }
     let value = ResourceData {
         key: Some(key.to_string()),
+        updated_at: None,
         ..value.into_inner()
     };
-    Ok(Json(ApiResponse::ok(pool.update(&value)?)))
+    Ok(Negotiated(ApiResponse::ok(pool.update(&value).await?)))
 }
 
+#[utoipa::path(
+    operation_id = "delete_resource",
+    context_path = "/api/v1/res",
+    tag = "resources",
+    responses(
+        (status = 200, description = "The resource is removed", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
 #[delete("/<key>")]
 async fn delete(
     _admin_claims: AdminClaims,
     key: &str,
     pool: &dyn ResourcesRepo,
 ) -> Result<&'static str, ApiError<'static>> {
-    pool.delete(key)?;
+    pool.delete(key).await?;
     Ok("OK")
 }
+
+#[utoipa::path(
+    context_path = "/api/v1/res",
+    tag = "resources",
+    responses(
+        (status = 200, description = "Metadata of the page in the language, English or empty ones when it has none", body = PageMetaResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    )
+)]
+#[get("/<key>/meta?<lang>")]
+async fn get_meta<'a>(
+    key: &'a str,
+    lang: &'a str,
+    pool: &dyn ResourcesRepo,
+) -> Result<Negotiated<ApiResponse<'a, PageMeta>>, ApiError<'a>> {
+    Ok(Negotiated(ApiResponse::ok(pool.get_meta(key, lang).await?)))
+}
+
+#[utoipa::path(
+    context_path = "/api/v1/res",
+    tag = "resources",
+    request_body = PageMeta,
+    responses(
+        (status = 200, description = "The saved metadata", body = PageMetaResponse),
+        (status = 400, description = "`ValidationError`", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "No resource with the key, `Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[put("/<key>/meta", data = "<meta>")]
+async fn update_meta<'a>(
+    _admin_claims: AdminClaims,
+    key: &'a str,
+    meta: Negotiated<PageMeta>,
+    pool: &dyn ResourcesRepo,
+) -> Result<Negotiated<ApiResponse<'a, PageMeta>>, ApiError<'a>> {
+    let meta = PageMeta {
+        key: key.to_string(),
+        ..meta.into_inner()
+    }
+    .normalized();
+    meta.validate()?;
+    if !pool.exists(key).await? {
+        return Err(Error::from(Status::NotFound).into());
+    }
+    Ok(Negotiated(ApiResponse::ok(pool.update_meta(&meta).await?)))
+}
@@ -1,6 +1,59 @@
-use serde::Serialize;
+use super::users::LoginData;
+use crate::{
+    error::Error,
+    models::{
+        bundle::{Bundle, ImportSummary},
+        comment::ThreadComment,
+        invite::Invite,
+        page_meta::PageMeta,
+        profile::{Profile, PublicProfile},
+        registration::RegistrationPolicy,
+        resource_data::ResourceData,
+        search::SearchHit,
+        user::User,
+        user_export::UserExport,
+    },
+    repositories::resources::cache::CacheMetrics,
+};
+use chrono::{DateTime, NaiveDateTime};
+use rocket::{
+    data::{self, FromData, Limits},
+    http::{ContentType, Header, MediaType, Status},
+    outcome::Outcome,
+    response::{self, Responder, Response},
+    serde::{json::serde_json, msgpack},
+    Data, Request,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::ops::Deref;
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+/// The aliases name the concrete responses in the OpenAPI document.
+#[derive(Serialize, ToSchema)]
+#[aliases(
+    EmptyResponse = ApiResponse<'static, Option<String>>,
+    StringResponse = ApiResponse<'static, String>,
+    StringsResponse = ApiResponse<'static, Vec<String>>,
+    UserResponse = ApiResponse<'static, User>,
+    UsersResponse = ApiResponse<'static, Vec<Vec<User>>>,
+    LoginResponse = ApiResponse<'static, LoginData>,
+    RegistrationPolicyResponse = ApiResponse<'static, RegistrationPolicy>,
+    ProfileResponse = ApiResponse<'static, Profile>,
+    PublicProfileResponse = ApiResponse<'static, PublicProfile>,
+    UserExportResponse = ApiResponse<'static, UserExport>,
+    ResourceDataResponse = ApiResponse<'static, ResourceData>,
+    PageMetaResponse = ApiResponse<'static, PageMeta>,
+    SearchHitsResponse = ApiResponse<'static, Vec<SearchHit>>,
+    CommentResponse = ApiResponse<'static, ThreadComment>,
+    CommentsResponse = ApiResponse<'static, Vec<ThreadComment>>,
+    InviteResponse = ApiResponse<'static, Invite>,
+    InvitesResponse = ApiResponse<'static, Vec<Invite>>,
+    BundleResponse = ApiResponse<'static, Bundle>,
+    ImportSummaryResponse = ApiResponse<'static, ImportSummary>,
+    CacheMetricsResponse = ApiResponse<'static, Option<CacheMetrics>>,
+    ErrorResponse = ApiResponse<'static, Error>
+)]
 pub struct ApiResponse<'a, T: Serialize> {
     pub status: &'a str,
     pub data: T,
@@ -21,3 +74,227 @@ impl<'a, T: Serialize> ApiResponse<'a, T> {
         }
     }
 }
+
+/// Encodings of the request and response bodies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    MsgPack,
+    Cbor,
+}
+
+impl Format {
+    pub const ALL: [Format; 3] = [Format::Json, Format::MsgPack, Format::Cbor];
+
+    pub fn media_type(&self) -> MediaType {
+        match self {
+            Format::Json => MediaType::JSON,
+            Format::MsgPack => MediaType::MsgPack,
+            Format::Cbor => MediaType::new("application", "cbor"),
+        }
+    }
+
+    fn from_media_type(media_type: &MediaType) -> Option<Self> {
+        if media_type.top() != "application" {
+            return None;
+        }
+        let sub = media_type.sub();
+        if sub == "json" {
+            Some(Format::Json)
+        } else if sub == "msgpack" || sub == "x-msgpack" {
+            Some(Format::MsgPack)
+        } else if sub == "cbor" {
+            Some(Format::Cbor)
+        } else {
+            None
+        }
+    }
+
+    /// The known format with the highest weight in the `Accept` header, JSON if there's none.
+    pub fn accepted(request: &Request<'_>) -> Self {
+        let Some(accept) = request.accept() else {
+            return Format::Json;
+        };
+        let mut media_types = accept.iter().collect::<Vec<_>>();
+        media_types.sort_by(|a, b| b.weight_or(1.0).total_cmp(&a.weight_or(1.0)));
+        media_types
+            .into_iter()
+            .find_map(|m| Self::from_media_type(m.media_type()))
+            .unwrap_or(Format::Json)
+    }
+
+    /// The format of the request body, bodies without the `Content-Type` are read as JSON.
+    /// So are the `text/plain` ones, which older clients sent JSON as.
+    pub fn of_body(request: &Request<'_>) -> Option<Self> {
+        match request.content_type() {
+            Some(content_type) if content_type.is_plain() => Some(Format::Json),
+            Some(content_type) => Self::from_media_type(content_type.media_type()),
+            None => Some(Format::Json),
+        }
+    }
+
+    /// Name of the body size limit in `Rocket.toml`.
+    fn limit_name(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::MsgPack => "msgpack",
+            Format::Cbor => "cbor",
+        }
+    }
+
+    pub fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            Format::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+            // Structs are encoded as maps, so the field names match the JSON.
+            Format::MsgPack => msgpack::to_vec(value).map_err(|e| e.to_string()),
+            Format::Cbor => {
+                let mut bytes = vec![];
+                ciborium::ser::into_writer(value, &mut bytes).map_err(|e| e.to_string())?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    pub fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, String> {
+        match self {
+            Format::Json => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+            Format::MsgPack => msgpack::from_slice(bytes).map_err(|e| e.to_string()),
+            Format::Cbor => ciborium::de::from_reader(bytes).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Like [`rocket::serde::json::Json`], but in the [`Format`] negotiated with the client.
+/// Responses follow the `Accept` header and request bodies the `Content-Type`.
+#[derive(Debug)]
+pub struct Negotiated<T>(pub T);
+
+impl<T> Negotiated<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Negotiated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for Negotiated<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let format = Format::accepted(request);
+        let body = format
+            .serialize(&self.0)
+            .map_err(|_| Status::InternalServerError)?;
+        (ContentType(format.media_type()), body).respond_to(request)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, T: DeserializeOwned> FromData<'r> for Negotiated<T> {
+    type Error = Error;
+
+    async fn from_data(request: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let Some(format) = Format::of_body(request) else {
+            return Outcome::Failure((
+                Status::UnsupportedMediaType,
+                Status::UnsupportedMediaType.into(),
+            ));
+        };
+        let limit = request
+            .limits()
+            .get(format.limit_name())
+            .unwrap_or(Limits::JSON);
+        let bytes = match data.open(limit).into_bytes().await {
+            Ok(bytes) if bytes.is_complete() => bytes.into_inner(),
+            Ok(_) => {
+                return Outcome::Failure((Status::PayloadTooLarge, Status::PayloadTooLarge.into()))
+            }
+            Err(e) => {
+                return Outcome::Failure((Status::BadRequest, Error::Status(400, e.to_string())))
+            }
+        };
+        match format.deserialize(&bytes) {
+            Ok(value) => Outcome::Success(Negotiated(value)),
+            Err(e) => Outcome::Failure((Status::UnprocessableEntity, Error::Status(422, e))),
+        }
+    }
+}
+
+/// [`Negotiated`] body with a strong `ETag` and `Last-Modified`,
+/// answered with `304 Not Modified` when the client already has it.
+pub struct Cached<T> {
+    pub body: T,
+    pub last_modified: NaiveDateTime,
+    pub cache_control: String,
+}
+
+impl<T> Cached<T> {
+    const HTTP_DATE: &'static str = "%a, %d %b %Y %H:%M:%S GMT";
+
+    /// `If-None-Match` takes precedence over `If-Modified-Since`, as in RFC 9110.
+    fn is_fresh(&self, request: &Request<'_>, etag: &str) -> bool {
+        let headers = request.headers();
+        if let Some(if_none_match) = headers.get_one("If-None-Match") {
+            return if_none_match
+                .split(',')
+                .map(str::trim)
+                .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag);
+        }
+        headers
+            .get_one("If-Modified-Since")
+            .and_then(|since| DateTime::parse_from_rfc2822(since).ok())
+            .is_some_and(|since| self.last_modified.and_utc().timestamp() <= since.timestamp())
+    }
+
+    /// The `body` with the caching headers, or just the headers when the client's copy is fresh.
+    fn respond_with(
+        self,
+        request: &Request<'_>,
+        content_type: ContentType,
+        body: Vec<u8>,
+    ) -> response::Result<'static> {
+        let etag = format!("\"{:x}\"", Sha256::digest(&body));
+        let mut response = match self.is_fresh(request, &etag) {
+            true => Response::build().status(Status::NotModified).finalize(),
+            false => (content_type, body).respond_to(request)?,
+        };
+        response.set_header(Header::new("ETag", etag));
+        response.set_header(Header::new(
+            "Last-Modified",
+            self.last_modified.format(Self::HTTP_DATE).to_string(),
+        ));
+        response.set_header(Header::new("Cache-Control", self.cache_control));
+        Ok(response)
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for Cached<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let format = Format::accepted(request);
+        let body = format
+            .serialize(&self.body)
+            .map_err(|_| Status::InternalServerError)?;
+        // The representation differs between the formats, so it is hashed rather than the value.
+        let mut response = self.respond_with(request, ContentType(format.media_type()), body)?;
+        response.set_header(Header::new("Vary", "Accept"));
+        Ok(response)
+    }
+}
+
+/// Document in a format of its own, sent as it is.
+pub struct Document {
+    pub content_type: ContentType,
+    pub body: String,
+}
+
+impl<'r> Responder<'r, 'static> for Cached<Document> {
+    fn respond_to(mut self, request: &'r Request<'_>) -> response::Result<'static> {
+        let body = std::mem::take(&mut self.body.body).into_bytes();
+        let content_type = self.body.content_type.clone();
+        self.respond_with(request, content_type, body)
+    }
+}
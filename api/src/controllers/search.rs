@@ -0,0 +1,45 @@
+use super::{
+    controller::Controller,
+    response::{ApiResponse, Negotiated},
+};
+use crate::{error::ApiError, models::search::SearchHit, repositories::search::repo::SearchRepo};
+use rocket::{get, routes};
+
+/// Hits returned by one search.
+const LIMIT: i64 = 20;
+
+pub struct SearchController;
+
+impl Controller for SearchController {
+    fn path(&self) -> &'static str {
+        "/search"
+    }
+
+    fn routes(&self) -> Vec<rocket::Route> {
+        routes![search]
+    }
+}
+
+#[utoipa::path(
+    operation_id = "search",
+    context_path = "/api/v1/search",
+    tag = "search",
+    params(
+        ("q" = String, Query, description = "Words to search for, quoted phrases and `-` exclusions are understood by Postgres"),
+        ("lang" = Option<String>, Query, description = "Language of the searched content, `en` by default")
+    ),
+    responses(
+        (status = 200, description = "Best matches first, the snippets mark the matches with `<mark>`", body = SearchHitsResponse),
+        (status = 400, description = "`ValidationError`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    )
+)]
+#[get("/?<q>&<lang>")]
+async fn search<'a>(
+    q: &'a str,
+    lang: Option<&'a str>,
+    pool: &dyn SearchRepo,
+) -> Result<Negotiated<ApiResponse<'a, Vec<SearchHit>>>, ApiError<'a>> {
+    let hits = pool.search(q, lang.unwrap_or("en"), LIMIT).await?;
+    Ok(Negotiated(ApiResponse::ok(hits)))
+}
@@ -0,0 +1,67 @@
+use crate::{
+    config::AppConfig,
+    controllers::controller::Controller,
+    error::ApiError,
+    models::sitemap::{robots as robots_txt, sitemap as sitemap_xml, SitemapEntry, PUBLIC_PAGES},
+    repositories::resources::repo::ResourcesRepo,
+};
+use rocket::{get, http::ContentType, routes, State};
+
+/// Documents for the crawlers, mounted at the root instead of the API prefix.
+pub struct SitemapController;
+
+impl Controller for SitemapController {
+    fn path(&self) -> &'static str {
+        "/"
+    }
+
+    fn routes(&self) -> Vec<rocket::Route> {
+        routes![sitemap, robots]
+    }
+}
+
+/// The public pages of the front in the languages it serves, dated by the last change of their content.
+#[utoipa::path(
+    tag = "seo",
+    responses(
+        (status = 200, description = "The sitemap with the `hreflang` alternates of the pages", body = String, content_type = "text/xml"),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    )
+)]
+#[get("/sitemap.xml")]
+async fn sitemap(
+    pool: &dyn ResourcesRepo,
+    config: &State<AppConfig>,
+) -> Result<(ContentType, String), ApiError<'static>> {
+    let resources = pool.get_all().await?;
+    let entries = PUBLIC_PAGES
+        .iter()
+        .map(|page| SitemapEntry {
+            path: page.path.to_string(),
+            lastmod: page
+                .reskey
+                .and_then(|key| resources.iter().find(|r| r.key.as_deref() == Some(key)))
+                .and_then(|r| r.updated_at),
+        })
+        .collect::<Vec<_>>();
+    Ok((
+        ContentType::XML,
+        sitemap_xml(&config.site_url, &config.site_langs, &entries),
+    ))
+}
+
+/// Rules for the crawlers, set with the `robots` configuration.
+#[utoipa::path(
+    tag = "seo",
+    responses(
+        (status = 200, description = "The `robots.txt` pointing to the sitemap", body = String, content_type = "text/plain")
+    )
+)]
+#[get("/robots.txt")]
+async fn robots(config: &State<AppConfig>) -> String {
+    robots_txt(
+        &config.site_url,
+        &config.robots.disallow,
+        config.robots.indexed,
+    )
+}
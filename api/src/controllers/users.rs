@@ -1,16 +1,31 @@
-use super::response::ApiResponse;
+use super::response::{ApiResponse, Negotiated};
 use crate::{
     auth::{
         claims::{AdminClaims, Claims},
+        cookie::{remove_auth_cookies, set_auth_cookies, AuthMode},
         error::AuthError,
+        keys::JwtKeys,
         token::create_token,
     },
+    config::AppConfig,
     controllers::controller::Controller,
     error::{ApiError, Error},
-    models::{credentials::Credentials, role::Role, user::User},
-    repositories::{query_config::QueryConfig, user::repo::UserRepo},
+    models::{
+        credentials::Credentials,
+        profile::{Profile, PublicProfile},
+        registration::RegistrationPolicy,
+        role::Role,
+        user::User,
+        user_export::{DeleteAccountRequest, UserExport},
+    },
+    repositories::{
+        comment::repo::CommentRepo, invite::repo::InviteRepo, query_config::QueryConfig,
+        upload::repo::UploadRepo, user::repo::UserRepo,
+    },
+    services::blob_store::BlobStore,
+    state::AppState,
 };
-use rocket::{delete, get, post, routes, serde::json::Json, State};
+use rocket::{delete, get, http::CookieJar, post, put, routes, State};
 use serde::{Deserialize, Serialize};
 
 pub struct UsersController;
@@ -21,87 +36,401 @@ impl Controller for UsersController {
     }
 
     fn routes(&self) -> Vec<rocket::Route> {
-        routes![create, login, get_self, activate, get_all, delete]
+        routes![
+            create,
+            registration,
+            login,
+            logout,
+            get_self,
+            activate,
+            get_all,
+            delete,
+            export_self,
+            delete_self,
+            get_profile,
+            update_profile,
+            get_public_profile
+        ]
     }
 }
 
+#[utoipa::path(
+    operation_id = "create_user",
+    context_path = "/api/v1/users",
+    tag = "users",
+    request_body = Credentials,
+    responses(
+        (status = 200, description = "The created user", body = UserResponse),
+        (status = 400, description = "`UserNameTaken`, `ValidationError`, `InviteCodeRequired`, `InviteCodeInvalid`, `InviteCodeExpired`, `InviteCodeUsedUp`", body = ErrorResponse),
+        (status = 403, description = "`RegistrationClosed`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    )
+)]
 #[post("/", data = "<credentials>")]
 async fn create(
-    credentials: Json<Credentials>,
-    pool: &dyn UserRepo,
-) -> Result<Json<ApiResponse<User>>, ApiError> {
-    let user = User::new(
+    credentials: Negotiated<Credentials>,
+    config: &State<AppConfig>,
+    state: &State<AppState>,
+    invite_pool: &dyn InviteRepo,
+) -> Result<Negotiated<ApiResponse<User>>, ApiError> {
+    let invite = match (config.registration, &credentials.invite) {
+        (RegistrationPolicy::Closed, _) => return Err(Error::RegistrationClosed.into()),
+        (RegistrationPolicy::InviteOnly, None) => return Err(Error::InviteCodeRequired.into()),
+        (_, Some(code)) => {
+            let invite = invite_pool.get_by_code(code.trim()).await?;
+            invite.check()?;
+            Some(invite)
+        }
+        (RegistrationPolicy::Open, None) => None,
+    };
+    let mut user = User::new(
         credentials.name.clone(),
         credentials.password.clone(),
-        Role::User,
+        invite.as_ref().map(|i| i.role).unwrap_or(Role::User),
+        &config.password,
     )?;
-    let user = pool.create(&user)?;
-    Ok(Json(ApiResponse::ok(user)))
+    // Invites are issued by admins, so there is no need for a manual activation.
+    user.confirmed = invite.is_some();
+    // The user is not kept when the invite was used up in the meantime.
+    let user = state
+        .transaction(move |repos| {
+            Box::pin(async move {
+                let user = repos.users.create(&user).await?;
+                if let Some(invite) = invite {
+                    repos.invites.use_code(&invite.code).await?;
+                }
+                Ok(user)
+            })
+        })
+        .await?;
+    Ok(Negotiated(ApiResponse::ok(user)))
+}
+
+#[utoipa::path(
+    context_path = "/api/v1/users",
+    tag = "users",
+    responses(
+        (status = 200, description = "The registration policy", body = RegistrationPolicyResponse)
+    )
+)]
+#[get("/registration")]
+async fn registration(
+    config: &State<AppConfig>,
+) -> Negotiated<ApiResponse<'static, RegistrationPolicy>> {
+    Negotiated(ApiResponse::ok(config.registration))
 }
 
-#[derive(Serialize, Deserialize)]
-struct LoginResponse {
-    token: String,
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LoginData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    csrf: Option<String>,
     user: User,
 }
 
+#[utoipa::path(
+    context_path = "/api/v1/users",
+    tag = "users",
+    request_body = Credentials,
+    responses(
+        (status = 200, description = "The token, or the CSRF token in the cookie mode", body = LoginResponse),
+        (status = 401, description = "`InvalidCredentials`", body = ErrorResponse),
+        (status = 402, description = "`UserNotConfirmed`", body = ErrorResponse),
+        (status = 500, description = "`AuthError`, `DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    )
+)]
 #[post("/login", data = "<credentials>")]
 async fn login<'a>(
-    credentials: Json<Credentials>,
+    credentials: Negotiated<Credentials>,
     pool: &'a dyn UserRepo,
-    secrets: &State<crate::Secrets>,
-) -> Result<Json<ApiResponse<'a, LoginResponse>>, ApiError<'a>> {
+    keys: &State<JwtKeys>,
+    config: &State<AppConfig>,
+    cookies: &CookieJar<'_>,
+) -> Result<Negotiated<ApiResponse<'a, LoginData>>, ApiError<'a>> {
     let user = pool
         .get_by_name(credentials.name.to_ascii_lowercase())
+        .await
         .map_err(|e| match e {
             Error::UserNotFound(_) => Error::InvalidCredentials,
             _ => e,
         })?;
-    if !user.password.verify(credentials.password.clone()) {
+    if user.deleted_at.is_some() || !user.password.verify(credentials.password.clone()) {
         return Err(Error::InvalidCredentials.into());
     }
     if !user.confirmed {
         return Err(Error::UserNotConfirmed(credentials.name.to_string()).into());
     }
-    let token = create_token(secrets, &user).map_err(<AuthError as Into<Error>>::into)?;
-    Ok(Json(ApiResponse::ok(LoginResponse { token, user })))
+    let claims =
+        Claims::new(&user, config.jwt.token_lifetime).map_err(<AuthError as Into<Error>>::into)?;
+    let token = create_token(keys, &claims).map_err(<AuthError as Into<Error>>::into)?;
+    let response = match config.auth_mode {
+        AuthMode::Header => LoginData {
+            token: Some(token),
+            csrf: None,
+            user,
+        },
+        AuthMode::Cookie => {
+            let csrf = set_auth_cookies(cookies, token, &claims);
+            LoginData {
+                token: None,
+                csrf: Some(csrf),
+                user,
+            }
+        }
+    };
+    Ok(Negotiated(ApiResponse::ok(response)))
+}
+
+#[utoipa::path(
+    context_path = "/api/v1/users",
+    tag = "users",
+    responses(
+        (status = 200, description = "The auth cookies are removed", body = EmptyResponse)
+    )
+)]
+#[post("/logout")]
+async fn logout(cookies: &CookieJar<'_>) -> Negotiated<ApiResponse<'static, ()>> {
+    remove_auth_cookies(cookies);
+    Negotiated(ApiResponse::ok(()))
 }
 
+#[utoipa::path(
+    operation_id = "get_self",
+    context_path = "/api/v1/users",
+    tag = "users",
+    responses(
+        (status = 200, description = "The authenticated user", body = UserResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "`UserNotFound`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
 #[get("/")]
 async fn get_self(
     claims: Claims,
     pool: &dyn UserRepo,
-) -> Result<Json<ApiResponse<User>>, ApiError> {
-    let user = pool.get_by_id(claims.sub)?;
-    Ok(Json(ApiResponse::ok(user)))
+) -> Result<Negotiated<ApiResponse<User>>, ApiError> {
+    let user = pool.get_by_id(claims.sub).await?;
+    Ok(Negotiated(ApiResponse::ok(user)))
 }
 
+#[utoipa::path(
+    operation_id = "get_users",
+    context_path = "/api/v1/users",
+    tag = "users",
+    params(QueryConfig),
+    responses(
+        (status = 200, description = "A page of users", body = UsersResponse),
+        (status = 400, description = "`ValidationError`", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
 #[get("/all?<query..>")]
-fn get_all(
+async fn get_all(
     _claims: AdminClaims,
     query: QueryConfig,
     pool: &dyn UserRepo,
-) -> Result<Json<ApiResponse<Vec<Vec<User>>>>, ApiError> {
-    let users = pool.get_all(&query)?;
-    Ok(Json(ApiResponse::ok(users)))
+) -> Result<Negotiated<ApiResponse<Vec<Vec<User>>>>, ApiError> {
+    let users = pool.get_all(&query).await?;
+    Ok(Negotiated(ApiResponse::ok(users)))
 }
 
+#[utoipa::path(
+    context_path = "/api/v1/users",
+    tag = "users",
+    responses(
+        (status = 200, description = "The activated user", body = UserResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "`UserNotFound`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
 #[post("/<id>/activate")]
 async fn activate(
     _claims: AdminClaims,
     id: i32,
     pool: &dyn UserRepo,
-) -> Result<Json<ApiResponse<User>>, ApiError> {
-    let user = pool.activate(id)?;
-    Ok(Json(ApiResponse::ok(user)))
+) -> Result<Negotiated<ApiResponse<User>>, ApiError> {
+    let user = pool.activate(id).await?;
+    Ok(Negotiated(ApiResponse::ok(user)))
 }
 
+#[utoipa::path(
+    operation_id = "delete_user",
+    context_path = "/api/v1/users",
+    tag = "users",
+    responses(
+        (status = 200, description = "The soft-deleted user", body = UserResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Not an admin or invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "`UserNotFound`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
 #[delete("/<id>")]
 async fn delete(
     _claims: AdminClaims,
     id: i32,
     pool: &dyn UserRepo,
-) -> Result<Json<ApiResponse<User>>, ApiError> {
-    let user = pool.delete(id)?;
-    Ok(Json(ApiResponse::ok(user)))
+) -> Result<Negotiated<ApiResponse<User>>, ApiError> {
+    let user = pool.delete(id).await?;
+    Ok(Negotiated(ApiResponse::ok(user)))
+}
+
+#[utoipa::path(
+    context_path = "/api/v1/users",
+    tag = "users",
+    responses(
+        (status = 200, description = "All data tied to the account", body = UserExportResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "`UserNotFound`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[get("/me/export")]
+async fn export_self(
+    claims: Claims,
+    pool: &dyn UserRepo,
+    invite_pool: &dyn InviteRepo,
+    upload_pool: &dyn UploadRepo,
+    comment_pool: &dyn CommentRepo,
+) -> Result<Negotiated<ApiResponse<UserExport>>, ApiError> {
+    let user = pool.get_by_id(claims.sub).await?;
+    let profile = pool.get_profile(claims.sub).await?;
+    let invites = invite_pool
+        .get_all()
+        .await?
+        .into_iter()
+        .filter(|i| i.created_by == claims.sub)
+        .collect();
+    let uploads = upload_pool.get_by_user(claims.sub).await?;
+    let comments = comment_pool.get_by_user(claims.sub).await?;
+    Ok(Negotiated(ApiResponse::ok(UserExport {
+        exported_at: chrono::Utc::now().naive_utc(),
+        user,
+        profile,
+        invites,
+        uploads,
+        comments,
+    })))
+}
+
+/// The account is anonymized right away and purged after the configured grace period.
+#[utoipa::path(
+    context_path = "/api/v1/users",
+    tag = "users",
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 200, description = "The account is anonymized", body = EmptyResponse),
+        (status = 401, description = "`InvalidCredentials`, missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 404, description = "`UserNotFound`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[delete("/me", data = "<request>")]
+async fn delete_self<'a>(
+    claims: Claims,
+    request: Negotiated<DeleteAccountRequest>,
+    pool: &'a dyn UserRepo,
+    cookies: &CookieJar<'_>,
+) -> Result<Negotiated<ApiResponse<'a, ()>>, ApiError<'a>> {
+    let user = pool.get_by_id(claims.sub).await?;
+    if user.deleted_at.is_some() || !user.password.verify(request.password.clone()) {
+        return Err(Error::InvalidCredentials.into());
+    }
+    pool.anonymize(claims.sub).await?;
+    remove_auth_cookies(cookies);
+    Ok(Negotiated(ApiResponse::ok(())))
+}
+
+#[utoipa::path(
+    context_path = "/api/v1/users",
+    tag = "users",
+    responses(
+        (status = 200, description = "The own profile", body = ProfileResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[get("/profile")]
+async fn get_profile(
+    claims: Claims,
+    pool: &dyn UserRepo,
+) -> Result<Negotiated<ApiResponse<Profile>>, ApiError> {
+    let profile = pool.get_profile(claims.sub).await?;
+    Ok(Negotiated(ApiResponse::ok(profile)))
+}
+
+#[utoipa::path(
+    context_path = "/api/v1/users",
+    tag = "users",
+    request_body = Profile,
+    responses(
+        (status = 200, description = "The saved profile", body = ProfileResponse),
+        (status = 400, description = "`ValidationError`", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token, `Status`", body = ErrorResponse),
+        (status = 403, description = "Invalid CSRF token, `Status`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    ),
+    security(("bearer" = []), ("cookie" = []))
+)]
+#[put("/profile", data = "<profile>")]
+async fn update_profile(
+    claims: Claims,
+    profile: Negotiated<Profile>,
+    pool: &dyn UserRepo,
+    blob_service: &dyn BlobStore,
+) -> Result<Negotiated<ApiResponse<Profile>>, ApiError> {
+    let profile = Profile {
+        user_id: claims.sub,
+        ..profile.into_inner()
+    }
+    .normalized();
+    profile.validate(&blob_service.url())?;
+    let profile = pool.update_profile(&profile).await?;
+    Ok(Negotiated(ApiResponse::ok(profile)))
+}
+
+#[utoipa::path(
+    context_path = "/api/v1/users",
+    tag = "users",
+    responses(
+        (status = 200, description = "The public profile", body = PublicProfileResponse),
+        (status = 404, description = "`UserNotFound`", body = ErrorResponse),
+        (status = 500, description = "`DatabaseError`, `DatabaseConnectionError`", body = ErrorResponse)
+    )
+)]
+#[get("/<name>/profile")]
+async fn get_public_profile<'a>(
+    name: &'a str,
+    pool: &'a dyn UserRepo,
+) -> Result<Negotiated<ApiResponse<'a, PublicProfile>>, ApiError<'a>> {
+    let user = pool.get_by_name(name.to_lowercase()).await?;
+    let Some(id) = user
+        .id
+        .filter(|_| user.confirmed && user.deleted_at.is_none())
+    else {
+        return Err(Error::UserNotFound(name.to_string()).into());
+    };
+    let profile = pool.get_profile(id).await?;
+    Ok(Negotiated(ApiResponse::ok(PublicProfile {
+        name: user.name,
+        created_at: user.created_at,
+        profile,
+    })))
 }
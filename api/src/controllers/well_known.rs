@@ -0,0 +1,31 @@
+use crate::{
+    auth::keys::{JwkSet, JwtKeys},
+    controllers::controller::Controller,
+};
+use rocket::{get, routes, serde::json::Json, State};
+
+/// Standard discovery documents, mounted at the root instead of the API prefix.
+pub struct WellKnownController;
+
+impl Controller for WellKnownController {
+    fn path(&self) -> &'static str {
+        "/.well-known"
+    }
+
+    fn routes(&self) -> Vec<rocket::Route> {
+        routes![jwks]
+    }
+}
+
+/// The public keys for the token verification, in the plain JWKS format.
+#[utoipa::path(
+    context_path = "/.well-known",
+    tag = "well-known",
+    responses(
+        (status = 200, description = "The public keys in the JWKS format", body = Object)
+    )
+)]
+#[get("/jwks.json")]
+async fn jwks(keys: &State<JwtKeys>) -> Json<JwkSet> {
+    Json(keys.jwks())
+}
@@ -1,110 +1,43 @@
-use crate::models::password::PasswordRequirements;
-use crate::{auth::error::AuthError, controllers::response::ApiResponse};
-use rocket::serde::json::Json;
-use rocket::{http, response::status};
-use serde::{Deserialize, Serialize};
-use std::fmt::Display;
-
-pub type ApiError<'a> = status::Custom<Json<ApiResponse<'a, Error>>>;
+use crate::controllers::response::{ApiResponse, Negotiated};
+pub use petompp_web_models::error::*;
+use rocket::{
+    http::ContentType,
+    response::{self, status, Responder},
+    serde::json::Json,
+    Request,
+};
+
+/// Error response with the status matching the [`Error`].
+/// Sent as [`Problem`] details when the client prefers `application/problem+json`.
+pub struct ApiError<'a>(status::Custom<Negotiated<ApiResponse<'a, Error>>>);
 
 impl From<Error> for ApiError<'_> {
     fn from(value: Error) -> Self {
-        status::Custom(value.status(), Json(ApiResponse::err(value)))
+        Self(status::Custom(
+            value.status(),
+            Negotiated(ApiResponse::err(value)),
+        ))
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub enum Error {
-    AuthError(AuthError),
-    DatabaseError(String),
-    DatabaseConnectionError(String),
-    UserNameTaken(String),
-    UserNotFound(String),
-    InvalidCredentials,
-    UserNotConfirmed(String),
-    ValidationError(ValidationError),
-    Status(u16, String),
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub enum ValidationError {
-    Username(UsernameValidationError),
-    Password(PasswordRequirements),
-    Query(QueryValidationError),
-    ResourceData(ResourceDataValidationError),
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub enum UsernameValidationError {
-    InvalidLength(i32, i32),
-    InvalidCharacters(Vec<char>),
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub enum QueryValidationError {
-    InvalidColumn(String),
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub enum ResourceDataValidationError {
-    KeyMismatch(String, String),
-    KeyMissing,
-    ValueMissing,
-}
-
-impl Error {
-    pub fn status(&self) -> http::Status {
-        match self {
-            Error::AuthError(e) => match e {
-                AuthError::JwtError(_) => http::Status::InternalServerError,
-                _ => http::Status::BadRequest,
-            },
-            Error::DatabaseError(_) => http::Status::InternalServerError,
-            Error::DatabaseConnectionError(_) => http::Status::InternalServerError,
-            Error::UserNameTaken(_) => http::Status::BadRequest,
-            Error::UserNotFound(_) => http::Status::NotFound,
-            Error::InvalidCredentials => http::Status::Unauthorized,
-            Error::UserNotConfirmed(_) => http::Status::PaymentRequired,
-            Error::ValidationError(_) => http::Status::BadRequest,
-            Error::Status(status, _) => http::Status::from_code(*status).unwrap(),
+impl<'r> Responder<'r, 'static> for ApiError<'_> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        if !prefers_problem(request) {
+            return self.0.respond_to(request);
         }
+        let status::Custom(status, Negotiated(response)) = self.0;
+        (
+            ContentType::new("application", "problem+json"),
+            status::Custom(status, Json(Problem::from(response.data))),
+        )
+            .respond_to(request)
     }
 }
 
-impl Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{:?}", self))
-    }
-}
-
-impl From<r2d2::Error> for Error {
-    fn from(e: r2d2::Error) -> Self {
-        Self::DatabaseConnectionError(e.to_string())
-    }
-}
-
-impl From<diesel::result::Error> for Error {
-    fn from(e: diesel::result::Error) -> Self {
-        Self::DatabaseError(e.to_string())
-    }
-}
-
-impl From<AuthError> for Error {
-    fn from(value: AuthError) -> Self {
-        Error::AuthError(value)
-    }
+/// Whether the most preferred media type in the `Accept` header is `application/problem+json`.
+fn prefers_problem(request: &Request<'_>) -> bool {
+    request.accept().is_some_and(|accept| {
+        let media_type = accept.preferred().media_type();
+        media_type.top() == "application" && media_type.sub() == "problem+json"
+    })
 }
-
-impl From<rocket::http::Status> for Error {
-    fn from(value: rocket::http::Status) -> Self {
-        Error::Status(value.code, value.to_string())
-    }
-}
-
-impl From<azure_core::error::Error> for Error {
-    fn from(value: azure_core::error::Error) -> Self {
-        Error::Status(500, value.to_string())
-    }
-}
-
-impl std::error::Error for Error {}
@@ -1,81 +1,153 @@
+use crate::controllers::controller::ControllerRegisterer;
 use crate::controllers::users::UsersController;
-use crate::controllers::{controller::ControllerRegisterer, response::ApiResponse};
+use auth::keys::JwtKeys;
+use config::AppConfig;
+use controllers::admin::AdminController;
+use controllers::comments::CommentsController;
+use controllers::controller::Controller;
+use controllers::feeds::FeedsController;
 use controllers::image::ImageController;
+use controllers::invites::InvitesController;
+use controllers::openapi::docs_routes;
 use controllers::resources::ResourcesController;
-use diesel::{
-    r2d2::{ConnectionManager, Pool},
-    PgConnection,
-};
-use error::Error;
-use repositories::{resources::repo::ResourcesRepo, user::repo::UserRepo};
-use rocket::{catch, http::Status, serde::json::Json, Build, Rocket};
+use controllers::search::SearchController;
+use controllers::sitemap::SitemapController;
+use controllers::well_known::WellKnownController;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+use error::{ApiError, Error};
+use repositories::user::repo::UserRepo;
+use rocket::{catch, fairing::AdHoc, http::Status, Build, Rocket};
 use rocket::{catchers, Request};
-use services::azure_blob::{AzureBlobSecrets, AzureBlobService};
+use rocket_cors::AllowedOrigins;
 use services::filename::FilenameService;
-use std::env;
-
+use state::AppState;
+use std::sync::Arc;
 pub mod auth;
+pub mod config;
 pub mod controllers;
 pub mod error;
 pub mod models;
 pub mod repositories;
-pub mod schema;
+pub use petompp_web_models::schema;
 pub mod services;
+pub mod state;
 
-pub type PgPool = Pool<ConnectionManager<PgConnection>>;
+#[cfg(all(feature = "postgres", feature = "sqlite"))]
+compile_error!("only one of the `postgres` and `sqlite` features can be enabled");
+#[cfg(not(any(feature = "postgres", feature = "sqlite")))]
+compile_error!("one of the `postgres` and `sqlite` features has to be enabled");
 
-#[derive(Clone, Debug)]
-pub struct Secrets {
-    pub api_secret: String,
-    pub database_url: String,
-}
+/// Connection to the backend selected by the `postgres` or `sqlite` feature.
+#[cfg(feature = "postgres")]
+pub type DbConnection = diesel::PgConnection;
+#[cfg(feature = "sqlite")]
+pub type DbConnection = diesel::SqliteConnection;
+/// Backend the queries are built for.
+pub type Db = <DbConnection as diesel::Connection>::Backend;
+pub type DbPool = Pool<ConnectionManager<DbConnection>>;
 
-impl Default for Secrets {
-    fn default() -> Self {
-        let api_secret = env::var("API_SECRET").expect("API_SECRET must be set");
-        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+#[cfg(feature = "postgres")]
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations/postgres");
+#[cfg(feature = "sqlite")]
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations/sqlite");
 
-        Self {
-            api_secret,
-            database_url,
-        }
-    }
-}
-
-pub fn build_rocket(
-    secrets: &Secrets,
-    user_repo: &'static dyn UserRepo,
-    resources_repo: &'static dyn ResourcesRepo,
-) -> Rocket<Build> {
+pub fn build_rocket(config: &AppConfig, jwt_keys: JwtKeys, state: AppState) -> Rocket<Build> {
+    let allowed_origins = match config.cors_origins.is_empty() {
+        true => AllowedOrigins::all(),
+        false => AllowedOrigins::some_exact(&config.cors_origins),
+    };
     let cors = rocket_cors::CorsOptions::default()
+        .allowed_origins(allowed_origins)
         .allow_credentials(true)
+        // Read by the front to revalidate the cached resources.
+        .expose_headers(["ETag", "Last-Modified"].map(String::from).into())
         .to_cors()
         .unwrap();
 
-    rocket::build()
-        .add(UsersController)
-        .add(ResourcesController)
-        .add(ImageController)
+    api_controllers()
+        .into_iter()
+        .fold(
+            rocket::custom(AppConfig::figment()),
+            |rocket, controller| rocket.add(controller),
+        )
+        .mount(WellKnownController.path(), WellKnownController.routes())
+        .mount(SitemapController.path(), SitemapController.routes())
+        .mount("/", docs_routes())
         .mount("/", rocket_cors::catch_all_options_routes())
         .register("/", catchers![err])
+        .attach(purge_scheduler(
+            state.users.clone(),
+            config.deletion_grace_days,
+        ))
         .attach(cors.clone())
         .manage(cors)
-        .manage(secrets.clone())
-        .manage(user_repo)
-        .manage(resources_repo)
-        .manage(AzureBlobService::new(AzureBlobSecrets::default()))
+        .manage(config.clone())
+        .manage(jwt_keys)
+        .manage(state)
         .manage(FilenameService::default())
 }
 
-pub fn get_connection_pool(secrets: &Secrets) -> PgPool {
-    let manager = ConnectionManager::<PgConnection>::new(secrets.database_url.clone());
-    Pool::builder()
-        .test_on_check_out(true)
-        .build(manager)
-        .expect("Failed to create pool")
+/// Every controller mounted under the API prefix, all of them are described by [`ApiDoc`](controllers::openapi::ApiDoc).
+pub fn api_controllers() -> Vec<Box<dyn Controller>> {
+    vec![
+        Box::new(UsersController),
+        Box::new(ResourcesController),
+        Box::new(SearchController),
+        Box::new(FeedsController),
+        Box::new(CommentsController),
+        Box::new(ImageController),
+        Box::new(InvitesController),
+        Box::new(AdminController),
+    ]
+}
+
+pub fn get_connection_pool(database_url: &str) -> DbPool {
+    let manager = ConnectionManager::<DbConnection>::new(database_url);
+    let builder = Pool::builder().test_on_check_out(true);
+    #[cfg(feature = "sqlite")]
+    let builder = builder.connection_customizer(Box::new(SqlitePragmas));
+    builder.build(manager).expect("Failed to create pool")
+}
+
+/// Settings SQLite keeps per connection, without them the foreign keys are not enforced
+/// and concurrent writers fail instead of waiting for each other.
+#[cfg(feature = "sqlite")]
+#[derive(Debug)]
+struct SqlitePragmas;
+
+#[cfg(feature = "sqlite")]
+impl diesel::r2d2::CustomizeConnection<DbConnection, diesel::r2d2::Error> for SqlitePragmas {
+    fn on_acquire(&self, conn: &mut DbConnection) -> Result<(), diesel::r2d2::Error> {
+        use diesel::connection::SimpleConnection;
+        conn.batch_execute("PRAGMA foreign_keys = ON; PRAGMA busy_timeout = 5000;")
+            .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
+/// Hourly removes the accounts deleted more than `grace_days` ago.
+fn purge_scheduler(user_repo: Arc<dyn UserRepo>, grace_days: i64) -> AdHoc {
+    AdHoc::on_liftoff("Deleted users purge", move |_| {
+        Box::pin(async move {
+            rocket::tokio::spawn(async move {
+                let mut interval =
+                    rocket::tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+                loop {
+                    interval.tick().await;
+                    let deleted_before =
+                        chrono::Utc::now().naive_utc() - chrono::Duration::days(grace_days);
+                    match user_repo.purge_deleted(deleted_before).await {
+                        Ok(0) => {}
+                        Ok(purged) => println!("Purged {} deleted users", purged),
+                        Err(e) => println!("Failed to purge deleted users: {}", e),
+                    }
+                }
+            });
+        })
+    })
 }
 
 #[catch(default)]
-async fn err(status: Status, _req: &Request<'_>) -> Json<ApiResponse<'static, Error>> {
-    Json(ApiResponse::err(Error::from(status)))
+async fn err(status: Status, _req: &Request<'_>) -> ApiError<'static> {
+    Error::from(status).into()
 }
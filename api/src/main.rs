@@ -1,24 +1,46 @@
-use diesel::{Connection, PgConnection};
-use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use diesel::Connection;
+use diesel_migrations::MigrationHarness;
 use lazy_static::lazy_static;
-use petompp_web_api::{build_rocket, get_connection_pool, PgPool, Secrets};
+use petompp_web_api::{
+    auth::keys::JwtKeys, build_rocket, config::AppConfig, get_connection_pool, state::AppState,
+    DbConnection, MIGRATIONS,
+};
 
 #[macro_use]
 extern crate rocket;
 
-pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
+fn load_config() -> AppConfig {
+    match AppConfig::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
 
 #[launch]
 fn rocket() -> _ {
     lazy_static! {
-        static ref SECRETS: Secrets = Secrets::default();
-        static ref USER_REPO: PgPool = get_connection_pool(&SECRETS);
-        static ref RESOURCES_REPO: PgPool = get_connection_pool(&SECRETS);
+        static ref CONFIG: AppConfig = load_config();
     }
 
+    let jwt_keys = match JwtKeys::new(&CONFIG.jwt) {
+        Ok(keys) => keys,
+        Err(e) => {
+            eprintln!(
+                "Invalid configuration:\n  - `jwt` keys could not be loaded: {}",
+                e
+            );
+            std::process::exit(1);
+        }
+    };
     {
-        let mut conn = PgConnection::establish(&SECRETS.database_url).unwrap();
-        conn.run_pending_migrations(MIGRATIONS).unwrap();
+        let mut conn =
+            DbConnection::establish(&CONFIG.database_url).expect("Failed to connect to database");
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("Failed to run migrations, use petompp-admin to inspect them");
     }
-    build_rocket(&SECRETS, &*USER_REPO, &*RESOURCES_REPO)
+    let state = AppState::new(get_connection_pool(&CONFIG.database_url), &CONFIG);
+    build_rocket(&CONFIG, jwt_keys, state)
 }
@@ -1,6 +1 @@
-pub mod credentials;
-pub mod password;
-pub mod resource_data;
-pub mod role;
-pub mod user;
-pub mod user_name;
+pub use petompp_web_models::models::*;
@@ -0,0 +1,168 @@
+use super::repo::CommentRepo;
+use crate::{
+    error::Error,
+    models::{
+        comment::{Comment, CommentStatus},
+        user_name::UserName,
+    },
+    repositories::user::{memory::InMemoryUserRepo, repo::UserRepo},
+};
+use rocket::async_trait;
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+/// [`CommentRepo`] keeping the comments and the bans in memory,
+/// the authors' names are read from the in-memory users.
+pub struct InMemoryCommentRepo {
+    users: Arc<InMemoryUserRepo>,
+    comments: Mutex<Vec<Comment>>,
+    bans: Mutex<BTreeSet<i32>>,
+}
+
+impl InMemoryCommentRepo {
+    pub fn new(users: Arc<InMemoryUserRepo>) -> Self {
+        Self {
+            users,
+            comments: Mutex::default(),
+            bans: Mutex::default(),
+        }
+    }
+
+    /// The `comments` with their authors' names, the ones of unknown users are left out.
+    /// Replies to those lose their parent, as with the `ON DELETE SET NULL` of the database.
+    async fn with_authors(
+        &self,
+        mut comments: Vec<Comment>,
+    ) -> Result<Vec<(Comment, UserName)>, Error> {
+        comments.sort_by_key(|c| (c.created_at, c.id));
+        let all = self
+            .comments
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|c| (c.id, c.user_id))
+            .collect::<Vec<_>>();
+        let mut purged = BTreeSet::new();
+        for (id, user_id) in all {
+            match self.users.get_by_id(user_id).await {
+                Ok(_) => {}
+                Err(Error::UserNotFound(_)) => {
+                    purged.insert(id);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        let mut authored = vec![];
+        for mut comment in comments {
+            if purged.contains(&comment.parent_id) {
+                comment.parent_id = None;
+            }
+            match self.users.get_by_id(comment.user_id).await {
+                Ok(user) => authored.push((comment, user.name)),
+                Err(Error::UserNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(authored)
+    }
+}
+
+#[async_trait]
+impl CommentRepo for InMemoryCommentRepo {
+    async fn create(&self, comment: &Comment) -> Result<Comment, Error> {
+        let mut comments = self.comments.lock().unwrap();
+        let comment = Comment {
+            id: Some(comments.iter().filter_map(|c| c.id).max().unwrap_or(0) + 1),
+            created_at: Some(chrono::Utc::now().naive_utc()),
+            ..comment.clone()
+        };
+        comments.push(comment.clone());
+        Ok(comment)
+    }
+
+    async fn get(&self, id: i32) -> Result<Comment, Error> {
+        let comments = self.comments.lock().unwrap();
+        let Some(comment) = comments.iter().find(|c| c.id == Some(id)) else {
+            return Err(Error::CommentNotFound(format!("ID: {}", id)));
+        };
+        Ok(comment.clone())
+    }
+
+    async fn get_thread(
+        &self,
+        key: &str,
+        viewer: Option<i32>,
+    ) -> Result<Vec<(Comment, UserName)>, Error> {
+        let comments = self
+            .comments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| c.key == key)
+            .filter(|c| match c.status {
+                CommentStatus::Approved => true,
+                CommentStatus::Pending => Some(c.user_id) == viewer,
+                CommentStatus::Rejected => false,
+            })
+            .cloned()
+            .collect();
+        self.with_authors(comments).await
+    }
+
+    async fn get_pending(&self) -> Result<Vec<(Comment, UserName)>, Error> {
+        let comments = self
+            .comments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| c.status == CommentStatus::Pending && !c.is_deleted())
+            .cloned()
+            .collect();
+        self.with_authors(comments).await
+    }
+
+    async fn get_by_user(&self, user_id: i32) -> Result<Vec<Comment>, Error> {
+        let mut comments = self
+            .comments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| c.user_id == user_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        comments.sort_by_key(|c| (c.created_at, c.id));
+        Ok(comments)
+    }
+
+    async fn update(&self, comment: &Comment) -> Result<Comment, Error> {
+        let mut comments = self.comments.lock().unwrap();
+        let Some(existing) = comments.iter_mut().find(|c| c.id == comment.id) else {
+            return Err(Error::CommentNotFound(format!(
+                "ID: {}",
+                comment.id.unwrap_or_default()
+            )));
+        };
+        existing.body = comment.body.clone();
+        existing.status = comment.status;
+        existing.edited_at = comment.edited_at;
+        existing.deleted_at = comment.deleted_at;
+        Ok(existing.clone())
+    }
+
+    async fn ban(&self, user_id: i32, _banned_by: i32) -> Result<(), Error> {
+        self.bans.lock().unwrap().insert(user_id);
+        self.comments
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .filter(|c| c.user_id == user_id && c.status == CommentStatus::Pending)
+            .for_each(|c| c.status = CommentStatus::Rejected);
+        Ok(())
+    }
+
+    async fn is_banned(&self, user_id: i32) -> Result<bool, Error> {
+        Ok(self.bans.lock().unwrap().contains(&user_id))
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    error::Error,
+    models::{
+        comment::{Comment, CommentStatus},
+        user_name::UserName,
+    },
+    repositories::Database,
+    schema::{comment_bans, comments, users},
+    state::AppState,
+};
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, NullableExpressionMethods,
+    OptionalExtension, QueryDsl, RunQueryDsl,
+};
+use rocket::{async_trait, http::Status, outcome::Outcome, request::FromRequest, Request};
+
+#[async_trait]
+pub trait CommentRepo: Send + Sync {
+    async fn create(&self, comment: &Comment) -> Result<Comment, Error>;
+    async fn get(&self, id: i32) -> Result<Comment, Error>;
+    /// Comments on the page of the `key` with their authors' names, oldest first.
+    /// Everyone sees the approved ones, the `viewer` their own pending ones too.
+    async fn get_thread(
+        &self,
+        key: &str,
+        viewer: Option<i32>,
+    ) -> Result<Vec<(Comment, UserName)>, Error>;
+    /// Comments waiting for a moderator with their authors' names, oldest first.
+    async fn get_pending(&self) -> Result<Vec<(Comment, UserName)>, Error>;
+    /// Every comment of the user, whatever its status, oldest first.
+    async fn get_by_user(&self, user_id: i32) -> Result<Vec<Comment>, Error>;
+    /// Saves the body, status and the edit and deletion times of the comment.
+    async fn update(&self, comment: &Comment) -> Result<Comment, Error>;
+    /// Keeps the user from commenting and rejects their pending comments.
+    async fn ban(&self, user_id: i32, banned_by: i32) -> Result<(), Error>;
+    async fn is_banned(&self, user_id: i32) -> Result<bool, Error>;
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for &'r dyn CommentRepo {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, (Status, Self::Error), ()> {
+        request
+            .guard::<&rocket::State<AppState>>()
+            .await
+            .map(|state| state.inner().comments.as_ref())
+    }
+}
+
+#[async_trait]
+impl<D: Database> CommentRepo for D {
+    async fn create(&self, comment: &Comment) -> Result<Comment, Error> {
+        let comment = comment.clone();
+        self.run(move |conn| {
+            Ok(diesel::insert_into(comments::dsl::comments)
+                .values(&comment)
+                .get_result::<Comment>(conn)?)
+        })
+        .await
+    }
+
+    async fn get(&self, id: i32) -> Result<Comment, Error> {
+        self.run(move |conn| {
+            let Some(comment) = comments::dsl::comments
+                .filter(comments::id.eq(id))
+                .first::<Comment>(conn)
+                .optional()?
+            else {
+                return Err(Error::CommentNotFound(format!("ID: {}", id)));
+            };
+            Ok(comment)
+        })
+        .await
+    }
+
+    async fn get_thread(
+        &self,
+        key: &str,
+        viewer: Option<i32>,
+    ) -> Result<Vec<(Comment, UserName)>, Error> {
+        let key = key.to_string();
+        self.run(move |conn| {
+            Ok(comments::dsl::comments
+                .inner_join(users::dsl::users)
+                .filter(comments::key.eq(&key))
+                .filter(
+                    comments::status
+                        .eq(CommentStatus::Approved)
+                        .or(comments::status
+                            .eq(CommentStatus::Pending)
+                            .and(comments::user_id.nullable().eq(viewer))),
+                )
+                .order(comments::created_at.asc())
+                .select((comments::all_columns, users::name))
+                .load::<(Comment, UserName)>(conn)?)
+        })
+        .await
+    }
+
+    async fn get_pending(&self) -> Result<Vec<(Comment, UserName)>, Error> {
+        self.run(|conn| {
+            Ok(comments::dsl::comments
+                .inner_join(users::dsl::users)
+                .filter(comments::status.eq(CommentStatus::Pending))
+                .filter(comments::deleted_at.is_null())
+                .order(comments::created_at.asc())
+                .select((comments::all_columns, users::name))
+                .load::<(Comment, UserName)>(conn)?)
+        })
+        .await
+    }
+
+    async fn get_by_user(&self, user_id: i32) -> Result<Vec<Comment>, Error> {
+        self.run(move |conn| {
+            Ok(comments::dsl::comments
+                .filter(comments::user_id.eq(user_id))
+                .order((comments::created_at.asc(), comments::id.asc()))
+                .load::<Comment>(conn)?)
+        })
+        .await
+    }
+
+    async fn update(&self, comment: &Comment) -> Result<Comment, Error> {
+        let comment = comment.clone();
+        let id = comment.id.unwrap_or_default();
+        self.run(move |conn| {
+            let Some(comment) = diesel::update(comments::dsl::comments.filter(comments::id.eq(id)))
+                .set((
+                    comments::body.eq(&comment.body),
+                    comments::status.eq(comment.status),
+                    comments::edited_at.eq(comment.edited_at),
+                    comments::deleted_at.eq(comment.deleted_at),
+                ))
+                .get_result::<Comment>(conn)
+                .optional()?
+            else {
+                return Err(Error::CommentNotFound(format!("ID: {}", id)));
+            };
+            Ok(comment)
+        })
+        .await
+    }
+
+    async fn ban(&self, user_id: i32, banned_by: i32) -> Result<(), Error> {
+        self.run(move |conn| {
+            conn.transaction(|conn| {
+                diesel::insert_into(comment_bans::dsl::comment_bans)
+                    .values((
+                        comment_bans::user_id.eq(user_id),
+                        comment_bans::banned_by.eq(Some(banned_by)),
+                    ))
+                    .on_conflict(comment_bans::user_id)
+                    .do_nothing()
+                    .execute(conn)?;
+                diesel::update(
+                    comments::dsl::comments
+                        .filter(comments::user_id.eq(user_id))
+                        .filter(comments::status.eq(CommentStatus::Pending)),
+                )
+                .set(comments::status.eq(CommentStatus::Rejected))
+                .execute(conn)?;
+                Ok(())
+            })
+        })
+        .await
+    }
+
+    async fn is_banned(&self, user_id: i32) -> Result<bool, Error> {
+        self.run(move |conn| {
+            Ok(comment_bans::dsl::comment_bans
+                .filter(comment_bans::user_id.eq(user_id))
+                .count()
+                .get_result::<i64>(conn)?
+                > 0)
+        })
+        .await
+    }
+}
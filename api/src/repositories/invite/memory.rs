@@ -0,0 +1,80 @@
+use super::repo::InviteRepo;
+use crate::{error::Error, models::invite::Invite, repositories::transaction::Snapshot};
+use rocket::async_trait;
+use std::sync::Mutex;
+
+/// [`InviteRepo`] keeping the invites in memory.
+#[derive(Default)]
+pub struct InMemoryInviteRepo {
+    invites: Mutex<Vec<Invite>>,
+}
+
+impl InMemoryInviteRepo {
+    /// Deletes the invites created by any of the users, as their purge does in the database.
+    pub(crate) fn delete_created_by(&self, user_ids: &[i32]) {
+        self.invites
+            .lock()
+            .unwrap()
+            .retain(|i| !user_ids.contains(&i.created_by));
+    }
+}
+
+impl Snapshot for InMemoryInviteRepo {
+    fn snapshot(&self) -> Self {
+        Self {
+            invites: Mutex::new(self.invites.lock().unwrap().clone()),
+        }
+    }
+
+    fn restore(&self, snapshot: Self) {
+        *self.invites.lock().unwrap() = snapshot.invites.into_inner().unwrap();
+    }
+}
+
+#[async_trait]
+impl InviteRepo for InMemoryInviteRepo {
+    async fn create(&self, invite: &Invite) -> Result<Invite, Error> {
+        let mut invites = self.invites.lock().unwrap();
+        let invite = Invite {
+            id: Some(invites.iter().filter_map(|i| i.id).max().unwrap_or(0) + 1),
+            created_at: Some(chrono::Utc::now().naive_utc()),
+            ..invite.clone()
+        };
+        invites.push(invite.clone());
+        Ok(invite)
+    }
+
+    async fn get_by_code(&self, code: &str) -> Result<Invite, Error> {
+        let invites = self.invites.lock().unwrap();
+        let Some(invite) = invites.iter().find(|i| i.code == code) else {
+            return Err(Error::InviteCodeInvalid(code.to_string()));
+        };
+        Ok(invite.clone())
+    }
+
+    async fn get_all(&self) -> Result<Vec<Invite>, Error> {
+        let mut invites = self.invites.lock().unwrap().clone();
+        invites.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(invites)
+    }
+
+    async fn use_code(&self, code: &str) -> Result<Invite, Error> {
+        let mut invites = self.invites.lock().unwrap();
+        let Some(invite) = invites
+            .iter_mut()
+            .find(|i| i.code == code && i.uses < i.max_uses)
+        else {
+            return Err(Error::InviteCodeUsedUp(code.to_string()));
+        };
+        invite.uses += 1;
+        Ok(invite.clone())
+    }
+
+    async fn delete(&self, id: i32) -> Result<Invite, Error> {
+        let mut invites = self.invites.lock().unwrap();
+        let Some(index) = invites.iter().position(|i| i.id == Some(id)) else {
+            return Err(Error::InviteNotFound(format!("ID: {}", id)));
+        };
+        Ok(invites.remove(index))
+    }
+}
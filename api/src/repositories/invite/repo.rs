@@ -0,0 +1,95 @@
+use crate::{
+    error::Error, models::invite::Invite, repositories::Database, schema::invites, state::AppState,
+};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use rocket::{async_trait, http::Status, outcome::Outcome, request::FromRequest, Request};
+
+#[async_trait]
+pub trait InviteRepo: Send + Sync {
+    async fn create(&self, invite: &Invite) -> Result<Invite, Error>;
+    async fn get_by_code(&self, code: &str) -> Result<Invite, Error>;
+    async fn get_all(&self) -> Result<Vec<Invite>, Error>;
+    async fn use_code(&self, code: &str) -> Result<Invite, Error>;
+    async fn delete(&self, id: i32) -> Result<Invite, Error>;
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for &'r dyn InviteRepo {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, (Status, Self::Error), ()> {
+        request
+            .guard::<&rocket::State<AppState>>()
+            .await
+            .map(|state| state.inner().invites.as_ref())
+    }
+}
+
+#[async_trait]
+impl<D: Database> InviteRepo for D {
+    async fn create(&self, invite: &Invite) -> Result<Invite, Error> {
+        let invite = invite.clone();
+        self.run(move |conn| {
+            Ok(diesel::insert_into(invites::dsl::invites)
+                .values(&invite)
+                .get_result::<Invite>(conn)?)
+        })
+        .await
+    }
+
+    async fn get_by_code(&self, code: &str) -> Result<Invite, Error> {
+        let code = code.to_string();
+        self.run(move |conn| {
+            let Some(invite) = invites::dsl::invites
+                .filter(invites::code.eq(&code))
+                .first::<Invite>(conn)
+                .optional()?
+            else {
+                return Err(Error::InviteCodeInvalid(code));
+            };
+            Ok(invite)
+        })
+        .await
+    }
+
+    async fn get_all(&self) -> Result<Vec<Invite>, Error> {
+        self.run(|conn| {
+            Ok(invites::dsl::invites
+                .order(invites::created_at.desc())
+                .load::<Invite>(conn)?)
+        })
+        .await
+    }
+
+    async fn use_code(&self, code: &str) -> Result<Invite, Error> {
+        let code = code.to_string();
+        self.run(move |conn| {
+            let Some(invite) = diesel::update(
+                invites::dsl::invites
+                    .filter(invites::code.eq(&code))
+                    .filter(invites::uses.lt(invites::max_uses)),
+            )
+            .set(invites::uses.eq(invites::uses + 1))
+            .get_result::<Invite>(conn)
+            .optional()?
+            else {
+                return Err(Error::InviteCodeUsedUp(code));
+            };
+            Ok(invite)
+        })
+        .await
+    }
+
+    async fn delete(&self, id: i32) -> Result<Invite, Error> {
+        self.run(move |conn| {
+            let Some(invite) = diesel::delete(invites::dsl::invites.filter(invites::id.eq(id)))
+                .get_result::<Invite>(conn)
+                .optional()?
+            else {
+                return Err(Error::InviteNotFound(format!("ID: {}", id)));
+            };
+            Ok(invite)
+        })
+        .await
+    }
+}
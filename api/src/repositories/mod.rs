@@ -1,3 +1,46 @@
+use crate::{error::Error, DbConnection, DbPool};
+use rocket::async_trait;
+
+pub mod comment;
+pub mod invite;
 pub mod query_config;
 pub mod resources;
+pub mod search;
+pub mod transaction;
+pub mod upload;
 pub mod user;
+
+/// Where the Diesel repositories run their queries,
+/// a pooled connection or the one of a [`transaction::DbTransaction`].
+#[async_trait]
+pub trait Database: Send + Sync {
+    async fn run<T, F>(&self, query: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut DbConnection) -> Result<T, Error> + Send + 'static;
+}
+
+#[async_trait]
+impl Database for DbPool {
+    /// Runs the `query` on a pooled connection on the blocking thread pool,
+    /// so the async workers keep serving requests while it waits for the database.
+    async fn run<T, F>(&self, query: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut DbConnection) -> Result<T, Error> + Send + 'static,
+    {
+        let pool = self.clone();
+        blocking(move || query(&mut pool.get()?)).await
+    }
+}
+
+/// Runs the blocking `f` on the blocking thread pool and waits for it without holding a worker.
+pub async fn blocking<T, F>(f: F) -> Result<T, Error>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, Error> + Send + 'static,
+{
+    rocket::tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Error::DatabaseConnectionError(e.to_string()))?
+}
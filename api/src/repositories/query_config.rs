@@ -1,3 +1,4 @@
+use crate::error::{Error, QueryValidationError, ValidationError};
 use deref_derive::{Deref, DerefMut};
 use rocket::{
     async_trait,
@@ -5,16 +6,56 @@ use rocket::{
     form::{self, DataField, FromFormField, ValueField},
     FromForm,
 };
-use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Display};
+use utoipa::IntoParams;
 
-#[derive(Debug, Clone, FromForm)]
+#[derive(Debug, Clone, FromForm, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct QueryConfig {
+    /// `all`, a single page or a `start-end` page range.
+    #[param(value_type = String, example = "1-3")]
     pub range: PageRange,
+    /// Items per page, 20 by default.
+    #[param(value_type = Option<i64>)]
     pub items: Option<ItemCount>,
     pub sort: Option<String>,
+    /// `asc` or `desc`.
+    #[param(value_type = Option<String>)]
     pub order: Option<SortOrder>,
 }
 
+/// A column the in-memory repositories sort by, named as in [`QueryConfig::sort`].
+pub type MemoryColumn<T> = (&'static str, fn(&T, &T) -> Ordering);
+
+impl QueryConfig {
+    /// Pages and sorts `items` the same way the query built by [`crate::impl_query_config!`] does.
+    pub fn apply<T>(
+        &self,
+        mut items: Vec<T>,
+        columns: &[MemoryColumn<T>],
+    ) -> Result<Vec<T>, Error> {
+        if let (Some(column), Some(order)) = (&self.sort, &self.order) {
+            let Some((_, cmp)) = columns.iter().find(|(name, _)| name == column) else {
+                return Err(Error::ValidationError(ValidationError::Query(
+                    QueryValidationError::InvalidColumn(column.to_string()),
+                )));
+            };
+            items.sort_by(|a, b| match order {
+                SortOrder::Asc => cmp(a, b),
+                SortOrder::Desc => cmp(b, a),
+            });
+        }
+        let (Some(count), Some((start, pages))) = (self.items, self.range.pages()) else {
+            return Ok(items);
+        };
+        Ok(items
+            .into_iter()
+            .skip((*count * start.max(0)).max(0) as usize)
+            .take((*count * pages).max(0) as usize)
+            .collect())
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deref, DerefMut)]
 pub struct ItemCount(i64);
 
@@ -113,6 +154,17 @@ pub enum PageRange {
     Range(i64, i64),
 }
 
+impl PageRange {
+    /// The first page and the number of pages, `None` for all of them.
+    fn pages(&self) -> Option<(i64, i64)> {
+        match self {
+            PageRange::All => None,
+            PageRange::Single(i) => Some((*i, 1)),
+            PageRange::Range(start, end) => Some((*start, (end - start).max(0) + 1)),
+        }
+    }
+}
+
 #[async_trait]
 impl<'r> FromFormField<'r> for PageRange {
     fn from_value(field: ValueField<'r>) -> form::Result<'r, Self> {
@@ -165,16 +217,23 @@ impl<'r> FromFormField<'r> for PageRange {
     }
 }
 
+/// Implements `$type::get_query` building the boxed query of `$table` described by a [`QueryConfig`],
+/// `$backend` defaults to the [`crate::Db`] the API is built for.
 #[macro_export]
 macro_rules! impl_query_config {
     ($dsl_table:expr, $table:ty, $boxed:ty, $type:ident, [$(($column:expr, $name:expr),)*]) => {
+        $crate::impl_query_config!(
+            $dsl_table, $table, $boxed, $crate::Db, $type, [$(($column, $name),)*]
+        );
+    };
+    ($dsl_table:expr, $table:ty, $boxed:ty, $backend:ty, $type:ident, [$(($column:expr, $name:expr),)*]) => {
         use $crate::{
             repositories::{
                 query_config::{PageRange, QueryConfig, SortOrder},
             },
             error::{Error, ValidationError, QueryValidationError},
         };
-        use diesel::{pg::Pg, query_builder::QueryFragment, AppearsOnTable, ExpressionMethods, QueryDsl};
+        use diesel::{query_builder::QueryFragment, AppearsOnTable, ExpressionMethods, QueryDsl};
 
         pub trait $type {
             fn get_query(&self) -> Result<$boxed, Error>;
@@ -266,7 +325,7 @@ macro_rules! impl_query_config {
             order: &SortOrder,
         ) -> $boxed
         where
-            U: ExpressionMethods + QueryFragment<Pg>,
+            U: ExpressionMethods + QueryFragment<$backend>,
         {
             match order {
                 SortOrder::Asc => query.order(column.asc()),
@@ -0,0 +1,180 @@
+use super::repo::ResourcesRepo;
+use crate::{
+    config::ResourcesCacheConfig,
+    error::Error,
+    models::{page_meta::PageMeta, resource_data::ResourceData},
+};
+use chrono::NaiveDateTime;
+use lru::LruCache;
+use rocket::async_trait;
+use serde::Serialize;
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+struct Entry {
+    value: (String, NaiveDateTime),
+    expires_at: Instant,
+}
+
+struct Entries {
+    values: LruCache<(String, String), Entry>,
+    /// Bumped by every write, values read before it are not cached.
+    generation: u64,
+}
+
+/// [`ResourcesRepo`] serving [`ResourcesRepo::get`] from a bounded LRU cache in front of `R`.
+/// Writes go straight to `R` and drop the cached values of the key in every language.
+pub struct CachedResourcesRepo<R> {
+    inner: R,
+    ttl: Duration,
+    entries: Option<Mutex<Entries>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<R: ResourcesRepo> CachedResourcesRepo<R> {
+    pub fn new(inner: R, config: ResourcesCacheConfig) -> Self {
+        Self {
+            inner,
+            ttl: Duration::from_secs(config.ttl),
+            entries: NonZeroUsize::new(config.capacity).map(|capacity| {
+                Mutex::new(Entries {
+                    values: LruCache::new(capacity),
+                    generation: 0,
+                })
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    fn invalidate(&self, key: &str) {
+        let Some(entries) = &self.entries else {
+            return;
+        };
+        let mut entries = entries.lock().unwrap();
+        entries.generation += 1;
+        let stale = entries
+            .values
+            .iter()
+            .filter(|((k, _), _)| k == key)
+            .map(|(k, _)| k.clone())
+            .collect::<Vec<_>>();
+        for k in stale {
+            entries.values.pop(&k);
+        }
+    }
+}
+
+#[async_trait]
+impl<R: ResourcesRepo> ResourcesRepo for CachedResourcesRepo<R> {
+    async fn get(&self, key: &str, lang: &str) -> Result<(String, NaiveDateTime), Error> {
+        let Some(entries) = &self.entries else {
+            return self.inner.get(key, lang).await;
+        };
+        let cache_key = (key.to_string(), lang.to_string());
+        let generation = {
+            let mut entries = entries.lock().unwrap();
+            match entries.values.get(&cache_key) {
+                Some(entry) if entry.expires_at > Instant::now() => {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return Ok(entry.value.clone());
+                }
+                Some(_) => {
+                    entries.values.pop(&cache_key);
+                }
+                None => {}
+            }
+            entries.generation
+        };
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let value = self.inner.get(key, lang).await?;
+        let mut entries = entries.lock().unwrap();
+        if entries.generation == generation {
+            entries.values.put(
+                cache_key,
+                Entry {
+                    value: value.clone(),
+                    expires_at: Instant::now() + self.ttl,
+                },
+            );
+        }
+        Ok(value)
+    }
+
+    async fn get_all(&self) -> Result<Vec<ResourceData>, Error> {
+        self.inner.get_all().await
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, Error> {
+        self.inner.exists(key).await
+    }
+
+    async fn create(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        let result = self.inner.create(data).await;
+        if let Some(key) = &data.key {
+            self.invalidate(key);
+        }
+        result
+    }
+
+    async fn update(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        let result = self.inner.update(data).await;
+        if let Some(key) = &data.key {
+            self.invalidate(key);
+        }
+        result
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), Error> {
+        let result = self.inner.delete(key).await;
+        self.invalidate(key);
+        result
+    }
+
+    async fn get_meta(&self, key: &str, lang: &str) -> Result<PageMeta, Error> {
+        self.inner.get_meta(key, lang).await
+    }
+
+    async fn update_meta(&self, meta: &PageMeta) -> Result<PageMeta, Error> {
+        self.inner.update_meta(meta).await
+    }
+
+    fn clear_cache(&self) {
+        let Some(entries) = &self.entries else {
+            return;
+        };
+        let mut entries = entries.lock().unwrap();
+        entries.generation += 1;
+        entries.values.clear();
+    }
+
+    fn cache_metrics(&self) -> Option<CacheMetrics> {
+        Some(CacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self
+                .entries
+                .as_ref()
+                .map_or(0, |entries| entries.lock().unwrap().values.len()),
+        })
+    }
+}
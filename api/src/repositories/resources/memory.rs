@@ -0,0 +1,137 @@
+use super::repo::{pick_meta, ResourcesRepo};
+use crate::{
+    error::{Error, ResourceDataValidationError, ValidationError},
+    models::{page_meta::PageMeta, resource_data::ResourceData},
+    repositories::transaction::Snapshot,
+};
+use chrono::NaiveDateTime;
+use rocket::{async_trait, http::Status};
+use std::{collections::BTreeMap, sync::Mutex};
+
+/// [`ResourcesRepo`] keeping the resources in memory.
+/// Missing keys fail with the same `Status(404)` the Diesel repository reports.
+#[derive(Default)]
+pub struct InMemoryResourcesRepo {
+    resources: Mutex<BTreeMap<String, ResourceData>>,
+    metas: Mutex<BTreeMap<(String, String), PageMeta>>,
+}
+
+fn not_found() -> Error {
+    Error::from(Status::NotFound)
+}
+
+impl Snapshot for InMemoryResourcesRepo {
+    fn snapshot(&self) -> Self {
+        Self {
+            resources: Mutex::new(self.resources.lock().unwrap().clone()),
+            metas: Mutex::new(self.metas.lock().unwrap().clone()),
+        }
+    }
+
+    fn restore(&self, snapshot: Self) {
+        *self.resources.lock().unwrap() = snapshot.resources.into_inner().unwrap();
+        *self.metas.lock().unwrap() = snapshot.metas.into_inner().unwrap();
+    }
+}
+
+#[async_trait]
+impl ResourcesRepo for InMemoryResourcesRepo {
+    async fn get(&self, key: &str, lang: &str) -> Result<(String, NaiveDateTime), Error> {
+        let resources = self.resources.lock().unwrap();
+        let resource = resources.get(key).ok_or_else(not_found)?;
+        let value = match lang {
+            "pl" => resource.pl.clone().or(resource.en.clone()),
+            _ => resource.en.clone(),
+        };
+        Ok((
+            value.unwrap_or_default(),
+            resource.updated_at.unwrap_or_default(),
+        ))
+    }
+
+    async fn get_all(&self) -> Result<Vec<ResourceData>, Error> {
+        Ok(self.resources.lock().unwrap().values().cloned().collect())
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, Error> {
+        Ok(self.resources.lock().unwrap().contains_key(key))
+    }
+
+    async fn create(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        let mut resources = self.resources.lock().unwrap();
+        let key = data
+            .key
+            .clone()
+            .ok_or(Error::ValidationError(ValidationError::ResourceData(
+                ResourceDataValidationError::KeyMissing,
+            )))?;
+        if data.en.is_none() || resources.contains_key(&key) {
+            return Err(Error::DatabaseError(format!(
+                "Resource `{}` could not be inserted",
+                key
+            )));
+        }
+        let data = ResourceData {
+            updated_at: Some(chrono::Utc::now().naive_utc()),
+            ..data.clone()
+        };
+        resources.insert(key, data.clone());
+        Ok(data)
+    }
+
+    async fn update(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        let mut resources = self.resources.lock().unwrap();
+        let key = data
+            .key
+            .clone()
+            .ok_or(Error::ValidationError(ValidationError::ResourceData(
+                ResourceDataValidationError::KeyMissing,
+            )))?;
+        if data.en.is_none() && data.pl.is_none() {
+            return Err(Error::ValidationError(ValidationError::ResourceData(
+                ResourceDataValidationError::ValueMissing,
+            )));
+        }
+        let resource = resources.get_mut(&key).ok_or_else(not_found)?;
+        if let Some(en) = &data.en {
+            resource.en = Some(en.clone());
+        }
+        if let Some(pl) = &data.pl {
+            resource.pl = Some(pl.clone());
+        }
+        resource.updated_at = Some(chrono::Utc::now().naive_utc());
+        Ok(resource.clone())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), Error> {
+        self.resources.lock().unwrap().remove(key);
+        self.metas.lock().unwrap().retain(|(k, _), _| k != key);
+        Ok(())
+    }
+
+    async fn get_meta(&self, key: &str, lang: &str) -> Result<PageMeta, Error> {
+        let metas = self
+            .metas
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|meta| meta.key == key && (meta.lang == lang || meta.lang == "en"))
+            .cloned()
+            .collect();
+        Ok(pick_meta(metas, key, lang))
+    }
+
+    async fn update_meta(&self, meta: &PageMeta) -> Result<PageMeta, Error> {
+        if !self.resources.lock().unwrap().contains_key(&meta.key) {
+            return Err(Error::DatabaseError(format!(
+                "Metadata of `{}` could not be inserted",
+                meta.key
+            )));
+        }
+        self.metas
+            .lock()
+            .unwrap()
+            .insert((meta.key.clone(), meta.lang.clone()), meta.clone());
+        Ok(meta.clone())
+    }
+}
@@ -1 +1,3 @@
+pub mod cache;
+pub mod memory;
 pub mod repo;
@@ -1,18 +1,36 @@
+use super::cache::CacheMetrics;
 use crate::{
     error::{Error, ResourceDataValidationError, ValidationError},
-    models::resource_data::ResourceData,
-    schema::resources,
-    PgPool,
+    models::{page_meta::PageMeta, resource_data::ResourceData},
+    repositories::Database,
+    schema::{page_meta, resources},
+    state::AppState,
+};
+use chrono::NaiveDateTime;
+use diesel::{
+    dsl::{exists, now},
+    ExpressionMethods, QueryDsl, RunQueryDsl,
 };
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
 use rocket::{async_trait, http::Status, outcome::Outcome, request::FromRequest, Request};
 
+#[async_trait]
 pub trait ResourcesRepo: Send + Sync {
-    fn get(&self, key: &str, lang: &str) -> Result<String, Error>;
-    fn get_all(&self) -> Result<Vec<ResourceData>, Error>;
-    fn create(&self, data: &ResourceData) -> Result<ResourceData, Error>;
-    fn update(&self, data: &ResourceData) -> Result<ResourceData, Error>;
-    fn delete(&self, key: &str) -> Result<(), Error>;
+    /// The value in the language, falling back to English, and the time of the last change.
+    async fn get(&self, key: &str, lang: &str) -> Result<(String, NaiveDateTime), Error>;
+    async fn get_all(&self) -> Result<Vec<ResourceData>, Error>;
+    async fn exists(&self, key: &str) -> Result<bool, Error>;
+    async fn create(&self, data: &ResourceData) -> Result<ResourceData, Error>;
+    async fn update(&self, data: &ResourceData) -> Result<ResourceData, Error>;
+    async fn delete(&self, key: &str) -> Result<(), Error>;
+    /// Metadata of the page in the language, falling back to English and then to an empty one.
+    async fn get_meta(&self, key: &str, lang: &str) -> Result<PageMeta, Error>;
+    async fn update_meta(&self, meta: &PageMeta) -> Result<PageMeta, Error>;
+    /// Hits and misses of the cache in front of the repository, if there is one.
+    fn cache_metrics(&self) -> Option<CacheMetrics> {
+        None
+    }
+    /// Drops every cached value, after writes that went around the cache.
+    fn clear_cache(&self) {}
 }
 
 #[async_trait]
@@ -21,76 +39,162 @@ impl<'r> FromRequest<'r> for &'r dyn ResourcesRepo {
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, (Status, Self::Error), ()> {
         request
-            .guard::<&rocket::State<&dyn ResourcesRepo>>()
+            .guard::<&rocket::State<AppState>>()
             .await
-            .map(|pool| *pool.inner())
+            .map(|state| state.inner().resources.as_ref())
     }
 }
 
-impl ResourcesRepo for PgPool {
-    fn get(&self, key: &str, lang: &str) -> Result<String, Error> {
-        let mut conn = self.get()?;
-        let q = resources::dsl::resources.filter(resources::key.eq(key));
-        let res = match lang {
-            "pl" => {
-                let (pl, en) = q
-                    .select((resources::pl, resources::en))
-                    .get_result::<(Option<String>, String)>(&mut conn)?;
-                pl.unwrap_or(en)
-            }
-            _ => q.select(resources::en).get_result::<String>(&mut conn)?,
-        };
-        Ok(res)
+#[async_trait]
+impl<D: Database> ResourcesRepo for D {
+    async fn get(&self, key: &str, lang: &str) -> Result<(String, NaiveDateTime), Error> {
+        let (key, lang) = (key.to_string(), lang.to_string());
+        self.run(move |conn| {
+            let q = resources::dsl::resources.filter(resources::key.eq(&key));
+            let res = match lang.as_str() {
+                "pl" => {
+                    let (pl, en, updated_at) = q
+                        .select((resources::pl, resources::en, resources::updated_at))
+                        .get_result::<(Option<String>, String, NaiveDateTime)>(conn)
+                        .map_err(not_found_as_status)?;
+                    (pl.unwrap_or(en), updated_at)
+                }
+                _ => q
+                    .select((resources::en, resources::updated_at))
+                    .get_result::<(String, NaiveDateTime)>(conn)
+                    .map_err(not_found_as_status)?,
+            };
+            Ok(res)
+        })
+        .await
     }
 
-    fn get_all(&self) -> Result<Vec<ResourceData>, Error> {
-        let mut conn = self.get()?;
-        let res = resources::dsl::resources.load::<ResourceData>(&mut conn)?;
-        Ok(res)
+    async fn get_all(&self) -> Result<Vec<ResourceData>, Error> {
+        self.run(|conn| Ok(resources::dsl::resources.load::<ResourceData>(conn)?))
+            .await
     }
 
-    fn create(&self, data: &ResourceData) -> Result<ResourceData, Error> {
-        let mut conn = self.get()?;
-        let res = diesel::insert_into(resources::dsl::resources)
-            .values(data)
-            .get_result::<ResourceData>(&mut conn)?;
-        Ok(res)
+    async fn exists(&self, key: &str) -> Result<bool, Error> {
+        let key = key.to_string();
+        self.run(move |conn| {
+            Ok(diesel::select(exists(
+                resources::dsl::resources.filter(resources::key.eq(key)),
+            ))
+            .get_result::<bool>(conn)?)
+        })
+        .await
     }
 
-    fn update(&self, data: &ResourceData) -> Result<ResourceData, Error> {
-        let mut conn = self.get()?;
+    async fn create(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        let data = data.clone();
+        self.run(move |conn| {
+            Ok(diesel::insert_into(resources::dsl::resources)
+                .values(&data)
+                .get_result::<ResourceData>(conn)?)
+        })
+        .await
+    }
+
+    async fn update(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        let data = data.clone();
         let key = data
             .key
             .clone()
             .ok_or(Error::ValidationError(ValidationError::ResourceData(
                 ResourceDataValidationError::KeyMissing,
             )))?;
-        let res = match (&data.en, &data.pl) {
-            (Some(en), Some(pl)) => diesel::update(resources::dsl::resources)
-                .filter(resources::dsl::key.eq(key))
-                .set((resources::dsl::en.eq(en), resources::dsl::pl.eq(pl)))
-                .get_result::<ResourceData>(&mut conn)?,
-            (Some(en), None) => diesel::update(resources::dsl::resources)
-                .filter(resources::dsl::key.eq(key))
-                .set(resources::dsl::en.eq(en))
-                .get_result::<ResourceData>(&mut conn)?,
-            (None, Some(pl)) => diesel::update(resources::dsl::resources)
-                .filter(resources::dsl::key.eq(key))
-                .set(resources::dsl::pl.eq(pl))
-                .get_result::<ResourceData>(&mut conn)?,
-            _ => {
-                return Err(Error::ValidationError(ValidationError::ResourceData(
-                    ResourceDataValidationError::ValueMissing,
-                )))
-            }
-        };
-        Ok(res)
+        self.run(move |conn| {
+            let res = match (&data.en, &data.pl) {
+                (Some(en), Some(pl)) => diesel::update(resources::dsl::resources)
+                    .filter(resources::dsl::key.eq(key))
+                    .set((
+                        resources::dsl::en.eq(en),
+                        resources::dsl::pl.eq(pl),
+                        resources::dsl::updated_at.eq(now),
+                    ))
+                    .get_result::<ResourceData>(conn)
+                    .map_err(not_found_as_status)?,
+                (Some(en), None) => diesel::update(resources::dsl::resources)
+                    .filter(resources::dsl::key.eq(key))
+                    .set((
+                        resources::dsl::en.eq(en),
+                        resources::dsl::updated_at.eq(now),
+                    ))
+                    .get_result::<ResourceData>(conn)
+                    .map_err(not_found_as_status)?,
+                (None, Some(pl)) => diesel::update(resources::dsl::resources)
+                    .filter(resources::dsl::key.eq(key))
+                    .set((
+                        resources::dsl::pl.eq(pl),
+                        resources::dsl::updated_at.eq(now),
+                    ))
+                    .get_result::<ResourceData>(conn)
+                    .map_err(not_found_as_status)?,
+                _ => {
+                    return Err(Error::ValidationError(ValidationError::ResourceData(
+                        ResourceDataValidationError::ValueMissing,
+                    )))
+                }
+            };
+            Ok(res)
+        })
+        .await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), Error> {
+        let key = key.to_string();
+        self.run(move |conn| {
+            diesel::delete(page_meta::dsl::page_meta.filter(page_meta::dsl::key.eq(&key)))
+                .execute(conn)?;
+            diesel::delete(resources::dsl::resources.filter(resources::dsl::key.eq(key)))
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
     }
 
-    fn delete(&self, key: &str) -> Result<(), Error> {
-        let mut conn = self.get()?;
-        diesel::delete(resources::dsl::resources.filter(resources::dsl::key.eq(key)))
-            .execute(&mut conn)?;
-        Ok(())
+    async fn get_meta(&self, key: &str, lang: &str) -> Result<PageMeta, Error> {
+        let (key, lang) = (key.to_string(), lang.to_string());
+        self.run(move |conn| {
+            let metas = page_meta::dsl::page_meta
+                .filter(page_meta::key.eq(&key))
+                .filter(page_meta::lang.eq_any([lang.as_str(), "en"]))
+                .load::<PageMeta>(conn)?;
+            Ok(pick_meta(metas, &key, &lang))
+        })
+        .await
+    }
+
+    async fn update_meta(&self, meta: &PageMeta) -> Result<PageMeta, Error> {
+        let meta = meta.clone();
+        self.run(move |conn| {
+            Ok(diesel::insert_into(page_meta::dsl::page_meta)
+                .values(&meta)
+                .on_conflict((page_meta::key, page_meta::lang))
+                .do_update()
+                .set(&meta)
+                .get_result::<PageMeta>(conn)?)
+        })
+        .await
+    }
+}
+
+/// A missing resource is the client's mistake, not a failure of the database.
+fn not_found_as_status(e: diesel::result::Error) -> Error {
+    match e {
+        diesel::result::Error::NotFound => Error::from(Status::NotFound),
+        _ => e.into(),
+    }
+}
+
+/// The metadata in the `lang` out of the `metas` of the key, the English one serves the others.
+pub(super) fn pick_meta(metas: Vec<PageMeta>, key: &str, lang: &str) -> PageMeta {
+    let (own, en): (Vec<_>, Vec<_>) = metas.into_iter().partition(|meta| meta.lang == lang);
+    match own.into_iter().chain(en).next() {
+        Some(meta) => PageMeta {
+            lang: lang.to_string(),
+            ..meta
+        },
+        None => PageMeta::new(key, lang),
     }
 }
@@ -0,0 +1,37 @@
+use super::repo::SearchRepo;
+use crate::{
+    error::Error,
+    models::search::{search, SearchDocument, SearchHit},
+    repositories::resources::{memory::InMemoryResourcesRepo, repo::ResourcesRepo},
+};
+use rocket::async_trait;
+use std::sync::Arc;
+
+/// [`SearchRepo`] through the content of the in-memory repositories.
+pub struct InMemorySearchRepo {
+    pub resources: Arc<InMemoryResourcesRepo>,
+}
+
+#[async_trait]
+impl SearchRepo for InMemorySearchRepo {
+    async fn search(&self, query: &str, lang: &str, limit: i64) -> Result<Vec<SearchHit>, Error> {
+        let documents = self
+            .resources
+            .get_all()
+            .await?
+            .into_iter()
+            .filter_map(|resource| {
+                let body = match lang {
+                    "pl" => resource.pl.or(resource.en),
+                    _ => resource.en,
+                };
+                Some(SearchDocument {
+                    kind: "resource".to_string(),
+                    key: resource.key?,
+                    body: body?,
+                })
+            })
+            .collect::<Vec<_>>();
+        search(documents, query, limit.max(0) as usize)
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    error::Error,
+    models::search::{query_terms, SearchHit},
+    repositories::Database,
+    state::AppState,
+    DbConnection,
+};
+use diesel::{sql_types::Text, RunQueryDsl};
+use rocket::{async_trait, http::Status, outcome::Outcome, request::FromRequest, Request};
+
+/// Full-text search through the `search_documents` view,
+/// content tables become searchable by being added to it.
+#[async_trait]
+pub trait SearchRepo: Send + Sync {
+    /// Best `limit` matches of the `query` in the content of the `lang`, the best one first.
+    async fn search(&self, query: &str, lang: &str, limit: i64) -> Result<Vec<SearchHit>, Error>;
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for &'r dyn SearchRepo {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, (Status, Self::Error), ()> {
+        request
+            .guard::<&rocket::State<AppState>>()
+            .await
+            .map(|state| state.inner().search.as_ref())
+    }
+}
+
+#[async_trait]
+impl<D: Database> SearchRepo for D {
+    async fn search(&self, query: &str, lang: &str, limit: i64) -> Result<Vec<SearchHit>, Error> {
+        query_terms(query)?;
+        let (query, lang) = (query.to_string(), lang.to_string());
+        self.run(move |conn| search_documents(conn, &query, &lang, limit))
+            .await
+    }
+}
+
+/// Ranks with the dictionary of the language, Postgres has none for Polish so it only splits the words.
+#[cfg(feature = "postgres")]
+fn search_documents(
+    conn: &mut DbConnection,
+    query: &str,
+    lang: &str,
+    limit: i64,
+) -> Result<Vec<SearchHit>, Error> {
+    use diesel::sql_types::BigInt;
+
+    let dictionary = match lang {
+        "pl" => "simple",
+        _ => "english",
+    };
+    Ok(diesel::sql_query(
+        "SELECT kind, key, \
+            ts_headline($1::regconfig, body, query, \
+                'StartSel=<mark>, StopSel=</mark>, MinWords=8, MaxWords=24, \
+                MaxFragments=2, FragmentDelimiter=\" … \"') AS snippet, \
+            ts_rank(document, query) AS rank \
+        FROM search_documents, websearch_to_tsquery($1::regconfig, $2) AS query \
+        WHERE lang = $3 AND document @@ query \
+        ORDER BY rank DESC, key \
+        LIMIT $4",
+    )
+    .bind::<Text, _>(dictionary)
+    .bind::<Text, _>(query)
+    .bind::<Text, _>(match lang {
+        "pl" => "pl",
+        _ => "en",
+    })
+    .bind::<BigInt, _>(limit)
+    .load::<SearchHit>(conn)?)
+}
+
+/// SQLite has no full-text search without an extension, so the documents are matched in memory.
+#[cfg(feature = "sqlite")]
+fn search_documents(
+    conn: &mut DbConnection,
+    query: &str,
+    lang: &str,
+    limit: i64,
+) -> Result<Vec<SearchHit>, Error> {
+    use crate::models::search::{search, SearchDocument};
+
+    let documents =
+        diesel::sql_query("SELECT kind, key, body FROM search_documents WHERE lang = ?")
+            .bind::<Text, _>(match lang {
+                "pl" => "pl",
+                _ => "en",
+            })
+            .load::<SearchDocument>(conn)?;
+    search(documents, query, limit.max(0) as usize)
+}
@@ -0,0 +1,141 @@
+use super::{
+    invite::{memory::InMemoryInviteRepo, repo::InviteRepo},
+    resources::{memory::InMemoryResourcesRepo, repo::ResourcesRepo},
+    upload::{memory::InMemoryUploadRepo, repo::UploadRepo},
+    user::{memory::InMemoryUserRepo, repo::UserRepo},
+    Database,
+};
+use crate::{error::Error, DbConnection, DbPool};
+use diesel::Connection;
+use rocket::{async_trait, futures::future::BoxFuture, tokio::runtime::Handle};
+use std::sync::{Arc, Mutex};
+
+/// Repositories taking part in one transaction.
+#[derive(Clone, Copy)]
+pub struct Repositories<'t> {
+    pub users: &'t dyn UserRepo,
+    pub resources: &'t dyn ResourcesRepo,
+    pub invites: &'t dyn InviteRepo,
+    pub uploads: &'t dyn UploadRepo,
+}
+
+/// Work done in a transaction, its future may borrow the [`Repositories`].
+pub type Work =
+    Box<dyn for<'t> FnOnce(Repositories<'t>) -> BoxFuture<'t, Result<(), Error>> + Send>;
+
+/// Runs [`Work`] atomically, its changes are kept only when it returns `Ok`.
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    async fn transaction(&self, work: Work) -> Result<(), Error>;
+}
+
+/// Runs `work` in a transaction of the `unit` and returns its output,
+/// nothing it changed is kept when it fails with any [`Error`].
+pub async fn atomically<T, F>(unit: &dyn UnitOfWork, work: F) -> Result<T, Error>
+where
+    T: Send + 'static,
+    F: for<'t> FnOnce(Repositories<'t>) -> BoxFuture<'t, Result<T, Error>> + Send + 'static,
+{
+    let output = Arc::new(Mutex::new(None));
+    let slot = output.clone();
+    unit.transaction(boxed(move |repos| {
+        Box::pin(async move {
+            let value = work(repos).await?;
+            *slot.lock().unwrap() = Some(value);
+            Ok(())
+        })
+    }))
+    .await?;
+    let value = output.lock().unwrap().take();
+    Ok(value.expect("committed work has an output"))
+}
+
+/// Pins the signature of the closure, so its future may borrow the repositories.
+fn boxed<F>(work: F) -> Work
+where
+    F: for<'t> FnOnce(Repositories<'t>) -> BoxFuture<'t, Result<(), Error>> + Send + 'static,
+{
+    Box::new(work)
+}
+
+/// Connection of a running database transaction,
+/// the repositories implemented for every [`Database`] run their queries on it.
+pub struct DbTransaction<'c> {
+    conn: Mutex<&'c mut DbConnection>,
+}
+
+#[async_trait]
+impl Database for DbTransaction<'_> {
+    async fn run<T, F>(&self, query: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut DbConnection) -> Result<T, Error> + Send + 'static,
+    {
+        // The whole transaction already runs on a blocking thread.
+        query(&mut self.conn.lock().unwrap())
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for DbPool {
+    async fn transaction(&self, work: Work) -> Result<(), Error> {
+        let handle = Handle::current();
+        self.run(move |conn| {
+            conn.transaction(|conn| {
+                let tx = DbTransaction {
+                    conn: Mutex::new(conn),
+                };
+                handle.block_on(work(Repositories {
+                    users: &tx,
+                    resources: &tx,
+                    invites: &tx,
+                    uploads: &tx,
+                }))
+            })
+        })
+        .await
+    }
+}
+
+/// In-memory repository whose content can be put back when a transaction fails.
+pub trait Snapshot {
+    fn snapshot(&self) -> Self;
+    fn restore(&self, snapshot: Self);
+}
+
+/// [`UnitOfWork`] of the in-memory repositories, the work runs on them directly
+/// and their content is restored when it fails.
+/// Unlike the database it doesn't isolate the work from the concurrent requests.
+pub struct InMemoryUnitOfWork {
+    pub users: Arc<InMemoryUserRepo>,
+    pub resources: Arc<InMemoryResourcesRepo>,
+    pub invites: Arc<InMemoryInviteRepo>,
+    pub uploads: Arc<InMemoryUploadRepo>,
+}
+
+#[async_trait]
+impl UnitOfWork for InMemoryUnitOfWork {
+    async fn transaction(&self, work: Work) -> Result<(), Error> {
+        let snapshots = (
+            self.users.snapshot(),
+            self.resources.snapshot(),
+            self.invites.snapshot(),
+            self.uploads.snapshot(),
+        );
+        let result = work(Repositories {
+            users: self.users.as_ref(),
+            resources: self.resources.as_ref(),
+            invites: self.invites.as_ref(),
+            uploads: self.uploads.as_ref(),
+        })
+        .await;
+        if result.is_err() {
+            let (users, resources, invites, uploads) = snapshots;
+            self.users.restore(users);
+            self.resources.restore(resources);
+            self.invites.restore(invites);
+            self.uploads.restore(uploads);
+        }
+        result
+    }
+}
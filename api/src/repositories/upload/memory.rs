@@ -0,0 +1,61 @@
+use super::repo::UploadRepo;
+use crate::{error::Error, models::upload::Upload, repositories::transaction::Snapshot};
+use rocket::async_trait;
+use std::sync::Mutex;
+
+/// [`UploadRepo`] keeping the upload records in memory.
+#[derive(Default)]
+pub struct InMemoryUploadRepo {
+    uploads: Mutex<Vec<Upload>>,
+}
+
+impl InMemoryUploadRepo {
+    /// Detaches the uploads from the user, as their anonymization does in the database.
+    pub(crate) fn detach(&self, user_id: i32) {
+        self.uploads
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .filter(|u| u.user_id == Some(user_id))
+            .for_each(|u| u.user_id = None);
+    }
+}
+
+impl Snapshot for InMemoryUploadRepo {
+    fn snapshot(&self) -> Self {
+        Self {
+            uploads: Mutex::new(self.uploads.lock().unwrap().clone()),
+        }
+    }
+
+    fn restore(&self, snapshot: Self) {
+        *self.uploads.lock().unwrap() = snapshot.uploads.into_inner().unwrap();
+    }
+}
+
+#[async_trait]
+impl UploadRepo for InMemoryUploadRepo {
+    async fn create(&self, upload: &Upload) -> Result<Upload, Error> {
+        let mut uploads = self.uploads.lock().unwrap();
+        let upload = Upload {
+            id: Some(uploads.iter().filter_map(|u| u.id).max().unwrap_or(0) + 1),
+            created_at: Some(chrono::Utc::now().naive_utc()),
+            ..upload.clone()
+        };
+        uploads.push(upload.clone());
+        Ok(upload)
+    }
+
+    async fn get_by_user(&self, user_id: i32) -> Result<Vec<Upload>, Error> {
+        let mut uploads = self
+            .uploads
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|u| u.user_id == Some(user_id))
+            .cloned()
+            .collect::<Vec<_>>();
+        uploads.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(uploads)
+    }
+}
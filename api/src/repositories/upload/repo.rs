@@ -0,0 +1,46 @@
+use crate::{
+    error::Error, models::upload::Upload, repositories::Database, schema::uploads, state::AppState,
+};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use rocket::{async_trait, http::Status, outcome::Outcome, request::FromRequest, Request};
+
+#[async_trait]
+pub trait UploadRepo: Send + Sync {
+    async fn create(&self, upload: &Upload) -> Result<Upload, Error>;
+    async fn get_by_user(&self, user_id: i32) -> Result<Vec<Upload>, Error>;
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for &'r dyn UploadRepo {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, (Status, Self::Error), ()> {
+        request
+            .guard::<&rocket::State<AppState>>()
+            .await
+            .map(|state| state.inner().uploads.as_ref())
+    }
+}
+
+#[async_trait]
+impl<D: Database> UploadRepo for D {
+    async fn create(&self, upload: &Upload) -> Result<Upload, Error> {
+        let upload = upload.clone();
+        self.run(move |conn| {
+            Ok(diesel::insert_into(uploads::dsl::uploads)
+                .values(&upload)
+                .get_result::<Upload>(conn)?)
+        })
+        .await
+    }
+
+    async fn get_by_user(&self, user_id: i32) -> Result<Vec<Upload>, Error> {
+        self.run(move |conn| {
+            Ok(uploads::dsl::uploads
+                .filter(uploads::user_id.eq(user_id))
+                .order(uploads::created_at.desc())
+                .get_results::<Upload>(conn)?)
+        })
+        .await
+    }
+}
@@ -0,0 +1,179 @@
+use super::repo::UserRepo;
+use crate::{
+    error::Error,
+    models::{password::Password, profile::Profile, role::Role, user::User, user_name::UserName},
+    repositories::{
+        invite::memory::InMemoryInviteRepo,
+        query_config::{MemoryColumn, QueryConfig},
+        transaction::Snapshot,
+        upload::memory::InMemoryUploadRepo,
+    },
+};
+use chrono::NaiveDateTime;
+use rocket::async_trait;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+const COLUMNS: &[MemoryColumn<User>] = &[
+    ("id", |a, b| a.id.cmp(&b.id)),
+    ("name", |a, b| (*a.name).cmp(&*b.name)),
+    ("normalized_name", |a, b| {
+        a.normalized_name.cmp(&b.normalized_name)
+    }),
+    ("role", |a, b| (a.role as i32).cmp(&(b.role as i32))),
+    ("confirmed", |a, b| a.confirmed.cmp(&b.confirmed)),
+    ("created_at", |a, b| a.created_at.cmp(&b.created_at)),
+    ("deleted_at", |a, b| a.deleted_at.cmp(&b.deleted_at)),
+];
+
+#[derive(Default, Clone)]
+struct Tables {
+    users: Vec<User>,
+    profiles: HashMap<i32, Profile>,
+}
+
+/// [`UserRepo`] keeping the users and their profiles in memory.
+/// Purging and anonymizing reach into the in-memory invites and uploads, as in the database.
+pub struct InMemoryUserRepo {
+    tables: Mutex<Tables>,
+    invites: Arc<InMemoryInviteRepo>,
+    uploads: Arc<InMemoryUploadRepo>,
+}
+
+impl InMemoryUserRepo {
+    pub fn new(invites: Arc<InMemoryInviteRepo>, uploads: Arc<InMemoryUploadRepo>) -> Self {
+        Self {
+            tables: Mutex::default(),
+            invites,
+            uploads,
+        }
+    }
+
+    fn modify(&self, id: i32, f: impl FnOnce(&mut User)) -> Result<User, Error> {
+        let mut tables = self.tables.lock().unwrap();
+        let Some(user) = tables.users.iter_mut().find(|u| u.id == Some(id)) else {
+            return Err(Error::UserNotFound(format!("ID: {}", id)));
+        };
+        f(user);
+        Ok(user.clone())
+    }
+}
+
+impl Snapshot for InMemoryUserRepo {
+    fn snapshot(&self) -> Self {
+        Self {
+            tables: Mutex::new(self.tables.lock().unwrap().clone()),
+            invites: self.invites.clone(),
+            uploads: self.uploads.clone(),
+        }
+    }
+
+    fn restore(&self, snapshot: Self) {
+        *self.tables.lock().unwrap() = snapshot.tables.into_inner().unwrap();
+    }
+}
+
+#[async_trait]
+impl UserRepo for InMemoryUserRepo {
+    async fn create(&self, user: &User) -> Result<User, Error> {
+        let mut tables = self.tables.lock().unwrap();
+        if tables
+            .users
+            .iter()
+            .any(|u| u.normalized_name == user.normalized_name)
+        {
+            return Err(Error::UserNameTaken(user.name.to_string()));
+        }
+        let user = User {
+            id: Some(tables.users.iter().filter_map(|u| u.id).max().unwrap_or(0) + 1),
+            created_at: Some(chrono::Utc::now().naive_utc()),
+            ..user.clone()
+        };
+        tables.users.push(user.clone());
+        Ok(user)
+    }
+
+    async fn get_by_name(&self, normalized_name: String) -> Result<User, Error> {
+        let tables = self.tables.lock().unwrap();
+        let Some(user) = tables
+            .users
+            .iter()
+            .find(|u| u.normalized_name == normalized_name)
+        else {
+            return Err(Error::UserNotFound(normalized_name));
+        };
+        Ok(user.clone())
+    }
+
+    async fn get_by_id(&self, id: i32) -> Result<User, Error> {
+        let tables = self.tables.lock().unwrap();
+        let Some(user) = tables.users.iter().find(|u| u.id == Some(id)) else {
+            return Err(Error::UserNotFound(format!("ID: {}", id)));
+        };
+        Ok(user.clone())
+    }
+
+    async fn get_all(&self, query_config: &QueryConfig) -> Result<Vec<Vec<User>>, Error> {
+        let users = self.tables.lock().unwrap().users.clone();
+        Ok(vec![query_config.apply(users, COLUMNS)?])
+    }
+
+    async fn activate(&self, id: i32) -> Result<User, Error> {
+        self.modify(id, |u| u.confirmed = true)
+    }
+
+    async fn delete(&self, id: i32) -> Result<User, Error> {
+        self.modify(id, |u| u.deleted_at = Some(chrono::Utc::now().naive_utc()))
+    }
+
+    async fn get_profile(&self, id: i32) -> Result<Profile, Error> {
+        let tables = self.tables.lock().unwrap();
+        Ok(tables.profiles.get(&id).cloned().unwrap_or(Profile {
+            user_id: id,
+            ..Default::default()
+        }))
+    }
+
+    async fn update_profile(&self, profile: &Profile) -> Result<Profile, Error> {
+        let mut tables = self.tables.lock().unwrap();
+        tables.profiles.insert(profile.user_id, profile.clone());
+        Ok(profile.clone())
+    }
+
+    async fn set_role(&self, id: i32, role: Role) -> Result<User, Error> {
+        self.modify(id, |u| u.role = role)
+    }
+
+    async fn set_password(&self, id: i32, password: &Password) -> Result<User, Error> {
+        self.modify(id, |u| u.password = password.clone())
+    }
+
+    async fn purge_deleted(&self, deleted_before: NaiveDateTime) -> Result<usize, Error> {
+        let mut tables = self.tables.lock().unwrap();
+        let (purged, kept) = std::mem::take(&mut tables.users)
+            .into_iter()
+            .partition::<Vec<_>, _>(|u| u.deleted_at.is_some_and(|d| d < deleted_before));
+        tables.users = kept;
+        let ids = purged.iter().filter_map(|u| u.id).collect::<Vec<_>>();
+        for id in &ids {
+            tables.profiles.remove(id);
+        }
+        self.invites.delete_created_by(&ids);
+        Ok(purged.len())
+    }
+
+    async fn anonymize(&self, id: i32) -> Result<User, Error> {
+        let anonymous_name = UserName::anonymous(id).to_string();
+        let user = self.modify(id, |u| {
+            *u.name = anonymous_name.clone();
+            u.normalized_name = anonymous_name.clone();
+            u.password = Password::unusable();
+            u.deleted_at = Some(chrono::Utc::now().naive_utc());
+        })?;
+        self.tables.lock().unwrap().profiles.remove(&id);
+        self.uploads.detach(id);
+        Ok(user)
+    }
+}
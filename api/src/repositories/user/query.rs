@@ -1,9 +1,9 @@
-use crate::{impl_query_config, schema::users};
+use crate::{impl_query_config, schema::users, Db};
 
 impl_query_config!(
     users::dsl::users,
     users::table,
-    users::BoxedQuery<'static, Pg>,
+    users::BoxedQuery<'static, Db>,
     UsersQuery,
     [
         (users::id, "id"),
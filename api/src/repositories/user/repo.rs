@@ -1,18 +1,31 @@
 use super::query::UsersQuery;
 use crate::{
-    error::Error, models::user::User, repositories::query_config::QueryConfig, schema::users,
-    PgPool,
+    error::Error,
+    models::{password::Password, profile::Profile, role::Role, user::User, user_name::UserName},
+    repositories::{query_config::QueryConfig, Database},
+    schema::{invites, uploads, user_profiles, users},
+    state::AppState,
 };
-use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use chrono::NaiveDateTime;
+use diesel::{Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
 use rocket::{async_trait, http::Status, outcome::Outcome, request::FromRequest, Request};
 
+#[async_trait]
 pub trait UserRepo: Send + Sync {
-    fn create(&self, user: &User) -> Result<User, Error>;
-    fn get_by_name(&self, normalized_name: String) -> Result<User, Error>;
-    fn get_by_id(&self, id: i32) -> Result<User, Error>;
-    fn get_all(&self, query_config: &QueryConfig) -> Result<Vec<Vec<User>>, Error>;
-    fn activate(&self, id: i32) -> Result<User, Error>;
-    fn delete(&self, id: i32) -> Result<User, Error>;
+    async fn create(&self, user: &User) -> Result<User, Error>;
+    async fn get_by_name(&self, normalized_name: String) -> Result<User, Error>;
+    async fn get_by_id(&self, id: i32) -> Result<User, Error>;
+    async fn get_all(&self, query_config: &QueryConfig) -> Result<Vec<Vec<User>>, Error>;
+    async fn activate(&self, id: i32) -> Result<User, Error>;
+    async fn delete(&self, id: i32) -> Result<User, Error>;
+    async fn get_profile(&self, id: i32) -> Result<Profile, Error>;
+    async fn update_profile(&self, profile: &Profile) -> Result<Profile, Error>;
+    async fn set_role(&self, id: i32, role: Role) -> Result<User, Error>;
+    async fn set_password(&self, id: i32, password: &Password) -> Result<User, Error>;
+    /// Permanently removes users soft-deleted before `deleted_before`, returns their count.
+    async fn purge_deleted(&self, deleted_before: NaiveDateTime) -> Result<usize, Error>;
+    /// Soft-deletes the user, replaces the personal data and detaches the authored content.
+    async fn anonymize(&self, id: i32) -> Result<User, Error>;
 }
 
 #[async_trait]
@@ -20,77 +33,192 @@ impl<'r> FromRequest<'r> for &'r dyn UserRepo {
     type Error = ();
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, (Status, Self::Error), ()> {
-        let pool = request
-            .guard::<&rocket::State<&dyn UserRepo>>()
+        request
+            .guard::<&rocket::State<AppState>>()
             .await
-            .unwrap();
-        Outcome::Success(*pool.inner())
+            .map(|state| state.inner().users.as_ref())
     }
 }
 
-impl UserRepo for PgPool {
-    fn create(&self, user: &User) -> Result<User, Error> {
-        let mut conn = self.get()?;
-        let user = diesel::insert_into(users::dsl::users)
-            .values(user)
-            .get_result::<User>(&mut conn)
-            .map_err(|e| unique_vol_as_user_exists(e, &*user.name))?;
-        Ok(user)
+#[async_trait]
+impl<D: Database> UserRepo for D {
+    async fn create(&self, user: &User) -> Result<User, Error> {
+        let user = user.clone();
+        self.run(move |conn| {
+            diesel::insert_into(users::dsl::users)
+                .values(&user)
+                .get_result::<User>(conn)
+                .map_err(|e| unique_vol_as_user_exists(e, &*user.name))
+        })
+        .await
+    }
+
+    async fn get_by_name(&self, normalized_name: String) -> Result<User, Error> {
+        self.run(move |conn| {
+            let Some(user) = users::dsl::users
+                .filter(users::normalized_name.eq(&normalized_name))
+                .first::<User>(conn)
+                .optional()?
+            else {
+                return Err(Error::UserNotFound(normalized_name));
+            };
+            Ok(user)
+        })
+        .await
+    }
+
+    async fn get_by_id(&self, id: i32) -> Result<User, Error> {
+        self.run(move |conn| {
+            let Some(user) = users::dsl::users
+                .filter(users::id.eq(id))
+                .first::<User>(conn)
+                .optional()?
+            else {
+                return Err(Error::UserNotFound(format!("ID: {}", id)));
+            };
+            Ok(user)
+        })
+        .await
+    }
+
+    async fn get_all(&self, query_config: &QueryConfig) -> Result<Vec<Vec<User>>, Error> {
+        let query_config = query_config.clone();
+        self.run(move |conn| Ok(vec![query_config.get_query()?.get_results::<User>(conn)?]))
+            .await
+    }
+
+    async fn activate(&self, id: i32) -> Result<User, Error> {
+        self.run(move |conn| {
+            let Some(user) = diesel::update(users::dsl::users.filter(users::id.eq(id)))
+                .set(users::confirmed.eq(true))
+                .get_result::<User>(conn)
+                .optional()?
+            else {
+                return Err(Error::UserNotFound(format!("ID: {}", id)));
+            };
+            Ok(user)
+        })
+        .await
+    }
+
+    async fn delete(&self, id: i32) -> Result<User, Error> {
+        self.run(move |conn| {
+            let Some(user) = diesel::update(users::dsl::users.filter(users::id.eq(id)))
+                .set(users::deleted_at.eq(chrono::Utc::now().naive_utc()))
+                .get_result::<User>(conn)
+                .optional()?
+            else {
+                return Err(Error::UserNotFound(format!("ID: {}", id)));
+            };
+            Ok(user)
+        })
+        .await
+    }
+
+    async fn get_profile(&self, id: i32) -> Result<Profile, Error> {
+        self.run(move |conn| {
+            let profile = user_profiles::dsl::user_profiles
+                .filter(user_profiles::user_id.eq(id))
+                .first::<Profile>(conn)
+                .optional()?
+                .unwrap_or(Profile {
+                    user_id: id,
+                    ..Default::default()
+                });
+            Ok(profile)
+        })
+        .await
     }
 
-    fn get_by_name(&self, normalized_name: String) -> Result<User, Error> {
-        let mut conn = self.get()?;
-        let Some(user) = users::dsl::users
-            .filter(users::normalized_name.eq(&normalized_name))
-            .first::<User>(&mut conn)
-            .optional()?
-        else {
-            return Err(Error::UserNotFound(normalized_name.to_string()));
-        };
-        Ok(user)
+    async fn update_profile(&self, profile: &Profile) -> Result<Profile, Error> {
+        let profile = profile.clone();
+        self.run(move |conn| {
+            Ok(diesel::insert_into(user_profiles::dsl::user_profiles)
+                .values(&profile)
+                .on_conflict(user_profiles::user_id)
+                .do_update()
+                .set(&profile)
+                .get_result::<Profile>(conn)?)
+        })
+        .await
     }
 
-    fn get_by_id(&self, id: i32) -> Result<User, Error> {
-        let mut conn = self.get()?;
-        let Some(user) = users::dsl::users
-            .filter(users::id.eq(id))
-            .first::<User>(&mut conn)
-            .optional()?
-        else {
-            return Err(Error::UserNotFound(format!("ID: {}", id)));
-        };
-        Ok(user)
+    async fn set_role(&self, id: i32, role: Role) -> Result<User, Error> {
+        self.run(move |conn| {
+            let Some(user) = diesel::update(users::dsl::users.filter(users::id.eq(id)))
+                .set(users::role.eq(role))
+                .get_result::<User>(conn)
+                .optional()?
+            else {
+                return Err(Error::UserNotFound(format!("ID: {}", id)));
+            };
+            Ok(user)
+        })
+        .await
     }
 
-    fn get_all(&self, query_config: &QueryConfig) -> Result<Vec<Vec<User>>, Error> {
-        let mut conn = self.get()?;
-        Ok(vec![query_config
-            .get_query()?
-            .get_results::<User>(&mut conn)?])
+    async fn set_password(&self, id: i32, password: &Password) -> Result<User, Error> {
+        let password = password.clone();
+        self.run(move |conn| {
+            let Some(user) = diesel::update(users::dsl::users.filter(users::id.eq(id)))
+                .set(users::password.eq(&password))
+                .get_result::<User>(conn)
+                .optional()?
+            else {
+                return Err(Error::UserNotFound(format!("ID: {}", id)));
+            };
+            Ok(user)
+        })
+        .await
     }
 
-    fn activate(&self, id: i32) -> Result<User, Error> {
-        let mut conn = self.get()?;
-        let Some(user) = diesel::update(users::dsl::users.filter(users::id.eq(id)))
-            .set(users::confirmed.eq(true))
-            .get_result::<User>(&mut conn)
-            .optional()?
-        else {
-            return Err(Error::UserNotFound(format!("ID: {}", id)));
-        };
-        Ok(user)
+    async fn purge_deleted(&self, deleted_before: NaiveDateTime) -> Result<usize, Error> {
+        self.run(move |conn| {
+            Ok(conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let ids = users::dsl::users
+                    .filter(users::deleted_at.lt(deleted_before))
+                    .select(users::id)
+                    .load::<i32>(conn)?;
+                diesel::delete(user_profiles::dsl::user_profiles)
+                    .filter(user_profiles::user_id.eq_any(&ids))
+                    .execute(conn)?;
+                diesel::delete(invites::dsl::invites)
+                    .filter(invites::created_by.eq_any(&ids))
+                    .execute(conn)?;
+                diesel::delete(users::dsl::users)
+                    .filter(users::id.eq_any(&ids))
+                    .execute(conn)
+            })?)
+        })
+        .await
     }
 
-    fn delete(&self, id: i32) -> Result<User, Error> {
-        let mut conn = self.get()?;
-        let Some(user) = diesel::update(users::dsl::users.filter(users::id.eq(id)))
-            .set(users::deleted_at.eq(chrono::Utc::now().naive_utc()))
-            .get_result::<User>(&mut conn)
-            .optional()?
-        else {
-            return Err(Error::UserNotFound(format!("ID: {}", id)));
-        };
-        Ok(user)
+    async fn anonymize(&self, id: i32) -> Result<User, Error> {
+        self.run(move |conn| {
+            let anonymous_name = UserName::anonymous(id).to_string();
+            let user = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                diesel::delete(user_profiles::dsl::user_profiles)
+                    .filter(user_profiles::user_id.eq(id))
+                    .execute(conn)?;
+                diesel::update(uploads::dsl::uploads.filter(uploads::user_id.eq(id)))
+                    .set(uploads::user_id.eq(None::<i32>))
+                    .execute(conn)?;
+                diesel::update(users::dsl::users.filter(users::id.eq(id)))
+                    .set((
+                        users::name.eq(&anonymous_name),
+                        users::normalized_name.eq(&anonymous_name),
+                        users::password.eq(Password::unusable()),
+                        users::deleted_at.eq(chrono::Utc::now().naive_utc()),
+                    ))
+                    .get_result::<User>(conn)
+                    .optional()
+            })?;
+            let Some(user) = user else {
+                return Err(Error::UserNotFound(format!("ID: {}", id)));
+            };
+            Ok(user)
+        })
+        .await
     }
 }
 
@@ -1,26 +1,18 @@
+use super::blob_store::BlobStore;
 use crate::error::Error;
+use azure_core::{error::ErrorKind, StatusCode};
 use azure_storage::prelude::*;
 use azure_storage_blobs::prelude::*;
+use rocket::async_trait;
+use serde::Deserialize;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct AzureBlobSecrets {
     pub account: String,
     pub account_key: String,
     pub container_name: String,
 }
 
-impl Default for AzureBlobSecrets {
-    fn default() -> Self {
-        Self {
-            account: std::env::var("STORAGE_ACCOUNT").expect("STORAGE_ACCOUNT must be set"),
-            account_key: std::env::var("STORAGE_ACCESS_KEY")
-                .expect("STORAGE_ACCESS_KEY must be set"),
-            container_name: std::env::var("STORAGE_CONTAINER")
-                .expect("STORAGE_CONTAINER must be set"),
-        }
-    }
-}
-
 pub struct AzureBlobService {
     secrets: AzureBlobSecrets,
     client: ClientBuilder,
@@ -32,24 +24,56 @@ impl AzureBlobService {
         let client = ClientBuilder::new(&secrets.account, creds);
         Self { secrets, client }
     }
+}
+
+#[async_trait]
+impl BlobStore for AzureBlobService {
+    fn url(&self) -> String {
+        format!(
+            "https://{}.blob.core.windows.net/{}/",
+            self.secrets.account, self.secrets.container_name
+        )
+    }
 
-    pub async fn upload(
+    async fn upload_to(
         &self,
-        name: String,
-        folder: String,
+        path: String,
         data: Vec<u8>,
         content_type: String,
     ) -> Result<(), Error> {
         Ok(self
             .client
             .clone()
-            .blob_client(
-                self.secrets.container_name.clone(),
-                format!("{}/{}", folder, name),
-            )
+            .blob_client(self.secrets.container_name.clone(), path)
             .put_block_blob(data)
             .content_type(content_type)
             .await
-            .map(|_| ())?)
+            .map(|_| ())
+            .map_err(storage_error)?)
+    }
+
+    async fn download(&self, path: String) -> Result<(Vec<u8>, String), Error> {
+        let client = self
+            .client
+            .clone()
+            .blob_client(self.secrets.container_name.clone(), path);
+        let content_type = client
+            .get_properties()
+            .await
+            .map_err(storage_error)?
+            .blob
+            .properties
+            .content_type;
+        let data = client.get_content().await.map_err(storage_error)?;
+        Ok((data, content_type))
+    }
+}
+
+fn storage_error(e: azure_core::error::Error) -> Error {
+    match e.kind() {
+        ErrorKind::HttpResponse { status, .. } if *status == StatusCode::NotFound => {
+            Error::Status(404, e.to_string())
+        }
+        _ => Error::Status(500, e.to_string()),
     }
 }
@@ -0,0 +1,40 @@
+use crate::{error::Error, state::AppState};
+use rocket::{async_trait, http::Status, outcome::Outcome, request::FromRequest, Request};
+
+/// Storage of the uploaded files, addressed by paths relative to [`BlobStore::url`].
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Public url of the store, blob paths are relative to it.
+    fn url(&self) -> String;
+    async fn upload_to(
+        &self,
+        path: String,
+        data: Vec<u8>,
+        content_type: String,
+    ) -> Result<(), Error>;
+    /// Returns the blob content with its content type, `Status(404)` when there's no such blob.
+    async fn download(&self, path: String) -> Result<(Vec<u8>, String), Error>;
+
+    async fn upload(
+        &self,
+        name: String,
+        folder: String,
+        data: Vec<u8>,
+        content_type: String,
+    ) -> Result<(), Error> {
+        self.upload_to(format!("{}/{}", folder, name), data, content_type)
+            .await
+    }
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for &'r dyn BlobStore {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, (Status, Self::Error), ()> {
+        request
+            .guard::<&rocket::State<AppState>>()
+            .await
+            .map(|state| state.inner().blobs.as_ref())
+    }
+}
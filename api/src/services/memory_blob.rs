@@ -0,0 +1,56 @@
+use super::blob_store::BlobStore;
+use crate::error::Error;
+use rocket::{async_trait, http::Status};
+use std::{collections::HashMap, sync::Mutex};
+
+/// [`BlobStore`] keeping the files in memory, for tests and local runs without Azure.
+#[derive(Default)]
+pub struct MemoryBlobStore {
+    blobs: Mutex<HashMap<String, (Vec<u8>, String)>>,
+}
+
+impl MemoryBlobStore {
+    pub const URL: &'static str = "memory://blobs/";
+
+    /// Paths of the stored blobs, sorted.
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths = self
+            .blobs
+            .lock()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths
+    }
+}
+
+#[async_trait]
+impl BlobStore for MemoryBlobStore {
+    fn url(&self) -> String {
+        Self::URL.to_string()
+    }
+
+    async fn upload_to(
+        &self,
+        path: String,
+        data: Vec<u8>,
+        content_type: String,
+    ) -> Result<(), Error> {
+        self.blobs
+            .lock()
+            .unwrap()
+            .insert(path, (data, content_type));
+        Ok(())
+    }
+
+    async fn download(&self, path: String) -> Result<(Vec<u8>, String), Error> {
+        self.blobs
+            .lock()
+            .unwrap()
+            .get(&path)
+            .cloned()
+            .ok_or(Error::from(Status::NotFound))
+    }
+}
@@ -1,2 +1,4 @@
 pub mod azure_blob;
+pub mod blob_store;
 pub mod filename;
+pub mod memory_blob;
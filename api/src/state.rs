@@ -0,0 +1,164 @@
+use crate::{
+    config::{AppConfig, StorageConfig},
+    error::Error,
+    models::{page_meta::PageMeta, resource_data::ResourceData},
+    repositories::{
+        comment::{memory::InMemoryCommentRepo, repo::CommentRepo},
+        invite::{memory::InMemoryInviteRepo, repo::InviteRepo},
+        resources::{
+            cache::CachedResourcesRepo, memory::InMemoryResourcesRepo, repo::ResourcesRepo,
+        },
+        search::{memory::InMemorySearchRepo, repo::SearchRepo},
+        transaction::{atomically, InMemoryUnitOfWork, Repositories, UnitOfWork},
+        upload::{memory::InMemoryUploadRepo, repo::UploadRepo},
+        user::{memory::InMemoryUserRepo, repo::UserRepo},
+    },
+    services::{azure_blob::AzureBlobService, blob_store::BlobStore, memory_blob::MemoryBlobStore},
+    DbPool,
+};
+use chrono::NaiveDateTime;
+use rocket::{async_trait, futures::future::BoxFuture};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Repositories and the blob store shared by every request, the guards borrow them from the managed state.
+#[derive(Clone)]
+pub struct AppState {
+    pub users: Arc<dyn UserRepo>,
+    pub resources: Arc<dyn ResourcesRepo>,
+    pub invites: Arc<dyn InviteRepo>,
+    pub uploads: Arc<dyn UploadRepo>,
+    pub search: Arc<dyn SearchRepo>,
+    pub comments: Arc<dyn CommentRepo>,
+    pub blobs: Arc<dyn BlobStore>,
+    pub transactions: Arc<dyn UnitOfWork>,
+}
+
+impl AppState {
+    /// Every repository backed by the one connection `pool`, resources are read through the cache.
+    pub fn new(pool: DbPool, config: &AppConfig) -> Self {
+        let blobs = match &config.storage {
+            StorageConfig::Azure(secrets) => AzureBlobService::new(secrets.clone()),
+        };
+        Self {
+            users: Arc::new(pool.clone()),
+            resources: Arc::new(CachedResourcesRepo::new(
+                pool.clone(),
+                config.resources_cache,
+            )),
+            invites: Arc::new(pool.clone()),
+            uploads: Arc::new(pool.clone()),
+            search: Arc::new(pool.clone()),
+            comments: Arc::new(pool.clone()),
+            blobs: Arc::new(blobs),
+            transactions: Arc::new(pool),
+        }
+    }
+
+    /// Everything kept in memory, nothing survives the process.
+    pub fn in_memory() -> Self {
+        let invites = Arc::new(InMemoryInviteRepo::default());
+        let uploads = Arc::new(InMemoryUploadRepo::default());
+        let transactions = InMemoryUnitOfWork {
+            users: Arc::new(InMemoryUserRepo::new(invites.clone(), uploads.clone())),
+            resources: Arc::new(InMemoryResourcesRepo::default()),
+            invites,
+            uploads,
+        };
+        Self {
+            users: transactions.users.clone(),
+            resources: transactions.resources.clone(),
+            invites: transactions.invites.clone(),
+            uploads: transactions.uploads.clone(),
+            search: Arc::new(InMemorySearchRepo {
+                resources: transactions.resources.clone(),
+            }),
+            comments: Arc::new(InMemoryCommentRepo::new(transactions.users.clone())),
+            blobs: Arc::new(MemoryBlobStore::default()),
+            transactions: Arc::new(transactions),
+        }
+    }
+
+    /// Runs `work` on repositories sharing one transaction and returns its output,
+    /// nothing it changed is kept when it fails with any [`Error`].
+    pub async fn transaction<T, F>(&self, work: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: for<'t> FnOnce(Repositories<'t>) -> BoxFuture<'t, Result<T, Error>> + Send + 'static,
+    {
+        let written = Arc::new(AtomicBool::new(false));
+        let tracked = written.clone();
+        let result = atomically(self.transactions.as_ref(), move |repos| {
+            Box::pin(async move {
+                let resources = WriteTracked {
+                    inner: repos.resources,
+                    written: &tracked,
+                };
+                work(Repositories {
+                    resources: &resources,
+                    ..repos
+                })
+                .await
+            })
+        })
+        .await;
+        // The resources were written around the cache.
+        if written.load(Ordering::SeqCst) {
+            self.resources.clear_cache();
+        }
+        result
+    }
+}
+
+/// Resources of a transaction, remembering whether anything was written through them.
+struct WriteTracked<'t> {
+    inner: &'t dyn ResourcesRepo,
+    written: &'t AtomicBool,
+}
+
+impl WriteTracked<'_> {
+    fn mark(&self) {
+        self.written.store(true, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl ResourcesRepo for WriteTracked<'_> {
+    async fn get(&self, key: &str, lang: &str) -> Result<(String, NaiveDateTime), Error> {
+        self.inner.get(key, lang).await
+    }
+
+    async fn get_all(&self) -> Result<Vec<ResourceData>, Error> {
+        self.inner.get_all().await
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, Error> {
+        self.inner.exists(key).await
+    }
+
+    async fn create(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        self.mark();
+        self.inner.create(data).await
+    }
+
+    async fn update(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        self.mark();
+        self.inner.update(data).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), Error> {
+        self.mark();
+        self.inner.delete(key).await
+    }
+
+    async fn get_meta(&self, key: &str, lang: &str) -> Result<PageMeta, Error> {
+        self.inner.get_meta(key, lang).await
+    }
+
+    async fn update_meta(&self, meta: &PageMeta) -> Result<PageMeta, Error> {
+        self.mark();
+        self.inner.update_meta(meta).await
+    }
+}
@@ -0,0 +1,889 @@
+mod common;
+
+use common::{
+    bearer, client, client_on, client_with, config, login, read, register, token, ADMIN, PASSWORD,
+};
+use petompp_web_api::{
+    auth::cookie::TOKEN_COOKIE,
+    config::{AppConfig, RobotsConfig},
+    controllers::response::Format,
+    error::Error,
+    models::{invite::Invite, registration::RegistrationPolicy, upload::Upload},
+    state::AppState,
+};
+use rocket::{
+    http::{Accept, ContentType, Cookie, Header, Status},
+    local::asynchronous::Client,
+    serde::json::{serde_json, serde_json::json, Value},
+};
+
+fn with_registration(registration: RegistrationPolicy) -> AppConfig {
+    AppConfig {
+        registration,
+        ..config()
+    }
+}
+
+/// [`Error::code`] of the error in the response envelope.
+fn code(body: &Value) -> &'static str {
+    serde_json::from_value::<Error>(body["data"].clone())
+        .unwrap()
+        .code()
+}
+
+async fn activate(client: &Client, id: &Value) -> (Status, Value) {
+    let admin = token(client, ADMIN).await;
+    let response = client
+        .post(format!("/api/v1/users/{}/activate", id))
+        .header(bearer(&admin))
+        .dispatch()
+        .await;
+    read(response).await
+}
+
+async fn put_resource(client: &Client, token: &str, key: &str, value: Value) -> (Status, Value) {
+    let response = client
+        .put(format!("/api/v1/res/{}", key))
+        .header(ContentType::JSON)
+        .header(bearer(token))
+        .body(value.to_string())
+        .dispatch()
+        .await;
+    read(response).await
+}
+
+async fn get_resource(client: &Client, key: &str, lang: &str) -> (Status, Value) {
+    let response = client
+        .get(format!("/api/v1/res/{}?lang={}", key, lang))
+        .dispatch()
+        .await;
+    read(response).await
+}
+
+async fn put_meta(client: &Client, token: &str, key: &str, meta: Value) -> (Status, Value) {
+    let response = client
+        .put(format!("/api/v1/res/{}/meta", key))
+        .header(ContentType::JSON)
+        .header(bearer(token))
+        .body(meta.to_string())
+        .dispatch()
+        .await;
+    read(response).await
+}
+
+async fn get_meta(client: &Client, key: &str, lang: &str) -> Value {
+    let response = client
+        .get(format!("/api/v1/res/{}/meta?lang={}", key, lang))
+        .dispatch()
+        .await;
+    let (status, body) = read(response).await;
+    assert_eq!(status, Status::Ok);
+    body["data"].clone()
+}
+
+/// Confirmed user with the `name` and their token.
+async fn confirmed(client: &Client, name: &str) -> String {
+    let (_, user) = register(client, name, None).await;
+    activate(client, &user["data"]["id"]).await;
+    token(client, name).await
+}
+
+async fn post_comment(client: &Client, token: &str, key: &str, comment: Value) -> (Status, Value) {
+    let response = client
+        .post(format!("/api/v1/comments/{}", key))
+        .header(ContentType::JSON)
+        .header(bearer(token))
+        .body(comment.to_string())
+        .dispatch()
+        .await;
+    read(response).await
+}
+
+/// The comments on the page of the `key`, as seen with the `token`.
+async fn thread(client: &Client, key: &str, token: Option<&str>) -> Vec<Value> {
+    let request = client.get(format!("/api/v1/comments/{}", key));
+    let request = match token {
+        Some(token) => request.header(bearer(token)),
+        None => request,
+    };
+    let (status, body) = read(request.dispatch().await).await;
+    assert_eq!(status, Status::Ok);
+    body["data"].as_array().unwrap().clone()
+}
+
+async fn moderate(client: &Client, token: &str, id: &Value, action: &str) -> (Status, Value) {
+    let response = client
+        .post(format!(
+            "/api/v1/comments/{}/moderate?action={}",
+            id, action
+        ))
+        .header(bearer(token))
+        .dispatch()
+        .await;
+    read(response).await
+}
+
+async fn users(client: &Client, query: &str) -> (Status, Value) {
+    let admin = token(client, ADMIN).await;
+    let response = client
+        .get(format!("/api/v1/users/all?{}", query))
+        .header(bearer(&admin))
+        .dispatch()
+        .await;
+    read(response).await
+}
+
+#[rocket::async_test]
+async fn registered_user_has_to_be_activated() {
+    let client = client().await;
+    let (status, user) = register(&client, "Alice", None).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(user["data"]["name"], "Alice");
+    assert_eq!(user["data"]["confirmed"], false);
+
+    let (status, body) = login(&client, "alice", PASSWORD).await;
+    assert_eq!(status, Status::PaymentRequired);
+    assert_eq!(code(&body), "user.not_confirmed");
+
+    let (status, body) = activate(&client, &user["data"]["id"]).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["confirmed"], true);
+    let alice = token(&client, "alice").await;
+    let response = client
+        .get("/api/v1/users")
+        .header(bearer(&alice))
+        .dispatch()
+        .await;
+    let (status, body) = read(response).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["name"], "Alice");
+}
+
+#[rocket::async_test]
+async fn registration_is_validated() {
+    let client = client().await;
+    register(&client, "Alice", None).await;
+    let (status, body) = register(&client, "ALICE", None).await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "user.name_taken");
+    let (status, body) = register(&client, "al", None).await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "validation.username.invalid_length");
+}
+
+#[rocket::async_test]
+async fn closed_registration_is_forbidden() {
+    let client = client_with(with_registration(RegistrationPolicy::Closed)).await;
+    let (status, body) = register(&client, "Alice", None).await;
+    assert_eq!(status, Status::Forbidden);
+    assert_eq!(code(&body), "registration.closed");
+}
+
+#[rocket::async_test]
+async fn invite_only_registration_uses_the_code() {
+    let client = client_with(with_registration(RegistrationPolicy::InviteOnly)).await;
+    let (status, body) = register(&client, "Alice", None).await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "invite.code_required");
+    let (status, body) = register(&client, "Alice", Some("nope")).await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "invite.code_invalid");
+
+    let admin = token(&client, ADMIN).await;
+    let response = client
+        .post("/api/v1/invites")
+        .header(ContentType::JSON)
+        .header(bearer(&admin))
+        .body(json!({ "max_uses": 1 }).to_string())
+        .dispatch()
+        .await;
+    let (status, invite) = read(response).await;
+    assert_eq!(status, Status::Ok);
+    let invite_code = invite["data"]["code"].as_str().unwrap();
+
+    let (status, user) = register(&client, "Alice", Some(invite_code)).await;
+    assert_eq!(status, Status::Ok);
+    // Invited users don't need the activation.
+    assert_eq!(user["data"]["confirmed"], true);
+    let (status, body) = register(&client, "Bob", Some(invite_code)).await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "invite.code_used_up");
+}
+
+#[rocket::async_test]
+async fn login_rejects_invalid_credentials() {
+    let client = client().await;
+    let (status, body) = login(&client, "nobody", PASSWORD).await;
+    assert_eq!(status, Status::Unauthorized);
+    assert_eq!(code(&body), "user.invalid_credentials");
+    let (status, body) = login(&client, ADMIN, "Wr0ng!Password").await;
+    assert_eq!(status, Status::Unauthorized);
+    assert_eq!(code(&body), "user.invalid_credentials");
+    let (status, body) = login(&client, ADMIN, PASSWORD).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["user"]["role"], "Admin");
+}
+
+#[rocket::async_test]
+async fn activation_is_for_admins() {
+    let client = client().await;
+    let (_, user) = register(&client, "Alice", None).await;
+    let response = client
+        .post(format!("/api/v1/users/{}/activate", user["data"]["id"]))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    activate(&client, &user["data"]["id"]).await;
+    let alice = token(&client, "alice").await;
+    let response = client
+        .post(format!("/api/v1/users/{}/activate", user["data"]["id"]))
+        .header(bearer(&alice))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+    let admin = token(&client, ADMIN).await;
+    let response = client
+        .post(format!("/api/v1/users/{}/activate", user["data"]["id"]))
+        .cookie(Cookie::new(TOKEN_COOKIE, admin))
+        .dispatch()
+        .await;
+    assert_eq!(
+        response.status(),
+        Status::Forbidden,
+        "the CSRF token is missing"
+    );
+
+    let (status, body) = activate(&client, &json!(999)).await;
+    assert_eq!(status, Status::NotFound);
+    assert_eq!(code(&body), "user.not_found");
+}
+
+#[rocket::async_test]
+async fn resources_can_be_managed_by_admins() {
+    let client = client().await;
+    let admin = token(&client, ADMIN).await;
+    let (status, _) = put_resource(&client, &admin, "home", json!({ "en": "Hello" })).await;
+    assert_eq!(status, Status::Ok);
+    let (status, body) = get_resource(&client, "home", "pl").await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"], "Hello");
+
+    let response = client
+        .post("/api/v1/res/home")
+        .header(ContentType::JSON)
+        .header(bearer(&admin))
+        .body(json!({ "key": "home", "pl": "Cześć" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(get_resource(&client, "home", "pl").await.1["data"], "Cześć");
+    assert_eq!(get_resource(&client, "home", "en").await.1["data"], "Hello");
+
+    let response = client
+        .post("/api/v1/res/home")
+        .header(ContentType::JSON)
+        .header(bearer(&admin))
+        .body(json!({ "key": "about", "en": "About" }).to_string())
+        .dispatch()
+        .await;
+    let (status, body) = read(response).await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "validation.resource_data.key_mismatch");
+
+    let response = client.get("/api/v1/res/keys").dispatch().await;
+    assert_eq!(read(response).await.1["data"], json!(["home"]));
+
+    let response = client
+        .delete("/api/v1/res/home")
+        .header(bearer(&admin))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let (status, body) = get_resource(&client, "home", "en").await;
+    assert_eq!(status, Status::NotFound);
+    assert_eq!(code(&body), "status");
+}
+
+#[rocket::async_test]
+async fn plain_text_bodies_are_read_as_json() {
+    let client = client().await;
+    let admin = token(&client, ADMIN).await;
+    let response = client
+        .put("/api/v1/res/home")
+        .header(Header::new("Content-Type", "text/plain;charset=UTF-8"))
+        .header(bearer(&admin))
+        .body(json!({ "en": "Hello" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(get_resource(&client, "home", "en").await.1["data"], "Hello");
+}
+
+#[rocket::async_test]
+async fn resources_are_not_writable_by_users() {
+    let client = client().await;
+    let (_, user) = register(&client, "Alice", None).await;
+    activate(&client, &user["data"]["id"]).await;
+    let alice = token(&client, "alice").await;
+    let (status, _) = put_resource(&client, &alice, "home", json!({ "en": "Hello" })).await;
+    assert_eq!(status, Status::Forbidden);
+}
+
+#[rocket::async_test]
+async fn profile_urls_are_validated() {
+    let client = client().await;
+    let (_, user) = register(&client, "Alice", None).await;
+    activate(&client, &user["data"]["id"]).await;
+    let alice = token(&client, "alice").await;
+    let long = format!("https://example.com/{}", "a".repeat(255));
+    for (profile, expected) in [
+        (
+            json!({ "avatar": long }),
+            "validation.profile.avatar_too_long",
+        ),
+        (
+            json!({ "links": [long] }),
+            "validation.profile.link_too_long",
+        ),
+        (
+            json!({ "avatar": "https://example.com/a.png" }),
+            "validation.profile.avatar_not_uploaded",
+        ),
+        (
+            json!({ "avatar": "memory://blobs/../a.png" }),
+            "validation.profile.avatar_not_uploaded",
+        ),
+    ] {
+        let response = client
+            .put("/api/v1/users/profile")
+            .header(ContentType::JSON)
+            .header(bearer(&alice))
+            .body(profile.to_string())
+            .dispatch()
+            .await;
+        let (status, body) = read(response).await;
+        assert_eq!(status, Status::BadRequest);
+        assert_eq!(code(&body), expected);
+    }
+    let response = client
+        .put("/api/v1/users/profile")
+        .header(ContentType::JSON)
+        .header(bearer(&alice))
+        .body(json!({ "avatar": "memory://blobs/avatars/a.png" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[rocket::async_test]
+async fn page_meta_falls_back_to_english() {
+    let client = client().await;
+    let admin = token(&client, ADMIN).await;
+    put_resource(&client, &admin, "home", json!({ "en": "Hello" })).await;
+    let meta = get_meta(&client, "home", "pl").await;
+    assert_eq!(meta["title"], Value::Null);
+    assert_eq!(meta["lang"], "pl");
+
+    let (status, body) = put_meta(
+        &client,
+        &admin,
+        "home",
+        json!({ "lang": "en", "title": " Home ", "description": "", "canonical": null, "image": null }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["title"], "Home");
+    assert_eq!(body["data"]["description"], Value::Null);
+    let meta = get_meta(&client, "home", "pl").await;
+    assert_eq!(
+        (&meta["title"], &meta["lang"]),
+        (&json!("Home"), &json!("pl"))
+    );
+
+    put_meta(
+        &client,
+        &admin,
+        "home",
+        json!({ "lang": "pl", "title": "Strona główna", "description": null, "canonical": null, "image": null }),
+    )
+    .await;
+    assert_eq!(
+        get_meta(&client, "home", "pl").await["title"],
+        "Strona główna"
+    );
+    assert_eq!(get_meta(&client, "home", "en").await["title"], "Home");
+}
+
+#[rocket::async_test]
+async fn page_meta_is_validated_and_admin_only() {
+    let client = client().await;
+    let admin = token(&client, ADMIN).await;
+    put_resource(&client, &admin, "home", json!({ "en": "Hello" })).await;
+    let meta = |lang: &str, image: &str| json!({ "lang": lang, "title": null, "description": null, "canonical": null, "image": image });
+
+    let (status, body) = put_meta(&client, &admin, "home", meta("de", "https://a.b/c.png")).await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "validation.page_meta.lang_invalid");
+    let (status, body) = put_meta(&client, &admin, "home", meta("en", "javascript:alert(1)")).await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "validation.page_meta.invalid_url");
+    let long_url = format!("https://a.b/{}", "c".repeat(255));
+    let (status, body) = put_meta(&client, &admin, "home", meta("en", &long_url)).await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "validation.page_meta.url_too_long");
+    let (status, _) = put_meta(&client, &admin, "missing", meta("en", "https://a.b/c.png")).await;
+    assert_eq!(status, Status::NotFound);
+
+    let (_, user) = register(&client, "Alice", None).await;
+    activate(&client, &user["data"]["id"]).await;
+    let alice = token(&client, "alice").await;
+    let (status, _) = put_meta(&client, &alice, "home", meta("en", "https://a.b/c.png")).await;
+    assert_eq!(status, Status::Forbidden);
+}
+
+#[rocket::async_test]
+async fn sitemap_lists_the_public_pages() {
+    let client = client().await;
+    let admin = token(&client, ADMIN).await;
+    put_resource(&client, &admin, "about-content", json!({ "en": "About" })).await;
+
+    let response = client.get("/sitemap.xml").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::XML));
+    let xml = response.into_string().await.unwrap();
+    assert!(xml.contains("<loc>https://petompp.net/about?lang=en</loc>"));
+    assert!(xml.contains(
+        "<xhtml:link rel=\"alternate\" hreflang=\"x-default\" href=\"https://petompp.net/about\"/>"
+    ));
+    // Only the prerendered language is listed.
+    assert!(!xml.contains("lang=pl"));
+    assert_eq!(xml.matches("<url>").count(), 4);
+    // Only the page with content has a date.
+    assert_eq!(xml.matches("<lastmod>").count(), 1);
+    assert!(!xml.contains("/admin") && !xml.contains("/editor"));
+}
+
+#[rocket::async_test]
+async fn sitemap_lists_every_served_language() {
+    let client = client_with(AppConfig {
+        site_langs: vec!["en".to_string(), "pl".to_string()],
+        ..config()
+    })
+    .await;
+
+    let response = client.get("/sitemap.xml").dispatch().await;
+    let xml = response.into_string().await.unwrap();
+    assert!(xml.contains("<loc>https://petompp.net/about?lang=pl</loc>"));
+    assert!(xml.contains(
+        "<xhtml:link rel=\"alternate\" hreflang=\"pl\" href=\"https://petompp.net/?lang=pl\"/>"
+    ));
+    assert_eq!(xml.matches("<url>").count(), 8);
+}
+
+#[rocket::async_test]
+async fn robots_keep_the_crawlers_out() {
+    let client = client().await;
+    let response = client.get("/robots.txt").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let robots = response.into_string().await.unwrap();
+    assert!(robots.contains("Disallow: /admin\nDisallow: /editor\n"));
+    assert!(robots.contains("Sitemap: https://petompp.net/sitemap.xml"));
+
+    let client = client_with(AppConfig {
+        robots: RobotsConfig {
+            indexed: false,
+            disallow: vec![],
+        },
+        ..config()
+    })
+    .await;
+    let robots = client
+        .get("/robots.txt")
+        .dispatch()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+    assert!(robots.contains("Disallow: /\n"));
+    assert!(!robots.contains("/admin"));
+}
+
+#[rocket::async_test]
+async fn feeds_list_the_updated_pages() {
+    let client = client().await;
+    let admin = token(&client, ADMIN).await;
+    let about = "# About\n\nI write *Rust* <script>alert(1)</script>.\n\nMore.";
+    put_resource(&client, &admin, "about-content", json!({ "en": about })).await;
+    put_resource(
+        &client,
+        &admin,
+        "admin-panel-content",
+        json!({ "en": "Admin" }),
+    )
+    .await;
+
+    let response = client.get("/api/v1/feeds/en/atom.xml").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.content_type(),
+        ContentType::parse_flexible("application/atom+xml")
+    );
+    let etag = response.headers().get_one("ETag").unwrap().to_string();
+    let atom = response.into_string().await.unwrap();
+    assert!(atom.contains("<title>About</title>"), "{}", atom);
+    assert!(atom.contains("<id>https://petompp.net/about?lang=en</id>"));
+    assert!(atom.contains("&lt;em&gt;Rust&lt;/em&gt; &amp;lt;script&amp;gt;"));
+    assert!(!atom.contains("More.") && !atom.contains("Admin"));
+
+    let response = client
+        .get("/api/v1/feeds/en/atom.xml")
+        .header(Header::new("If-None-Match", etag))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotModified);
+
+    let response = client.get("/api/v1/feeds/pl/rss.xml").dispatch().await;
+    let rss = response.into_string().await.unwrap();
+    assert!(rss.contains("<link>https://petompp.net/about?lang=pl</link>"));
+    assert!(rss.contains("<language>pl</language>"));
+
+    let response = client.get("/api/v1/feeds/en/feed.json").dispatch().await;
+    let json: Value = serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    assert_eq!(json["items"][0]["title"], "About");
+    assert_eq!(
+        json["feed_url"],
+        "https://api.petompp.net/api/v1/feeds/en/feed.json"
+    );
+
+    for path in ["/api/v1/feeds/en/feed.xml", "/api/v1/feeds/de/atom.xml"] {
+        assert_eq!(client.get(path).dispatch().await.status(), Status::NotFound);
+    }
+}
+
+#[rocket::async_test]
+async fn comments_are_shown_once_approved() {
+    let client = client().await;
+    let admin = token(&client, ADMIN).await;
+    put_resource(&client, &admin, "home", json!({ "en": "Hello" })).await;
+    let alice = confirmed(&client, "Alice").await;
+
+    let body = "Nice *page* <script>alert(1)</script> [x](javascript:alert(1))";
+    let (status, comment) = post_comment(&client, &alice, "home", json!({ "body": body })).await;
+    assert_eq!(status, Status::Ok);
+    let comment = &comment["data"];
+    assert_eq!(comment["comment"]["status"], "Pending");
+    assert_eq!(comment["author"], "Alice");
+    let html = comment["html"].as_str().unwrap();
+    assert!(html.contains("<em>page</em>"), "{}", html);
+    assert!(!html.contains("<script>") && !html.contains("javascript:"));
+
+    assert!(thread(&client, "home", None).await.is_empty());
+    assert_eq!(thread(&client, "home", Some(&alice)).await.len(), 1);
+    let response = client
+        .get("/api/v1/comments/moderation/queue")
+        .header(bearer(&alice))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+    let response = client
+        .get("/api/v1/comments/moderation/queue")
+        .header(bearer(&admin))
+        .dispatch()
+        .await;
+    let queue = read(response).await.1["data"].clone();
+    assert_eq!(queue.as_array().unwrap().len(), 1);
+
+    let id = &comment["comment"]["id"];
+    let (status, body) = post_comment(
+        &client,
+        &alice,
+        "home",
+        json!({ "body": "Reply", "parent_id": id }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "validation.comment.parent_invalid");
+
+    let (status, _) = moderate(&client, &admin, id, "approve").await;
+    assert_eq!(status, Status::Ok);
+    let (status, reply) = post_comment(
+        &client,
+        &admin,
+        "home",
+        json!({ "body": "Thanks", "parent_id": id }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(reply["data"]["comment"]["status"], "Approved");
+    let comments = thread(&client, "home", None).await;
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[1]["comment"]["parent_id"], *id);
+
+    let (status, body) = post_comment(&client, &alice, "home", json!({ "body": "  " })).await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "validation.comment.body_empty");
+    let (status, _) = post_comment(&client, &alice, "missing", json!({ "body": "Hi" })).await;
+    assert_eq!(status, Status::NotFound);
+
+    // The page of any key has a thread, the moderation queue is out of their way.
+    put_resource(&client, &admin, "queue", json!({ "en": "Queue" })).await;
+    post_comment(&client, &admin, "queue", json!({ "body": "Hi" })).await;
+    assert_eq!(thread(&client, "queue", None).await.len(), 1);
+}
+
+#[rocket::async_test]
+async fn replies_outlive_their_purged_parents() {
+    let state = AppState::in_memory();
+    let client = client_on(config(), state.clone()).await;
+    let admin = token(&client, ADMIN).await;
+    put_resource(&client, &admin, "home", json!({ "en": "Hello" })).await;
+    let alice = confirmed(&client, "Alice").await;
+    let (_, comment) = post_comment(&client, &alice, "home", json!({ "body": "First" })).await;
+    let comment = &comment["data"]["comment"];
+    moderate(&client, &admin, &comment["id"], "approve").await;
+    let reply = json!({ "body": "Reply", "parent_id": comment["id"] });
+    post_comment(&client, &admin, "home", reply).await;
+
+    let alice_id = comment["user_id"].as_i64().unwrap() as i32;
+    state.users.anonymize(alice_id).await.unwrap();
+    let later = chrono::Utc::now().naive_utc() + chrono::Duration::minutes(1);
+    assert_eq!(state.users.purge_deleted(later).await.unwrap(), 1);
+    let comments = thread(&client, "home", None).await;
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["comment"]["body"], "Reply");
+    assert_eq!(comments[0]["comment"]["parent_id"], Value::Null);
+}
+
+#[rocket::async_test]
+async fn deleted_accounts_are_exported_and_purged() {
+    let state = AppState::in_memory();
+    let client = client_on(config(), state.clone()).await;
+    let admin = token(&client, ADMIN).await;
+    put_resource(&client, &admin, "home", json!({ "en": "Hello" })).await;
+    let (_, alice) = register(&client, "Alice", None).await;
+    let alice_id = alice["data"]["id"].as_i64().unwrap() as i32;
+    activate(&client, &alice["data"]["id"]).await;
+    let alice = token(&client, "Alice").await;
+    post_comment(&client, &alice, "home", json!({ "body": "First" })).await;
+    let upload = Upload {
+        path: "avatars/alice.png".to_string(),
+        user_id: Some(alice_id),
+        ..Default::default()
+    };
+    state.uploads.create(&upload).await.unwrap();
+    let invite = Invite {
+        code: "ALICE".to_string(),
+        created_by: alice_id,
+        ..Default::default()
+    };
+    state.invites.create(&invite).await.unwrap();
+
+    let response = client
+        .get("/api/v1/users/me/export")
+        .header(bearer(&alice))
+        .dispatch()
+        .await;
+    let (status, body) = read(response).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["comments"][0]["body"], "First");
+    assert_eq!(body["data"]["uploads"][0]["path"], "avatars/alice.png");
+    assert_eq!(body["data"]["invites"][0]["code"], "ALICE");
+
+    let response = client
+        .delete("/api/v1/users/me")
+        .header(ContentType::JSON)
+        .header(bearer(&alice))
+        .body(json!({ "password": PASSWORD }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response = client
+        .get("/api/v1/users/profile")
+        .header(bearer(&alice))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(state
+        .uploads
+        .get_by_user(alice_id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let later = chrono::Utc::now().naive_utc() + chrono::Duration::minutes(1);
+    assert_eq!(state.users.purge_deleted(later).await.unwrap(), 1);
+    assert!(state.invites.get_all().await.unwrap().is_empty());
+}
+
+#[rocket::async_test]
+async fn comments_are_changed_only_by_their_authors() {
+    let client = client().await;
+    let admin = token(&client, ADMIN).await;
+    put_resource(&client, &admin, "home", json!({ "en": "Hello" })).await;
+    let alice = confirmed(&client, "Alice").await;
+    let bob = confirmed(&client, "Bob").await;
+    let (_, comment) = post_comment(&client, &alice, "home", json!({ "body": "First" })).await;
+    let id = comment["data"]["comment"]["id"].clone();
+    moderate(&client, &admin, &id, "approve").await;
+
+    let edit = |token: String| {
+        client
+            .put(format!("/api/v1/comments/{}", id))
+            .header(ContentType::JSON)
+            .header(bearer(&token))
+            .body(json!({ "body": "Edited" }).to_string())
+            .dispatch()
+    };
+    assert_eq!(edit(bob.clone()).await.status(), Status::Forbidden);
+    let (status, body) = read(edit(alice.clone()).await).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["comment"]["body"], "Edited");
+    assert_eq!(body["data"]["comment"]["status"], "Pending");
+    assert!(body["data"]["comment"]["edited_at"].is_string());
+    moderate(&client, &admin, &id, "approve").await;
+
+    let delete = |token: String| {
+        client
+            .delete(format!("/api/v1/comments/{}", id))
+            .header(bearer(&token))
+            .dispatch()
+    };
+    assert_eq!(delete(bob).await.status(), Status::Forbidden);
+    let (status, _) = read(delete(alice.clone()).await).await;
+    assert_eq!(status, Status::Ok);
+    let comments = thread(&client, "home", None).await;
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["comment"]["body"], "");
+    assert_eq!(comments[0]["html"], "");
+    assert!(comments[0]["comment"]["deleted_at"].is_string());
+    assert_eq!(edit(alice).await.status(), Status::Forbidden);
+}
+
+#[rocket::async_test]
+async fn banned_users_cannot_comment() {
+    let client = client().await;
+    let admin = token(&client, ADMIN).await;
+    put_resource(&client, &admin, "home", json!({ "en": "Hello" })).await;
+    let alice = confirmed(&client, "Alice").await;
+    let (_, first) = post_comment(&client, &alice, "home", json!({ "body": "Spam" })).await;
+    post_comment(&client, &alice, "home", json!({ "body": "More spam" })).await;
+
+    let (status, body) = moderate(&client, &admin, &first["data"]["comment"]["id"], "ban").await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["comment"]["status"], "Rejected");
+    assert!(thread(&client, "home", Some(&alice)).await.is_empty());
+    let response = client
+        .get("/api/v1/comments/moderation/queue")
+        .header(bearer(&admin))
+        .dispatch()
+        .await;
+    assert_eq!(read(response).await.1["data"], json!([]));
+
+    let (status, body) = post_comment(&client, &alice, "home", json!({ "body": "Hi" })).await;
+    assert_eq!(status, Status::Forbidden);
+    assert_eq!(code(&body), "comment.banned");
+}
+
+#[rocket::async_test]
+async fn users_query_is_validated() {
+    let client = client().await;
+    register(&client, "Alice", None).await;
+    register(&client, "Bob", None).await;
+
+    let (status, body) = users(&client, "range=all&sort=password&order=asc").await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "validation.query.invalid_column");
+
+    let (status, body) = users(&client, "range=all&sort=name&order=asc").await;
+    assert_eq!(status, Status::Ok);
+    let names = body["data"][0]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|u| u["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["Alice", "Bob", "admin"]);
+
+    let (_, body) = users(&client, "range=1&items=1&sort=id&order=desc").await;
+    assert_eq!(body["data"][0][0]["name"], "Alice");
+}
+
+#[rocket::async_test]
+async fn resources_can_be_searched() {
+    let client = client().await;
+    let admin = token(&client, ADMIN).await;
+    let home = json!({ "en": "Welcome to my page about the projects I write in Rust" });
+    put_resource(&client, &admin, "home", home).await;
+    let about = json!({ "en": "I write Rust and TypeScript", "pl": "Piszę w Rust" });
+    put_resource(&client, &admin, "about", about).await;
+
+    let response = client.get("/api/v1/search?q=RUST%20write").dispatch().await;
+    let (status, body) = read(response).await;
+    assert_eq!(status, Status::Ok);
+    let keys = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|h| h["key"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(keys, ["about", "home"]);
+    assert_eq!(
+        body["data"][0]["snippet"],
+        "I <mark>write</mark> <mark>Rust</mark> and TypeScript"
+    );
+
+    let response = client.get("/api/v1/search?q=pisz&lang=pl").dispatch().await;
+    let (_, body) = read(response).await;
+    assert_eq!(body["data"][0]["key"], "about");
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    let response = client.get("/api/v1/search?q=%20-%20").dispatch().await;
+    let (status, body) = read(response).await;
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(code(&body), "validation.search.query_empty");
+}
+
+#[rocket::async_test]
+async fn bundles_round_trip_in_other_formats() {
+    let state = AppState::in_memory();
+    let png = (vec![1, 2, 3], "image/png".to_string());
+    state
+        .blobs
+        .upload_to("images/a.png".to_string(), png.0.clone(), png.1.clone())
+        .await
+        .unwrap();
+    let client = client_on(config(), state).await;
+    let admin = token(&client, ADMIN).await;
+    let home = "![a](memory://blobs/images/a.png) ![b](memory://blobs/images/gone.png)";
+    put_resource(&client, &admin, "home", json!({ "en": home })).await;
+
+    let response = client
+        .get("/api/v1/admin/export")
+        .header(Accept::new([Format::MsgPack.media_type()]))
+        .header(bearer(&admin))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body = response.into_bytes().await.unwrap();
+    let bundle = Format::MsgPack.deserialize::<Value>(&body).unwrap()["data"].clone();
+    assert_eq!(bundle["missing_blobs"], json!(["images/gone.png"]));
+    assert_eq!(bundle["blobs"].as_array().unwrap().len(), 1);
+
+    let state = AppState::in_memory();
+    let target = client_on(config(), state.clone()).await;
+    let admin = token(&target, ADMIN).await;
+    let response = target
+        .post("/api/v1/admin/import")
+        .header(ContentType(Format::MsgPack.media_type()))
+        .header(bearer(&admin))
+        .body(Format::MsgPack.serialize(&bundle).unwrap())
+        .dispatch()
+        .await;
+    let (status, body) = read(response).await;
+    assert_eq!(status, Status::Ok, "{}", body);
+    assert_eq!(body["data"]["created"], json!(["home"]));
+    assert_eq!(get_resource(&target, "home", "en").await.1["data"], home);
+    let blob = state.blobs.download("images/a.png".to_string()).await;
+    assert_eq!(blob.unwrap(), png);
+}
@@ -0,0 +1,98 @@
+use chrono::NaiveDate;
+use petompp_web_api::controllers::response::{ApiResponse, Cached};
+use rocket::{
+    get,
+    http::{Accept, Header, MediaType, Status},
+    local::blocking::Client,
+    routes,
+};
+
+#[get("/cached")]
+fn cached() -> Cached<ApiResponse<'static, &'static str>> {
+    Cached {
+        body: ApiResponse::ok("# Hello"),
+        last_modified: NaiveDate::from_ymd_opt(2023, 10, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap(),
+        cache_control: "public, no-cache".to_string(),
+    }
+}
+
+fn client() -> Client {
+    Client::tracked(rocket::build().mount("/", routes![cached])).unwrap()
+}
+
+fn etag(client: &Client) -> String {
+    let response = client.get("/cached").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    response.headers().get_one("ETag").unwrap().to_string()
+}
+
+#[test]
+fn response_carries_validators() {
+    let client = client();
+    let response = client.get("/cached").dispatch();
+    let headers = response.headers();
+    assert!(headers.get_one("ETag").unwrap().starts_with('"'));
+    assert_eq!(
+        headers.get_one("Last-Modified"),
+        Some("Sun, 01 Oct 2023 12:00:00 GMT")
+    );
+    assert_eq!(headers.get_one("Cache-Control"), Some("public, no-cache"));
+}
+
+#[test]
+fn matching_etag_is_not_modified() {
+    let client = client();
+    let etag = etag(&client);
+    let response = client
+        .get("/cached")
+        .header(Header::new("If-None-Match", etag.clone()))
+        .dispatch();
+    assert_eq!(response.status(), Status::NotModified);
+    assert_eq!(response.headers().get_one("ETag"), Some(etag.as_str()));
+    assert!(response.into_bytes().unwrap_or_default().is_empty());
+}
+
+#[test]
+fn stale_etag_gets_the_body() {
+    let client = client();
+    let response = client
+        .get("/cached")
+        .header(Header::new("If-None-Match", "\"stale\""))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.into_string().unwrap().contains("# Hello"));
+}
+
+#[test]
+fn etag_differs_between_formats() {
+    let client = client();
+    let msgpack = client
+        .get("/cached")
+        .header(Accept::new([MediaType::MsgPack]))
+        .dispatch();
+    assert_ne!(msgpack.headers().get_one("ETag").unwrap(), etag(&client));
+}
+
+#[test]
+fn if_modified_since_is_honored() {
+    let client = client();
+    let fresh = client
+        .get("/cached")
+        .header(Header::new(
+            "If-Modified-Since",
+            "Sun, 01 Oct 2023 12:00:00 GMT",
+        ))
+        .dispatch();
+    assert_eq!(fresh.status(), Status::NotModified);
+    let stale = client
+        .get("/cached")
+        .header(Header::new(
+            "If-Modified-Since",
+            "Sat, 30 Sep 2023 12:00:00 GMT",
+        ))
+        .dispatch();
+    assert_eq!(stale.status(), Status::Ok);
+}
@@ -0,0 +1,122 @@
+// Every test crate uses only some of the helpers.
+#![allow(dead_code)]
+
+use petompp_web_api::{
+    auth::{cookie::AuthMode, keys::JwtKeys},
+    build_rocket,
+    config::{
+        AppConfig, JwtConfig, ResourcesCacheConfig, RobotsConfig, StorageConfig,
+        VerificationKeyConfig,
+    },
+    models::{
+        password::PasswordRequirements, registration::RegistrationPolicy, role::Role, user::User,
+    },
+    services::azure_blob::AzureBlobSecrets,
+    state::AppState,
+};
+use rocket::{
+    http::{ContentType, Header, Status},
+    local::asynchronous::{Client, LocalResponse},
+    serde::json::{serde_json::json, Value},
+};
+
+pub const PASSWORD: &str = "Str0ng!Password";
+pub const ADMIN: &str = "admin";
+
+/// Configuration of the test API, the storage and database are never reached.
+pub fn config() -> AppConfig {
+    let key = |name: &str| format!("{}/tests/common/keys/{}", env!("CARGO_MANIFEST_DIR"), name);
+    AppConfig {
+        database_url: String::new(),
+        jwt: JwtConfig {
+            signing_key_id: "test".to_string(),
+            signing_key: key("test.pem"),
+            verification_keys: vec![VerificationKeyConfig {
+                kid: "test".to_string(),
+                path: key("test.pub.pem"),
+            }],
+            token_lifetime: AppConfig::DEFAULT_TOKEN_LIFETIME,
+        },
+        password: PasswordRequirements::default(),
+        storage: StorageConfig::Azure(AzureBlobSecrets {
+            account: "test".to_string(),
+            account_key: String::new(),
+            container_name: "test".to_string(),
+        }),
+        cors_origins: vec![],
+        registration: RegistrationPolicy::Open,
+        auth_mode: AuthMode::Header,
+        deletion_grace_days: AppConfig::DEFAULT_DELETION_GRACE_DAYS,
+        resources_cache_control: AppConfig::DEFAULT_RESOURCES_CACHE_CONTROL.to_string(),
+        resources_cache: ResourcesCacheConfig::default(),
+        site_url: "https://petompp.net".to_string(),
+        site_langs: vec!["en".to_string()],
+        api_url: "https://api.petompp.net".to_string(),
+        robots: RobotsConfig::default(),
+    }
+}
+
+/// Client of the whole API backed by [`AppState::in_memory`], with a confirmed [`ADMIN`].
+pub async fn client_with(config: AppConfig) -> Client {
+    client_on(config, AppState::in_memory()).await
+}
+
+/// Client of the whole API backed by the `state`, with a confirmed [`ADMIN`].
+pub async fn client_on(config: AppConfig, state: AppState) -> Client {
+    let keys = JwtKeys::new(&config.jwt).unwrap();
+    let mut admin = User::new(
+        ADMIN.to_string(),
+        PASSWORD.to_string(),
+        Role::Admin,
+        &config.password,
+    )
+    .unwrap();
+    admin.confirmed = true;
+    state.users.create(&admin).await.unwrap();
+    Client::tracked(build_rocket(&config, keys, state))
+        .await
+        .unwrap()
+}
+
+pub async fn client() -> Client {
+    client_with(config()).await
+}
+
+pub fn bearer(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+pub async fn read(response: LocalResponse<'_>) -> (Status, Value) {
+    let status = response.status();
+    (
+        status,
+        response.into_json::<Value>().await.unwrap_or_default(),
+    )
+}
+
+pub async fn register(client: &Client, name: &str, invite: Option<&str>) -> (Status, Value) {
+    let response = client
+        .post("/api/v1/users")
+        .header(ContentType::JSON)
+        .body(json!({ "name": name, "password": PASSWORD, "invite": invite }).to_string())
+        .dispatch()
+        .await;
+    read(response).await
+}
+
+pub async fn login(client: &Client, name: &str, password: &str) -> (Status, Value) {
+    let response = client
+        .post("/api/v1/users/login")
+        .header(ContentType::JSON)
+        .body(json!({ "name": name, "password": password }).to_string())
+        .dispatch()
+        .await;
+    read(response).await
+}
+
+/// Token of the user, who has to be confirmed already.
+pub async fn token(client: &Client, name: &str) -> String {
+    let (status, body) = login(client, name, PASSWORD).await;
+    assert_eq!(status, Status::Ok, "{}", body);
+    body["data"]["token"].as_str().unwrap().to_string()
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use chrono::NaiveDateTime;
+use common::{client_on, config};
+use petompp_web_api::{
+    error::Error,
+    models::{page_meta::PageMeta, resource_data::ResourceData},
+    repositories::{blocking, resources::repo::ResourcesRepo},
+    state::AppState,
+};
+use rocket::{async_trait, futures::future::join_all, http::Status};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+const QUERY_TIME: Duration = Duration::from_millis(100);
+const REQUESTS: usize = 8;
+
+/// Resources behind a query taking [`QUERY_TIME`], like a slow database round trip.
+#[derive(Default)]
+struct SlowRepo {
+    /// Whether the query runs through [`blocking`] as the Diesel repositories do.
+    offloaded: bool,
+    running: Arc<AtomicUsize>,
+    most_running: Arc<AtomicUsize>,
+}
+
+impl SlowRepo {
+    fn query(
+        running: Arc<AtomicUsize>,
+        most_running: Arc<AtomicUsize>,
+    ) -> Result<(String, NaiveDateTime), Error> {
+        let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+        most_running.fetch_max(now_running, Ordering::SeqCst);
+        thread::sleep(QUERY_TIME);
+        running.fetch_sub(1, Ordering::SeqCst);
+        Ok(("Hello".to_string(), NaiveDateTime::default()))
+    }
+}
+
+#[async_trait]
+impl ResourcesRepo for SlowRepo {
+    async fn get(&self, _key: &str, _lang: &str) -> Result<(String, NaiveDateTime), Error> {
+        let (running, most_running) = (self.running.clone(), self.most_running.clone());
+        match self.offloaded {
+            true => blocking(move || Self::query(running, most_running)).await,
+            false => Self::query(running, most_running),
+        }
+    }
+
+    async fn get_all(&self) -> Result<Vec<ResourceData>, Error> {
+        Ok(vec![])
+    }
+
+    async fn exists(&self, _key: &str) -> Result<bool, Error> {
+        Ok(true)
+    }
+
+    async fn create(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        Ok(data.clone())
+    }
+
+    async fn update(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        Ok(data.clone())
+    }
+
+    async fn delete(&self, _key: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn get_meta(&self, key: &str, lang: &str) -> Result<PageMeta, Error> {
+        Ok(PageMeta::new(key, lang))
+    }
+
+    async fn update_meta(&self, meta: &PageMeta) -> Result<PageMeta, Error> {
+        Ok(meta.clone())
+    }
+}
+
+/// Sends [`REQUESTS`] concurrent reads through the API, returns how many queries overlapped at most.
+async fn load(offloaded: bool) -> usize {
+    let most_running = Arc::new(AtomicUsize::default());
+    let state = AppState {
+        resources: Arc::new(SlowRepo {
+            offloaded,
+            most_running: most_running.clone(),
+            ..Default::default()
+        }),
+        ..AppState::in_memory()
+    };
+    let client = client_on(config(), state).await;
+    let requests = (0..REQUESTS).map(|_| client.get("/api/v1/res/home?lang=en").dispatch());
+    for response in join_all(requests).await {
+        assert_eq!(response.status(), Status::Ok);
+    }
+    most_running.load(Ordering::SeqCst)
+}
+
+#[rocket::async_test]
+async fn blocking_queries_serialize_requests() {
+    assert_eq!(load(false).await, 1);
+}
+
+#[rocket::async_test]
+async fn offloaded_queries_run_concurrently() {
+    assert!(load(true).await > 1);
+}
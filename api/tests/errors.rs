@@ -0,0 +1,323 @@
+use petompp_web_api::{
+    error::{
+        ApiError, AuthError, CommentValidationError, Error, InviteValidationError,
+        PageMetaValidationError, Problem, ProfileValidationError, QueryValidationError,
+        ResourceDataValidationError, SearchValidationError, UsernameValidationError,
+        ValidationError,
+    },
+    models::password::PasswordRequirements,
+};
+use rocket::{
+    get,
+    http::{Accept, ContentType, MediaType, Status},
+    local::blocking::Client,
+    routes,
+    serde::json::{serde_json, serde_json::Value},
+};
+
+#[get("/taken")]
+fn taken() -> Result<(), ApiError<'static>> {
+    Err(Error::UserNameTaken("PetoMPP".to_string()).into())
+}
+
+#[get("/unknown")]
+fn unknown() -> Result<(), ApiError<'static>> {
+    Err(Error::Status(999, "Unknown".to_string()).into())
+}
+
+/// An error of every kind with the status it has to be sent with.
+fn statuses() -> Vec<(Error, Status)> {
+    let s = || "value".to_string();
+    let validation = Error::ValidationError;
+    vec![
+        (
+            Error::AuthError(AuthError::MissingClaim(s())),
+            Status::BadRequest,
+        ),
+        (
+            Error::AuthError(AuthError::InvalidFormat(s())),
+            Status::BadRequest,
+        ),
+        (
+            Error::AuthError(AuthError::InvalidClaim(s())),
+            Status::BadRequest,
+        ),
+        (
+            Error::AuthError(AuthError::TokenExpiredS(1)),
+            Status::BadRequest,
+        ),
+        (
+            Error::AuthError(AuthError::TokenNotYetValidS(1)),
+            Status::BadRequest,
+        ),
+        (
+            Error::AuthError(AuthError::UnknownKey(s())),
+            Status::BadRequest,
+        ),
+        (
+            Error::AuthError(AuthError::InvalidKey(s())),
+            Status::BadRequest,
+        ),
+        (
+            Error::AuthError(AuthError::JwtError(s())),
+            Status::InternalServerError,
+        ),
+        (Error::DatabaseError(s()), Status::InternalServerError),
+        (
+            Error::DatabaseConnectionError(s()),
+            Status::InternalServerError,
+        ),
+        (Error::UserNameTaken(s()), Status::BadRequest),
+        (Error::UserNotFound(s()), Status::NotFound),
+        (Error::InvalidCredentials, Status::Unauthorized),
+        (Error::UserNotConfirmed(s()), Status::PaymentRequired),
+        (Error::RegistrationClosed, Status::Forbidden),
+        (Error::InviteCodeRequired, Status::BadRequest),
+        (Error::InviteCodeInvalid(s()), Status::BadRequest),
+        (Error::InviteCodeExpired(s()), Status::BadRequest),
+        (Error::InviteCodeUsedUp(s()), Status::BadRequest),
+        (Error::InviteNotFound(s()), Status::NotFound),
+        (Error::BundleInvalid(s()), Status::BadRequest),
+        (Error::BundleVersionUnsupported(2), Status::BadRequest),
+        (Error::CommentNotFound(s()), Status::NotFound),
+        (Error::CommentEditWindowClosed(15), Status::Forbidden),
+        (Error::CommentingBanned, Status::Forbidden),
+        (
+            validation(ValidationError::Username(
+                UsernameValidationError::InvalidLength(3, 28),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Username(
+                UsernameValidationError::InvalidCharacters(vec!['-']),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Password(PasswordRequirements::default())),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Query(QueryValidationError::InvalidColumn(
+                s(),
+            ))),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::ResourceData(
+                ResourceDataValidationError::KeyMismatch(s(), s()),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::ResourceData(
+                ResourceDataValidationError::KeyMissing,
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::ResourceData(
+                ResourceDataValidationError::ValueMissing,
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Profile(
+                ProfileValidationError::DisplayNameTooLong(64),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Profile(
+                ProfileValidationError::BioTooLong(1),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Profile(
+                ProfileValidationError::TooManyLinks(1),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Profile(
+                ProfileValidationError::InvalidLink(s()),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Profile(
+                ProfileValidationError::AvatarTooLong(255),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Profile(
+                ProfileValidationError::LinkTooLong(255),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Profile(
+                ProfileValidationError::AvatarNotUploaded(s()),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Invite(
+                InviteValidationError::InvalidMaxUses,
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Invite(InviteValidationError::ExpiryInPast)),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Search(SearchValidationError::QueryEmpty)),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Search(
+                SearchValidationError::QueryTooLong(1),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::PageMeta(
+                PageMetaValidationError::LangInvalid(s()),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::PageMeta(
+                PageMetaValidationError::TitleTooLong(1),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::PageMeta(
+                PageMetaValidationError::DescriptionTooLong(1),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::PageMeta(
+                PageMetaValidationError::InvalidUrl(s()),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::PageMeta(
+                PageMetaValidationError::UrlTooLong(1),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Comment(CommentValidationError::BodyEmpty)),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Comment(
+                CommentValidationError::BodyTooLong(2000),
+            )),
+            Status::BadRequest,
+        ),
+        (
+            validation(ValidationError::Comment(
+                CommentValidationError::ParentInvalid(1),
+            )),
+            Status::BadRequest,
+        ),
+        (Error::Status(418, s()), Status::ImATeapot),
+    ]
+}
+
+#[get("/error/<index>")]
+fn error(index: usize) -> Result<(), ApiError<'static>> {
+    Err(statuses().swap_remove(index).0.into())
+}
+
+fn client() -> Client {
+    Client::tracked(rocket::build().mount("/", routes![taken, unknown, error])).unwrap()
+}
+
+fn problem_json() -> Accept {
+    Accept::new([
+        MediaType::new("application", "problem+json"),
+        MediaType::JSON,
+    ])
+}
+
+#[test]
+fn envelope_is_the_default() {
+    let client = client();
+    let response = client.get("/taken").header(Accept::JSON).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    let body = response.into_json::<Value>().unwrap();
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["data"]["UserNameTaken"], "PetoMPP");
+}
+
+#[test]
+fn problem_is_negotiated() {
+    let client = client();
+    let response = client.get("/taken").header(problem_json()).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("application", "problem+json"))
+    );
+    let problem = response.into_json::<Problem>().unwrap();
+    assert_eq!(problem.kind, "about:blank");
+    assert_eq!(problem.status, 400);
+    assert_eq!(problem.code, "user.name_taken");
+    assert_eq!(problem.params, vec!["PetoMPP".to_string()]);
+    assert_eq!(
+        problem.into_error(),
+        Error::UserNameTaken("PetoMPP".to_string())
+    );
+}
+
+#[test]
+fn every_error_is_sent_with_its_status() {
+    let client = client();
+    let statuses = statuses();
+    // Every code has an error of its kind above.
+    assert_eq!(statuses.len(), Error::CODES.len());
+    for (index, (error, status)) in statuses.into_iter().enumerate() {
+        let response = client
+            .get(format!("/error/{}", index))
+            .header(problem_json())
+            .dispatch();
+        assert_eq!(response.status(), status, "{:?}", error);
+        let problem = response.into_json::<Problem>().unwrap();
+        assert_eq!(problem.code, error.code());
+        assert_eq!(problem.into_error(), error);
+    }
+}
+
+#[test]
+fn invalid_status_code_does_not_panic() {
+    let client = client();
+    let response = client.get("/unknown").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
+
+#[test]
+fn unknown_errors_degrade_to_status() {
+    let problem = serde_json::from_value::<Problem>(serde_json::json!({
+        "title": "Comments are disabled",
+        "status": 403,
+        "code": "comments.disabled",
+        "error": { "CommentsDisabled": null }
+    }))
+    .unwrap();
+    assert_eq!(problem.code, "comments.disabled");
+    assert_eq!(
+        problem.into_error(),
+        Error::Status(403, "Comments are disabled".to_string())
+    );
+}
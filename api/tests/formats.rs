@@ -0,0 +1,117 @@
+use petompp_web_api::{
+    controllers::response::{ApiResponse, Format, Negotiated},
+    error::{ApiError, Error},
+    models::resource_data::ResourceData,
+};
+use rocket::{
+    get,
+    http::{Accept, ContentType, Header, Status},
+    local::blocking::Client,
+    post, routes,
+    serde::json::{serde_json, serde_json::Value},
+};
+
+#[post("/echo", data = "<value>")]
+fn echo(value: Negotiated<ResourceData>) -> Negotiated<ApiResponse<'static, ResourceData>> {
+    Negotiated(ApiResponse::ok(value.into_inner()))
+}
+
+#[get("/missing")]
+fn missing() -> Result<Negotiated<ApiResponse<'static, ()>>, ApiError<'static>> {
+    Err(Error::UserNotFound("PetoMPP".to_string()).into())
+}
+
+fn client() -> Client {
+    Client::tracked(rocket::build().mount("/", routes![echo, missing])).unwrap()
+}
+
+fn resource() -> ResourceData {
+    ResourceData::new_from_lang("home", "pl", "Witaj").unwrap()
+}
+
+#[test]
+fn bodies_have_the_same_meaning_in_every_format() {
+    let client = client();
+    let expected = serde_json::to_value(ApiResponse::ok(resource())).unwrap();
+    for format in Format::ALL {
+        let response = client
+            .post("/echo")
+            .header(ContentType(format.media_type()))
+            .header(Accept::new([format.media_type()]))
+            .body(format.serialize(&resource()).unwrap())
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok, "{:?}", format);
+        assert_eq!(
+            response.content_type(),
+            Some(ContentType(format.media_type()))
+        );
+        let body = response.into_bytes().unwrap();
+        assert_eq!(
+            format.deserialize::<Value>(&body).unwrap(),
+            expected,
+            "{:?}",
+            format
+        );
+    }
+}
+
+#[test]
+fn errors_have_the_same_meaning_in_every_format() {
+    let client = client();
+    let expected =
+        serde_json::to_value(ApiResponse::err(Error::UserNotFound("PetoMPP".to_string()))).unwrap();
+    for format in Format::ALL {
+        let response = client
+            .get("/missing")
+            .header(Accept::new([format.media_type()]))
+            .dispatch();
+        assert_eq!(response.status(), Status::NotFound, "{:?}", format);
+        let body = response.into_bytes().unwrap();
+        assert_eq!(
+            format.deserialize::<Value>(&body).unwrap(),
+            expected,
+            "{:?}",
+            format
+        );
+    }
+}
+
+#[test]
+fn json_is_the_default() {
+    let client = client();
+    let response = client
+        .post("/echo")
+        .body(serde_json::to_string(&resource()).unwrap())
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+}
+
+#[test]
+fn preferred_format_wins() {
+    let client = client();
+    let response = client
+        .post("/echo")
+        .header(ContentType::JSON)
+        .header(Header::new(
+            "Accept",
+            "application/json; q=0.5, application/cbor",
+        ))
+        .body(serde_json::to_string(&resource()).unwrap())
+        .dispatch();
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType(Format::Cbor.media_type()))
+    );
+}
+
+#[test]
+fn unsupported_body_is_rejected() {
+    let client = client();
+    let response = client
+        .post("/echo")
+        .header(ContentType::XML)
+        .body("<resource />")
+        .dispatch();
+    assert_eq!(response.status(), Status::UnsupportedMediaType);
+}
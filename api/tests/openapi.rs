@@ -0,0 +1,130 @@
+use petompp_web_api::{
+    api_controllers,
+    controllers::{
+        controller::Controller, openapi::ApiDoc, sitemap::SitemapController,
+        well_known::WellKnownController,
+    },
+};
+use regex::Regex;
+use rocket::serde::json::{serde_json, serde_json::Value};
+use std::collections::BTreeSet;
+use utoipa::OpenApi;
+
+const METHODS: [&str; 7] = ["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// `/users/<id>/` -> `/users/{id}`
+fn normalize(path: &str) -> String {
+    let params = Regex::new(r"<([^>.]+)(\.\.)?>").unwrap();
+    let path = params.replace_all(path, "{$1}");
+    match path.trim_end_matches('/') {
+        "" => "/".to_string(),
+        path => path.to_string(),
+    }
+}
+
+fn spec() -> Value {
+    serde_json::to_value(ApiDoc::openapi()).unwrap()
+}
+
+#[test]
+fn spec_matches_routes() {
+    let mut routes = BTreeSet::new();
+    let well_known = (
+        WellKnownController.path().to_string(),
+        WellKnownController.routes(),
+    );
+    // Mounted at `/`, its routes already start with it.
+    let sitemap = (String::new(), SitemapController.routes());
+    let controllers = api_controllers()
+        .into_iter()
+        .map(|c| (c.mount_path(), c.routes()))
+        .chain([well_known, sitemap]);
+    for (mount_path, controller_routes) in controllers {
+        for route in controller_routes {
+            routes.insert((
+                route.method.as_str().to_lowercase(),
+                normalize(&format!("{}{}", mount_path, route.uri.path())),
+            ));
+        }
+    }
+    let spec = spec();
+    let documented = spec["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(path, item)| {
+            item.as_object()
+                .unwrap()
+                .keys()
+                .filter(|k| METHODS.contains(&k.as_str()))
+                .map(|method| (method.clone(), normalize(path)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<BTreeSet<_>>();
+
+    let undocumented = routes.difference(&documented).collect::<Vec<_>>();
+    let stale = documented.difference(&routes).collect::<Vec<_>>();
+    assert!(
+        undocumented.is_empty(),
+        "Routes missing in the spec: {:?}",
+        undocumented
+    );
+    assert!(stale.is_empty(), "Spec paths without a route: {:?}", stale);
+}
+
+#[test]
+fn every_operation_documents_errors() {
+    let spec = spec();
+    for (path, item) in spec["paths"].as_object().unwrap() {
+        for (method, operation) in item.as_object().unwrap() {
+            if !METHODS.contains(&method.as_str()) || path.starts_with("/.well-known") {
+                continue;
+            }
+            let responses = operation["responses"].as_object().unwrap();
+            assert!(
+                responses.contains_key("200"),
+                "{} {} has no success response",
+                method,
+                path
+            );
+            let secured = operation.get("security").is_some();
+            if secured {
+                assert!(
+                    responses.contains_key("401"),
+                    "{} {} is secured but does not document 401",
+                    method,
+                    path
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn references_resolve() {
+    fn collect_refs(value: &Value, refs: &mut BTreeSet<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(r)) = map.get("$ref") {
+                    refs.insert(r.clone());
+                }
+                map.values().for_each(|v| collect_refs(v, refs));
+            }
+            Value::Array(values) => values.iter().for_each(|v| collect_refs(v, refs)),
+            _ => {}
+        }
+    }
+
+    let spec = spec();
+    let mut refs = BTreeSet::new();
+    collect_refs(&spec, &mut refs);
+    let schemas = spec["components"]["schemas"].as_object().unwrap();
+    let missing = refs
+        .iter()
+        .filter(|r| {
+            r.strip_prefix("#/components/schemas/")
+                .map_or(true, |name| !schemas.contains_key(name))
+        })
+        .collect::<Vec<_>>();
+    assert!(missing.is_empty(), "Unresolved references: {:?}", missing);
+}
@@ -0,0 +1,177 @@
+use chrono::NaiveDateTime;
+use petompp_web_api::{
+    config::ResourcesCacheConfig,
+    error::Error,
+    models::{page_meta::PageMeta, resource_data::ResourceData},
+    repositories::resources::{cache::CachedResourcesRepo, repo::ResourcesRepo},
+};
+use rocket::{async_trait, tokio::runtime};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+};
+
+/// Resources in memory, counting the reads.
+#[derive(Default)]
+struct MemoryRepo {
+    values: Mutex<HashMap<String, String>>,
+    reads: AtomicUsize,
+    /// Lets the test pause a read after the value was loaded.
+    pause: Mutex<Option<(mpsc::Sender<()>, mpsc::Receiver<()>)>>,
+}
+
+impl MemoryRepo {
+    fn with(key: &str, value: &str) -> Self {
+        let repo = Self::default();
+        repo.values
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.to_string());
+        repo
+    }
+}
+
+#[async_trait]
+impl ResourcesRepo for MemoryRepo {
+    async fn get(&self, key: &str, _lang: &str) -> Result<(String, NaiveDateTime), Error> {
+        self.reads.fetch_add(1, Ordering::SeqCst);
+        let value = self
+            .values
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or(Error::Status(404, "Not Found".to_string()))?;
+        if let Some((loaded, resume)) = self.pause.lock().unwrap().take() {
+            loaded.send(()).unwrap();
+            resume.recv().unwrap();
+        }
+        Ok((value, NaiveDateTime::default()))
+    }
+
+    async fn get_all(&self) -> Result<Vec<ResourceData>, Error> {
+        Ok(vec![])
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, Error> {
+        Ok(self.values.lock().unwrap().contains_key(key))
+    }
+
+    async fn create(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        self.update(data).await
+    }
+
+    async fn update(&self, data: &ResourceData) -> Result<ResourceData, Error> {
+        self.values.lock().unwrap().insert(
+            data.key.clone().unwrap(),
+            data.en.clone().unwrap_or_default(),
+        );
+        Ok(data.clone())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), Error> {
+        self.values.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn get_meta(&self, key: &str, lang: &str) -> Result<PageMeta, Error> {
+        Ok(PageMeta::new(key, lang))
+    }
+
+    async fn update_meta(&self, meta: &PageMeta) -> Result<PageMeta, Error> {
+        Ok(meta.clone())
+    }
+}
+
+fn config(capacity: usize, ttl: u64) -> ResourcesCacheConfig {
+    ResourcesCacheConfig { capacity, ttl }
+}
+
+/// Drives the repository future on the calling thread, the fake never yields.
+fn wait<T>(future: impl Future<Output = T>) -> T {
+    runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+fn value(repo: &impl ResourcesRepo, key: &str) -> String {
+    wait(repo.get(key, "en")).unwrap().0
+}
+
+#[test]
+fn repeated_reads_are_served_from_memory() {
+    let repo = CachedResourcesRepo::new(MemoryRepo::with("home", "Hello"), config(8, 60));
+    assert_eq!(value(&repo, "home"), "Hello");
+    assert_eq!(value(&repo, "home"), "Hello");
+    assert_eq!(repo.inner().reads.load(Ordering::SeqCst), 1);
+    let metrics = repo.cache_metrics().unwrap();
+    assert_eq!((metrics.hits, metrics.misses, metrics.entries), (1, 1, 1));
+}
+
+#[test]
+fn writes_invalidate_every_language() {
+    let repo = CachedResourcesRepo::new(MemoryRepo::with("home", "Hello"), config(8, 60));
+    value(&repo, "home");
+    wait(repo.get("home", "pl")).unwrap();
+    wait(repo.update(&ResourceData::new_from_lang("home", "en", "Hi").unwrap())).unwrap();
+    assert_eq!(value(&repo, "home"), "Hi");
+    assert_eq!(wait(repo.get("home", "pl")).unwrap().0, "Hi");
+    wait(repo.delete("home")).unwrap();
+    assert!(wait(repo.get("home", "en")).is_err());
+}
+
+#[test]
+fn expired_values_are_read_again() {
+    let repo = CachedResourcesRepo::new(MemoryRepo::with("home", "Hello"), config(8, 0));
+    value(&repo, "home");
+    value(&repo, "home");
+    assert_eq!(repo.inner().reads.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn least_recently_used_value_is_evicted() {
+    let repo = CachedResourcesRepo::new(MemoryRepo::with("home", "Hello"), config(1, 60));
+    wait(repo.update(&ResourceData::new_from_lang("about", "en", "About").unwrap())).unwrap();
+    value(&repo, "home");
+    value(&repo, "about");
+    value(&repo, "home");
+    assert_eq!(repo.inner().reads.load(Ordering::SeqCst), 3);
+    assert_eq!(repo.cache_metrics().unwrap().entries, 1);
+}
+
+#[test]
+fn disabled_cache_always_reads_through() {
+    let repo = CachedResourcesRepo::new(MemoryRepo::with("home", "Hello"), config(0, 60));
+    value(&repo, "home");
+    value(&repo, "home");
+    assert_eq!(repo.inner().reads.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn read_racing_an_update_is_not_cached() {
+    let repo = Arc::new(CachedResourcesRepo::new(
+        MemoryRepo::with("home", "Hello"),
+        config(8, 60),
+    ));
+    let (loaded_tx, loaded_rx) = mpsc::channel();
+    let (resume_tx, resume_rx) = mpsc::channel();
+    *repo.inner().pause.lock().unwrap() = Some((loaded_tx, resume_rx));
+
+    let reader = {
+        let repo = repo.clone();
+        thread::spawn(move || value(&*repo, "home"))
+    };
+    loaded_rx.recv().unwrap();
+    wait(repo.update(&ResourceData::new_from_lang("home", "en", "Hi").unwrap())).unwrap();
+    resume_tx.send(()).unwrap();
+
+    // The read started before the update, so it may see the old value, later ones may not.
+    assert_eq!(reader.join().unwrap(), "Hello");
+    assert_eq!(value(&*repo, "home"), "Hi");
+}
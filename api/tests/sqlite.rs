@@ -0,0 +1,160 @@
+//! The API on an SQLite database, run with `cargo test --no-default-features --features sqlite`.
+#![cfg(feature = "sqlite")]
+
+mod common;
+
+use common::{bearer, client_on, config, read, register, token, ADMIN};
+use diesel_migrations::MigrationHarness;
+use petompp_web_api::{get_connection_pool, state::AppState, MIGRATIONS};
+use rocket::{
+    http::{ContentType, Status},
+    local::asynchronous::Client,
+    serde::json::{serde_json::json, Value},
+};
+use std::path::PathBuf;
+
+/// Database file removed with the client.
+struct Database {
+    path: PathBuf,
+    state: AppState,
+    client: Client,
+}
+
+impl Drop for Database {
+    fn drop(&mut self) {
+        _ = std::fs::remove_file(&self.path);
+    }
+}
+
+async fn database() -> Database {
+    let path = std::env::temp_dir().join(format!("petompp-{}.db", uuid::Uuid::new_v4()));
+    let pool = get_connection_pool(path.to_str().unwrap());
+    pool.get()
+        .unwrap()
+        .run_pending_migrations(MIGRATIONS)
+        .unwrap();
+    let config = config();
+    let state = AppState::new(pool, &config);
+    let client = client_on(config, state.clone()).await;
+    Database {
+        path,
+        state,
+        client,
+    }
+}
+
+async fn post(client: &Client, token: &str, uri: &str, value: Value) -> (Status, Value) {
+    let response = client
+        .post(uri.to_string())
+        .header(ContentType::JSON)
+        .header(bearer(token))
+        .body(value.to_string())
+        .dispatch()
+        .await;
+    read(response).await
+}
+
+async fn put(client: &Client, token: &str, uri: &str, value: Value) -> (Status, Value) {
+    let response = client
+        .put(uri.to_string())
+        .header(ContentType::JSON)
+        .header(bearer(token))
+        .body(value.to_string())
+        .dispatch()
+        .await;
+    read(response).await
+}
+
+#[rocket::async_test]
+async fn users_are_stored() {
+    let db = database().await;
+    let client = &db.client;
+    let (status, user) = register(client, "Alice", None).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(user["data"]["role"], "User");
+    let (status, _) = register(client, "alice", None).await;
+    assert_eq!(status, Status::BadRequest);
+
+    let admin = token(client, ADMIN).await;
+    let response = client
+        .post(format!("/api/v1/users/{}/activate", user["data"]["id"]))
+        .header(bearer(&admin))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let alice = token(client, "alice").await;
+
+    let profile = json!({ "display_name": "Alice", "links": ["https://example.com"] });
+    let (status, body) = put(client, &alice, "/api/v1/users/profile", profile.clone()).await;
+    assert_eq!(status, Status::Ok, "{}", body);
+    let (status, body) = put(client, &alice, "/api/v1/users/profile", profile).await;
+    assert_eq!(status, Status::Ok, "{}", body);
+    assert_eq!(body["data"]["links"], json!(["https://example.com"]));
+
+    let response = client
+        .get("/api/v1/users/all?range=all&sort=name&order=desc")
+        .header(bearer(&admin))
+        .dispatch()
+        .await;
+    let (status, body) = read(response).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"][0][0]["name"], "admin");
+}
+
+#[rocket::async_test]
+async fn resources_are_stored() {
+    let db = database().await;
+    let client = &db.client;
+    let admin = token(client, ADMIN).await;
+    let (status, body) = put(client, &admin, "/api/v1/res/home", json!({ "en": "Hello" })).await;
+    assert_eq!(status, Status::Ok, "{}", body);
+    let response = client
+        .post("/api/v1/res/home")
+        .header(ContentType::JSON)
+        .header(bearer(&admin))
+        .body(json!({ "key": "home", "pl": "Cześć" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get("/api/v1/res/home?lang=pl").dispatch().await;
+    let (status, body) = read(response).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"], "Cześć");
+    let response = client.get("/api/v1/res/keys").dispatch().await;
+    let keys = read(response).await.1["data"].clone();
+    assert!(keys.as_array().unwrap().contains(&json!("home")));
+}
+
+#[rocket::async_test]
+async fn replies_outlive_their_purged_parents() {
+    let db = database().await;
+    let client = &db.client;
+    let admin = token(client, ADMIN).await;
+    put(client, &admin, "/api/v1/res/home", json!({ "en": "Hello" })).await;
+    let (_, user) = register(client, "Alice", None).await;
+    let alice_id = &user["data"]["id"];
+    let uri = format!("/api/v1/users/{}/activate", alice_id);
+    post(client, &admin, &uri, Value::Null).await;
+    let alice = token(client, "alice").await;
+
+    let comment = json!({ "body": "First" });
+    let (status, body) = post(client, &alice, "/api/v1/comments/home", comment).await;
+    assert_eq!(status, Status::Ok, "{}", body);
+    let id = &body["data"]["comment"]["id"];
+    let uri = format!("/api/v1/comments/{}/moderate?action=approve", id);
+    post(client, &admin, &uri, Value::Null).await;
+    let reply = json!({ "body": "Reply", "parent_id": id });
+    let (status, body) = post(client, &admin, "/api/v1/comments/home", reply).await;
+    assert_eq!(status, Status::Ok, "{}", body);
+
+    let alice_id = alice_id.as_i64().unwrap() as i32;
+    db.state.users.anonymize(alice_id).await.unwrap();
+    let later = chrono::Utc::now().naive_utc() + chrono::Duration::minutes(1);
+    assert_eq!(db.state.users.purge_deleted(later).await.unwrap(), 1);
+    let (_, body) = read(client.get("/api/v1/comments/home").dispatch().await).await;
+    let comments = body["data"].as_array().unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["comment"]["body"], "Reply");
+    assert_eq!(comments[0]["comment"]["parent_id"], Value::Null);
+}
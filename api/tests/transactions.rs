@@ -0,0 +1,173 @@
+use diesel_migrations::MigrationHarness;
+use petompp_web_api::{
+    config::ResourcesCacheConfig,
+    error::Error,
+    get_connection_pool,
+    models::{password::PasswordRequirements, resource_data::ResourceData, role::Role, user::User},
+    repositories::{
+        resources::{cache::CachedResourcesRepo, memory::InMemoryResourcesRepo},
+        transaction::atomically,
+    },
+    state::AppState,
+    MIGRATIONS,
+};
+use std::sync::Arc;
+
+fn alice() -> User {
+    User::new(
+        "Alice".to_string(),
+        "Str0ng!Password".to_string(),
+        Role::User,
+        &PasswordRequirements::default(),
+    )
+    .unwrap()
+}
+
+fn resource(key: &str, en: &str) -> ResourceData {
+    ResourceData {
+        key: Some(key.to_string()),
+        en: Some(en.to_string()),
+        ..Default::default()
+    }
+}
+
+#[rocket::async_test]
+async fn committed_work_is_kept() {
+    let state = AppState::in_memory();
+    let user = state
+        .transaction(|repos| {
+            Box::pin(async move {
+                let user = repos.users.create(&alice()).await?;
+                repos.resources.create(&resource("home", "Hello")).await?;
+                Ok(user)
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(user.name.as_str(), "Alice");
+    assert!(state.users.get_by_name("alice".to_string()).await.is_ok());
+    assert_eq!(state.resources.get("home", "en").await.unwrap().0, "Hello");
+}
+
+#[rocket::async_test]
+async fn failed_work_is_rolled_back() {
+    let state = AppState::in_memory();
+    let result = state
+        .transaction(|repos| {
+            Box::pin(async move {
+                repos.users.create(&alice()).await?;
+                repos.invites.use_code("missing").await?;
+                Ok(())
+            })
+        })
+        .await;
+    assert!(matches!(result, Err(Error::InviteCodeUsedUp(_))));
+    assert!(state.users.get_by_name("alice".to_string()).await.is_err());
+}
+
+#[rocket::async_test]
+async fn failed_import_keeps_the_resources() {
+    let state = AppState::in_memory();
+    state
+        .resources
+        .create(&resource("home", "Hello"))
+        .await
+        .unwrap();
+    let result = state
+        .transaction(|repos| {
+            Box::pin(async move {
+                repos.resources.update(&resource("home", "Hi")).await?;
+                repos.resources.create(&resource("about", "About")).await?;
+                // Fails, the key is missing.
+                repos.resources.create(&ResourceData::default()).await?;
+                Ok(())
+            })
+        })
+        .await;
+    assert!(result.is_err());
+    assert_eq!(state.resources.get("home", "en").await.unwrap().0, "Hello");
+    assert!(state.resources.get("about", "en").await.is_err());
+}
+
+#[rocket::async_test]
+async fn only_resource_writes_clear_the_cache() {
+    let state = AppState {
+        resources: Arc::new(CachedResourcesRepo::new(
+            InMemoryResourcesRepo::default(),
+            ResourcesCacheConfig::default(),
+        )),
+        ..AppState::in_memory()
+    };
+    state
+        .resources
+        .create(&resource("home", "Hello"))
+        .await
+        .unwrap();
+    state.resources.get("home", "en").await.unwrap();
+    let cached = || state.resources.cache_metrics().unwrap().entries;
+    assert_eq!(cached(), 1);
+
+    state
+        .transaction(|repos| {
+            Box::pin(async move {
+                repos.users.create(&alice()).await?;
+                repos.resources.get_all().await?;
+                Ok(())
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(cached(), 1);
+
+    state
+        .transaction(|repos| {
+            Box::pin(async move {
+                repos.resources.create(&resource("about", "About")).await?;
+                Ok(())
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(cached(), 0);
+}
+
+#[rocket::async_test]
+#[ignore = "needs DATABASE_URL"]
+async fn database_transaction_is_rolled_back() {
+    let pool = get_connection_pool(&std::env::var("DATABASE_URL").unwrap());
+    pool.get()
+        .unwrap()
+        .run_pending_migrations(MIGRATIONS)
+        .unwrap();
+    let key = "transactions-test";
+
+    let result = atomically(&pool, move |repos| {
+        Box::pin(async move {
+            repos.resources.create(&resource(key, "Hello")).await?;
+            repos.resources.create(&resource(key, "Hello")).await?;
+            Ok(())
+        })
+    })
+    .await;
+    assert!(result.is_err());
+    let resources = atomically(&pool, |repos| Box::pin(repos.resources.get_all()))
+        .await
+        .unwrap();
+    assert!(resources.iter().all(|r| r.key.as_deref() != Some(key)));
+
+    atomically(&pool, move |repos| {
+        Box::pin(async move {
+            repos.resources.create(&resource(key, "Hello")).await?;
+            Ok(())
+        })
+    })
+    .await
+    .unwrap();
+    let (value, _) = atomically(&pool, move |repos| Box::pin(repos.resources.get(key, "en")))
+        .await
+        .unwrap();
+    assert_eq!(value, "Hello");
+    atomically(&pool, move |repos| Box::pin(repos.resources.delete(key)))
+        .await
+        .unwrap();
+}
@@ -1,15 +1,29 @@
-use super::error::ApiError;
-use crate::models::{credentials::Credentials, resource_data::ResourceData, user::User};
-use reqwasm::http::*;
+use super::error::{ApiError, Problem};
+use crate::models::{
+    bundle::{ConflictStrategy, ImportSummary},
+    comment::{CommentRequest, ModerationAction, ThreadComment},
+    credentials::Credentials,
+    invite::{Invite, InviteRequest},
+    page_meta::PageMeta,
+    profile::{Profile, PublicProfile},
+    registration::RegistrationPolicy,
+    search::SearchHit,
+    user::User,
+};
+use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
-use std::{collections::HashMap, fmt::Display};
+use std::{collections::HashMap, fmt::Display, rc::Rc};
+use web_sys::{AbortController, AbortSignal};
+use yew::prelude::*;
 
 #[derive(Debug, PartialEq)]
 pub enum RequestError {
     Endpoint(u16, ApiError),
     Parse(String),
     Network(String),
+    /// The request was aborted with the client's signal, nothing is left to handle it.
+    Aborted,
 }
 
 impl Display for RequestError {
@@ -26,12 +40,8 @@ pub enum Response<T> {
 }
 
 impl<T: DeserializeOwned> Response<T> {
-    async fn from_response(value: reqwasm::http::Response) -> Result<Self, RequestError> {
-        let status_code = value.status();
-        let body = value
-            .json::<Value>()
-            .await
-            .map_err(|e| RequestError::Parse(e.to_string()))?;
+    /// Reads the `{ status, data }` envelope every endpoint responds with.
+    pub fn from_body(status_code: u16, body: Value) -> Result<Self, RequestError> {
         let status = body
             .get("status")
             .ok_or(RequestError::Parse("Missing status".to_string()))?
@@ -45,179 +55,185 @@ impl<T: DeserializeOwned> Response<T> {
                 serde_json::from_value(data.clone())
                     .map_err(|e| RequestError::Parse(format!("Invalid data format, {}", e)))?,
             )),
+            // Errors added to the API after this build are shown as they are.
             "error" => Ok(Response::Error(
                 status_code,
                 serde_json::from_value(data.clone())
-                    .map_err(|e| RequestError::Parse(format!("Invalid data format, {}", e)))?,
+                    .unwrap_or_else(|_| ApiError::Status(status_code, data.to_string())),
             )),
             _ => Err(RequestError::Parse("Invalid status".to_string())),
         }
     }
+
+    /// Reads the `application/problem+json` details of an error response.
+    pub fn from_problem(body: Value) -> Result<Self, RequestError> {
+        let problem = serde_json::from_value::<Problem>(body)
+            .map_err(|e| RequestError::Parse(format!("Invalid problem format, {}", e)))?;
+        Ok(Response::Error(problem.status, problem.into_error()))
+    }
 }
 
-pub struct Client;
-lazy_static::lazy_static! {
-    static ref API_URL: String = match std::option_env!("API_URL").unwrap_or_default() {
-        url if url.ends_with('/') => url.to_string(),
-        url => format!("{}/", url)
-    };
-    static ref AZURE_STORAGE_URL: String = match std::option_env!("AZURE_STORAGE_URL").unwrap_or_default() {
-        url if url.ends_with('/') => url.to_string(),
-        url => format!("{}/", url)
-    };
+/// Result of a request conditional on the cached value's `ETag`.
+#[derive(Debug, PartialEq)]
+pub enum Revalidated<T> {
+    NotModified,
+    Modified { value: T, etag: Option<String> },
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct LoginResponse {
-    pub token: String,
+    pub token: Option<String>,
+    pub csrf: Option<String>,
     pub user: User,
 }
 
-impl Client {
-    fn get_api_url(path: &str) -> String {
-        format!("{}{}", *API_URL, path)
-    }
-
-    async fn send_json<R: DeserializeOwned>(
-        method: Method,
-        path: &str,
-        token: Option<&str>,
-        body: Option<&impl Serialize>,
-    ) -> Result<R, RequestError> {
-        let mut request = Request::new(Self::get_api_url(path).as_str()).method(method);
-        if let Some(token) = token {
-            request = request.header("Authorization", format!("Bearer {}", token).as_str());
-        }
-        if let Some(body) = body {
-            request = request
-                .body(serde_json::to_string(body).map_err(|e| RequestError::Parse(e.to_string()))?);
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| RequestError::Network(e.to_string()))?;
-
-        match Response::from_response(response).await? {
-            Response::Success(data) => Ok(data),
-            Response::Error(s, e) => Err(RequestError::Endpoint(s, e)),
-        }
-    }
-
-    pub async fn login(credentials: Credentials) -> Result<LoginResponse, RequestError> {
-        Self::send_json(Method::POST, "api/v1/users/login", None, Some(&credentials)).await
-    }
-
-    pub async fn register(credentials: Credentials) -> Result<(), RequestError> {
-        Self::send_json::<User>(Method::POST, "api/v1/users", None, Some(&credentials))
-            .await
-            .map(|_| ())
-    }
-
-    pub async fn get_users(token: &str) -> Result<Vec<User>, RequestError> {
-        Self::send_json(
-            Method::GET,
-            "api/v1/users/all?range=all",
-            Some(token),
-            Option::<&String>::None,
-        )
-        .await
-        .map(|u: Vec<Vec<User>>| u[0].clone())
-    }
-
-    pub async fn activate_user(token: &str, id: i32) -> Result<(), RequestError> {
-        Self::send_json::<User>(
-            Method::POST,
-            format!("api/v1/users/{}/activate", id).as_str(),
-            Some(token),
-            Option::<&String>::None,
-        )
-        .await
-        .map(|_| ())
-    }
-
-    pub async fn delete_user(token: &str, id: i32) -> Result<(), RequestError> {
-        Self::send_json::<User>(
-            Method::DELETE,
-            format!("api/v1/users/{}", id).as_str(),
-            Some(token),
-            Option::<&String>::None,
-        )
-        .await
-        .map(|_| ())
-    }
-
-    pub async fn get_resource(key: &str, lang: &str) -> Result<String, RequestError> {
-        Self::send_json(
-            Method::GET,
-            format!("api/v1/res/{}?lang={}", key, lang).as_str(),
-            None,
-            Option::<&String>::None,
-        )
-        .await
-    }
-
-    pub async fn get_resource_keys(token: &str) -> Result<Vec<String>, RequestError> {
-        Self::send_json(
-            Method::GET,
-            "api/v1/res/keys",
-            Some(token),
-            Option::<&String>::None,
-        )
-        .await
-    }
+/// Everything the front asks the API for.
+/// Components get it through [`use_api`], so tests can provide a fake one.
+#[async_trait(?Send)]
+pub trait ApiClient {
+    /// Client whose requests are aborted together with the `signal`.
+    /// Aborted requests fail with [`RequestError::Aborted`], which the handlers ignore.
+    fn scoped(&self, signal: AbortSignal) -> Rc<dyn ApiClient>;
+
+    async fn login(&self, credentials: Credentials) -> Result<LoginResponse, RequestError>;
+    async fn logout(&self) -> Result<(), RequestError>;
+    async fn register(&self, credentials: Credentials) -> Result<(), RequestError>;
+    async fn get_registration_policy(&self) -> Result<RegistrationPolicy, RequestError>;
+
+    async fn get_users(&self, token: &str) -> Result<Vec<User>, RequestError>;
+    async fn activate_user(&self, token: &str, id: i32) -> Result<(), RequestError>;
+    async fn delete_user(&self, token: &str, id: i32) -> Result<(), RequestError>;
+
+    async fn get_invites(&self, token: &str) -> Result<Vec<Invite>, RequestError>;
+    async fn create_invite(
+        &self,
+        token: &str,
+        request: &InviteRequest,
+    ) -> Result<Invite, RequestError>;
+    async fn delete_invite(&self, token: &str, id: i32) -> Result<(), RequestError>;
 
-    pub async fn update_resource(
+    async fn export_bundle(&self, token: &str) -> Result<Value, RequestError>;
+    async fn import_bundle(
+        &self,
+        token: &str,
+        bundle: &Value,
+        strategy: ConflictStrategy,
+        dry_run: bool,
+    ) -> Result<ImportSummary, RequestError>;
+
+    async fn export_user_data(&self, token: &str) -> Result<Value, RequestError>;
+    async fn delete_account(&self, token: &str, password: &str) -> Result<(), RequestError>;
+    async fn get_profile(&self, token: &str) -> Result<Profile, RequestError>;
+    async fn update_profile(&self, token: &str, profile: &Profile)
+        -> Result<Profile, RequestError>;
+    async fn get_public_profile(&self, name: &str) -> Result<PublicProfile, RequestError>;
+
+    /// Sends the `etag` of the cached value, so it is only downloaded again when it changed.
+    async fn get_resource(
+        &self,
+        key: &str,
+        lang: &str,
+        etag: Option<&str>,
+    ) -> Result<Revalidated<String>, RequestError>;
+    async fn get_resource_keys(&self, token: &str) -> Result<Vec<String>, RequestError>;
+    async fn update_resource(
+        &self,
         token: &str,
         key: &str,
         lang: &str,
         value: &str,
-    ) -> Result<(), RequestError> {
-        let resource = ResourceData::new_from_lang(key, lang, value)?;
-        Self::send_json(
-            Method::POST,
-            format!("api/v1/res/{}", key).as_str(),
-            Some(token),
-            Some(&resource),
-        )
-        .await
-        .map(|_: ResourceData| ())
+    ) -> Result<(), RequestError>;
+    /// Metadata of the page, the API falls back to the English or empty one.
+    async fn get_page_meta(&self, key: &str, lang: &str) -> Result<PageMeta, RequestError>;
+    async fn update_page_meta(
+        &self,
+        token: &str,
+        meta: &PageMeta,
+    ) -> Result<PageMeta, RequestError>;
+    /// Best matches of the `query` in the content of the `lang`.
+    async fn search(&self, query: &str, lang: &str) -> Result<Vec<SearchHit>, RequestError>;
+    /// Thread of the page, with the user's pending comments when the `token` is sent.
+    async fn get_comments(
+        &self,
+        token: Option<&str>,
+        key: &str,
+    ) -> Result<Vec<ThreadComment>, RequestError>;
+    async fn create_comment(
+        &self,
+        token: &str,
+        key: &str,
+        request: &CommentRequest,
+    ) -> Result<ThreadComment, RequestError>;
+    async fn update_comment(
+        &self,
+        token: &str,
+        id: i32,
+        request: &CommentRequest,
+    ) -> Result<ThreadComment, RequestError>;
+    async fn delete_comment(&self, token: &str, id: i32) -> Result<ThreadComment, RequestError>;
+    async fn get_comment_queue(&self, token: &str) -> Result<Vec<ThreadComment>, RequestError>;
+    async fn moderate_comment(
+        &self,
+        token: &str,
+        id: i32,
+        action: ModerationAction,
+    ) -> Result<ThreadComment, RequestError>;
+    async fn get_locale(&self, lang: &str) -> Result<HashMap<String, String>, RequestError>;
+    async fn upload_img(
+        &self,
+        token: &str,
+        img: web_sys::File,
+        folder: &str,
+    ) -> Result<String, RequestError>;
+}
+
+/// The [`ApiClient`] provided to the components.
+#[derive(Clone)]
+pub struct ApiContext(pub Rc<dyn ApiClient>);
+
+impl ApiContext {
+    pub fn new(client: impl ApiClient + 'static) -> Self {
+        Self(Rc::new(client))
     }
+}
 
-    pub async fn get_locale(lang: &str) -> Result<HashMap<String, String>, RequestError> {
-        let resp = Request::new(format!("/locales/{}.yml", lang).as_str())
-            .method(Method::GET)
-            .send()
-            .await
-            .map_err(|e| RequestError::Network(e.to_string()))?;
-        let body = resp
-            .binary()
-            .await
-            .map_err(|e| RequestError::Network(e.to_string()))?;
-
-        serde_yaml::from_slice::<HashMap<String, String>>(&body)
-            .map_err(|e| RequestError::Parse(e.to_string()))
+impl PartialEq for ApiContext {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::as_ptr(&self.0) as *const () == Rc::as_ptr(&other.0) as *const ()
     }
+}
 
-    pub async fn upload_img(
-        token: &str,
-        img: web_sys::File,
-        folder: &str,
-    ) -> Result<String, RequestError> {
-        let resp = Request::new(
-            Self::get_api_url(format!("api/v1/img/?folder={}", folder).as_str()).as_str(),
-        )
-        .method(Method::PUT)
-        .header("Authorization", format!("Bearer {}", token).as_str())
-        .body(img)
-        .send()
-        .await
-        .map_err(|e| RequestError::Network(e.to_string()))?;
-        match Response::<String>::from_response(resp).await? {
-            Response::Success(filename) => {
-                Ok(format!("{}{}/{}", *AZURE_STORAGE_URL, folder, filename))
-            }
-            Response::Error(s, e) => Err(RequestError::Endpoint(s, e)),
-        }
+/// The client from the context, its requests are aborted when the component unmounts.
+#[hook]
+pub fn use_api() -> Rc<dyn ApiClient> {
+    let context = use_context::<ApiContext>().expect("ApiContext is not provided");
+    // Nothing is requested while rendering on the server.
+    let controller = use_memo(
+        |_| match cfg!(feature = "ssr") {
+            true => None,
+            false => AbortController::new().ok(),
+        },
+        (),
+    );
+    {
+        let controller = controller.clone();
+        use_effect_with_deps(
+            move |_| {
+                move || {
+                    if let Some(controller) = &*controller {
+                        controller.abort();
+                    }
+                }
+            },
+            (),
+        );
     }
+    let client = use_memo(
+        |context| match &*controller {
+            Some(controller) => context.0.scoped(controller.signal()),
+            None => context.0.clone(),
+        },
+        context,
+    );
+    (*client).clone()
 }
@@ -0,0 +1,494 @@
+use super::{
+    client::{ApiClient, LoginResponse, RequestError, Revalidated},
+    error::ApiError,
+};
+use crate::models::{
+    bundle::{ConflictStrategy, ImportSummary},
+    comment::{Comment, CommentRequest, CommentStatus, ModerationAction, ThreadComment},
+    credentials::Credentials,
+    invite::{Invite, InviteRequest},
+    page_meta::PageMeta,
+    profile::{Profile, PublicProfile},
+    registration::RegistrationPolicy,
+    resource_data::ResourceData,
+    search::{search, SearchDocument, SearchHit},
+    user::User,
+    user_name::UserName,
+};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::{
+    cell::{RefCell, RefMut},
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+use web_sys::AbortSignal;
+
+/// In-memory [`ApiClient`] for tests, all clones share the same data.
+#[derive(Clone, Default)]
+pub struct FakeClient(Rc<RefCell<FakeData>>);
+
+#[derive(Default)]
+pub struct FakeData {
+    pub users: Vec<User>,
+    pub invites: Vec<Invite>,
+    pub resources: Vec<ResourceData>,
+    pub metas: Vec<PageMeta>,
+    pub comments: Vec<ThreadComment>,
+    pub profile: Profile,
+    /// Public profiles by the lowercase user name.
+    pub profiles: HashMap<String, PublicProfile>,
+    pub registration_policy: RegistrationPolicy,
+    pub locale: HashMap<String, String>,
+    /// Error returned instead of the data by the next call of the named method.
+    pub errors: HashMap<&'static str, RequestError>,
+    /// Names of the called methods, in order.
+    pub calls: Vec<&'static str>,
+}
+
+impl FakeClient {
+    pub fn new(data: FakeData) -> Self {
+        Self(Rc::new(RefCell::new(data)))
+    }
+
+    pub fn data(&self) -> RefMut<FakeData> {
+        self.0.borrow_mut()
+    }
+
+    fn call(&self, name: &'static str) -> Result<RefMut<FakeData>, RequestError> {
+        let mut data = self.0.borrow_mut();
+        data.calls.push(name);
+        match data.errors.remove(name) {
+            Some(error) => Err(error),
+            None => Ok(data),
+        }
+    }
+}
+
+fn not_found(error: ApiError) -> RequestError {
+    RequestError::Endpoint(404, error)
+}
+
+impl FakeData {
+    /// User of the `token` handed out by [`FakeClient::login`].
+    fn user_of(&self, token: &str) -> Option<&User> {
+        let name = token.strip_prefix("token-")?;
+        self.users.iter().find(|u| u.normalized_name == name)
+    }
+
+    fn comment_mut(&mut self, id: i32) -> Result<&mut ThreadComment, RequestError> {
+        self.comments
+            .iter_mut()
+            .find(|c| c.comment.id == Some(id))
+            .ok_or_else(|| not_found(ApiError::CommentNotFound(format!("ID: {}", id))))
+    }
+}
+
+#[async_trait(?Send)]
+impl ApiClient for FakeClient {
+    fn scoped(&self, _signal: AbortSignal) -> Rc<dyn ApiClient> {
+        Rc::new(self.clone())
+    }
+
+    async fn login(&self, credentials: Credentials) -> Result<LoginResponse, RequestError> {
+        let data = self.call("login")?;
+        let name = credentials.name.to_lowercase();
+        match data.users.iter().find(|u| u.normalized_name == name) {
+            Some(user) => Ok(LoginResponse {
+                token: Some(format!("token-{}", name)),
+                csrf: None,
+                user: user.clone(),
+            }),
+            None => Err(RequestError::Endpoint(401, ApiError::InvalidCredentials)),
+        }
+    }
+
+    async fn logout(&self) -> Result<(), RequestError> {
+        self.call("logout").map(|_| ())
+    }
+
+    async fn register(&self, credentials: Credentials) -> Result<(), RequestError> {
+        let mut data = self.call("register")?;
+        let name = UserName::new(credentials.name).map_err(|e| RequestError::Endpoint(400, e))?;
+        let normalized_name = name.to_lowercase();
+        if data
+            .users
+            .iter()
+            .any(|u| u.normalized_name == normalized_name)
+        {
+            return Err(RequestError::Endpoint(
+                400,
+                ApiError::UserNameTaken(name.to_string()),
+            ));
+        }
+        let id = data.users.len() as i32 + 1;
+        data.users.push(User {
+            id: Some(id),
+            name,
+            normalized_name,
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    async fn get_registration_policy(&self) -> Result<RegistrationPolicy, RequestError> {
+        self.call("get_registration_policy")
+            .map(|d| d.registration_policy)
+    }
+
+    async fn get_users(&self, _token: &str) -> Result<Vec<User>, RequestError> {
+        self.call("get_users").map(|d| d.users.clone())
+    }
+
+    async fn activate_user(&self, _token: &str, id: i32) -> Result<(), RequestError> {
+        let mut data = self.call("activate_user")?;
+        match data.users.iter_mut().find(|u| u.id == Some(id)) {
+            Some(user) => {
+                user.confirmed = true;
+                Ok(())
+            }
+            None => Err(not_found(ApiError::UserNotFound(id.to_string()))),
+        }
+    }
+
+    async fn delete_user(&self, _token: &str, id: i32) -> Result<(), RequestError> {
+        let mut data = self.call("delete_user")?;
+        match data.users.iter_mut().find(|u| u.id == Some(id)) {
+            Some(user) => {
+                user.deleted_at = Some(chrono::Utc::now().naive_utc());
+                Ok(())
+            }
+            None => Err(not_found(ApiError::UserNotFound(id.to_string()))),
+        }
+    }
+
+    async fn get_invites(&self, _token: &str) -> Result<Vec<Invite>, RequestError> {
+        self.call("get_invites").map(|d| d.invites.clone())
+    }
+
+    async fn create_invite(
+        &self,
+        _token: &str,
+        request: &InviteRequest,
+    ) -> Result<Invite, RequestError> {
+        let mut data = self.call("create_invite")?;
+        let id = data
+            .invites
+            .iter()
+            .filter_map(|i| i.id)
+            .max()
+            .unwrap_or_default()
+            + 1;
+        let invite = Invite {
+            id: Some(id),
+            code: format!("invite-{}", id),
+            role: request.role,
+            max_uses: request.max_uses,
+            expires_at: request.expires_at,
+            created_at: Some(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        };
+        data.invites.push(invite.clone());
+        Ok(invite)
+    }
+
+    async fn delete_invite(&self, _token: &str, id: i32) -> Result<(), RequestError> {
+        let mut data = self.call("delete_invite")?;
+        match data.invites.iter().position(|i| i.id == Some(id)) {
+            Some(index) => {
+                data.invites.remove(index);
+                Ok(())
+            }
+            None => Err(not_found(ApiError::InviteNotFound(id.to_string()))),
+        }
+    }
+
+    async fn export_bundle(&self, _token: &str) -> Result<Value, RequestError> {
+        self.call("export_bundle")
+            .map(|d| json!({ "resources": d.resources }))
+    }
+
+    async fn import_bundle(
+        &self,
+        _token: &str,
+        _bundle: &Value,
+        _strategy: ConflictStrategy,
+        dry_run: bool,
+    ) -> Result<ImportSummary, RequestError> {
+        self.call("import_bundle").map(|_| ImportSummary {
+            dry_run,
+            ..Default::default()
+        })
+    }
+
+    async fn export_user_data(&self, _token: &str) -> Result<Value, RequestError> {
+        self.call("export_user_data")
+            .map(|d| json!({ "profile": d.profile }))
+    }
+
+    async fn delete_account(&self, _token: &str, _password: &str) -> Result<(), RequestError> {
+        self.call("delete_account").map(|_| ())
+    }
+
+    async fn get_profile(&self, _token: &str) -> Result<Profile, RequestError> {
+        self.call("get_profile").map(|d| d.profile.clone())
+    }
+
+    async fn update_profile(
+        &self,
+        _token: &str,
+        profile: &Profile,
+    ) -> Result<Profile, RequestError> {
+        let mut data = self.call("update_profile")?;
+        data.profile = profile.clone();
+        Ok(profile.clone())
+    }
+
+    async fn get_public_profile(&self, name: &str) -> Result<PublicProfile, RequestError> {
+        self.call("get_public_profile")?
+            .profiles
+            .get(&name.to_lowercase())
+            .cloned()
+            .ok_or_else(|| not_found(ApiError::UserNotFound(name.to_string())))
+    }
+
+    async fn get_resource(
+        &self,
+        key: &str,
+        lang: &str,
+        etag: Option<&str>,
+    ) -> Result<Revalidated<String>, RequestError> {
+        let value = self
+            .call("get_resource")?
+            .resources
+            .iter()
+            .find(|r| r.key.as_deref() == Some(key))
+            .and_then(|r| match lang {
+                "pl" => r.pl.clone().or_else(|| r.en.clone()),
+                _ => r.en.clone(),
+            })
+            .ok_or_else(|| not_found(ApiError::Status(404, "Not Found".to_string())))?;
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let current = format!("\"{:x}\"", hasher.finish());
+        match etag == Some(current.as_str()) {
+            true => Ok(Revalidated::NotModified),
+            false => Ok(Revalidated::Modified {
+                value,
+                etag: Some(current),
+            }),
+        }
+    }
+
+    async fn get_resource_keys(&self, _token: &str) -> Result<Vec<String>, RequestError> {
+        self.call("get_resource_keys")
+            .map(|d| d.resources.iter().filter_map(|r| r.key.clone()).collect())
+    }
+
+    async fn update_resource(
+        &self,
+        _token: &str,
+        key: &str,
+        lang: &str,
+        value: &str,
+    ) -> Result<(), RequestError> {
+        let mut data = self.call("update_resource")?;
+        let update = ResourceData::new_from_lang(key, lang, value)
+            .ok_or(RequestError::Parse("Invalid language".into()))?;
+        match data.resources.iter_mut().find(|r| r.key == update.key) {
+            Some(resource) => {
+                resource.en = update.en.or(resource.en.take());
+                resource.pl = update.pl.or(resource.pl.take());
+            }
+            None => data.resources.push(update),
+        }
+        Ok(())
+    }
+
+    async fn get_page_meta(&self, key: &str, lang: &str) -> Result<PageMeta, RequestError> {
+        let data = self.call("get_page_meta")?;
+        let find = |lang: &str| {
+            data.metas
+                .iter()
+                .find(|m| m.key == key && m.lang == lang)
+                .cloned()
+        };
+        Ok(find(lang)
+            .or_else(|| find("en"))
+            .map(|meta| PageMeta {
+                lang: lang.to_string(),
+                ..meta
+            })
+            .unwrap_or_else(|| PageMeta::new(key, lang)))
+    }
+
+    async fn update_page_meta(
+        &self,
+        _token: &str,
+        meta: &PageMeta,
+    ) -> Result<PageMeta, RequestError> {
+        let mut data = self.call("update_page_meta")?;
+        let meta = meta.clone().normalized();
+        meta.validate()
+            .map_err(|e| RequestError::Endpoint(e.status_code(), e))?;
+        data.metas
+            .retain(|m| !(m.key == meta.key && m.lang == meta.lang));
+        data.metas.push(meta.clone());
+        Ok(meta)
+    }
+
+    async fn search(&self, query: &str, lang: &str) -> Result<Vec<SearchHit>, RequestError> {
+        let documents = self
+            .call("search")?
+            .resources
+            .iter()
+            .filter_map(|r| {
+                let body = match lang {
+                    "pl" => r.pl.clone().or_else(|| r.en.clone()),
+                    _ => r.en.clone(),
+                };
+                Some(SearchDocument {
+                    kind: "resource".to_string(),
+                    key: r.key.clone()?,
+                    body: body?,
+                })
+            })
+            .collect::<Vec<_>>();
+        search(documents, query, 20).map_err(|e| RequestError::Endpoint(e.status_code(), e))
+    }
+
+    async fn get_comments(
+        &self,
+        token: Option<&str>,
+        key: &str,
+    ) -> Result<Vec<ThreadComment>, RequestError> {
+        let data = self.call("get_comments")?;
+        let viewer = token.and_then(|t| data.user_of(t)).and_then(|u| u.id);
+        Ok(data
+            .comments
+            .iter()
+            .filter(|c| c.comment.key == key)
+            .filter(|c| match c.comment.status {
+                CommentStatus::Approved => true,
+                CommentStatus::Pending => Some(c.comment.user_id) == viewer,
+                CommentStatus::Rejected => false,
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn create_comment(
+        &self,
+        token: &str,
+        key: &str,
+        request: &CommentRequest,
+    ) -> Result<ThreadComment, RequestError> {
+        let mut data = self.call("create_comment")?;
+        let body = Comment::validate_body(request)
+            .map_err(|e| RequestError::Endpoint(e.status_code(), e))?;
+        let author = data.user_of(token).cloned().unwrap_or_default();
+        let id = data
+            .comments
+            .iter()
+            .filter_map(|c| c.comment.id)
+            .max()
+            .unwrap_or_default()
+            + 1;
+        let comment = ThreadComment::new(
+            Comment {
+                id: Some(id),
+                key: key.to_string(),
+                parent_id: request.parent_id,
+                user_id: author.id.unwrap_or_default(),
+                body,
+                created_at: Some(chrono::Utc::now().naive_utc()),
+                ..Default::default()
+            },
+            author.name,
+        );
+        data.comments.push(comment.clone());
+        Ok(comment)
+    }
+
+    async fn update_comment(
+        &self,
+        _token: &str,
+        id: i32,
+        request: &CommentRequest,
+    ) -> Result<ThreadComment, RequestError> {
+        let mut data = self.call("update_comment")?;
+        let body = Comment::validate_body(request)
+            .map_err(|e| RequestError::Endpoint(e.status_code(), e))?;
+        let comment = data.comment_mut(id)?;
+        *comment = ThreadComment::new(
+            Comment {
+                body,
+                edited_at: Some(chrono::Utc::now().naive_utc()),
+                ..comment.comment.clone()
+            },
+            comment.author.clone(),
+        );
+        Ok(comment.clone())
+    }
+
+    async fn delete_comment(&self, _token: &str, id: i32) -> Result<ThreadComment, RequestError> {
+        let mut data = self.call("delete_comment")?;
+        let comment = data.comment_mut(id)?;
+        *comment = ThreadComment::new(
+            Comment {
+                deleted_at: Some(chrono::Utc::now().naive_utc()),
+                ..comment.comment.clone()
+            },
+            comment.author.clone(),
+        );
+        Ok(comment.clone())
+    }
+
+    async fn get_comment_queue(&self, _token: &str) -> Result<Vec<ThreadComment>, RequestError> {
+        self.call("get_comment_queue").map(|d| {
+            d.comments
+                .iter()
+                .filter(|c| c.comment.status == CommentStatus::Pending && !c.comment.is_deleted())
+                .cloned()
+                .collect()
+        })
+    }
+
+    async fn moderate_comment(
+        &self,
+        _token: &str,
+        id: i32,
+        action: ModerationAction,
+    ) -> Result<ThreadComment, RequestError> {
+        let mut data = self.call("moderate_comment")?;
+        let user_id = data.comment_mut(id)?.comment.user_id;
+        if action == ModerationAction::Ban {
+            data.comments
+                .iter_mut()
+                .filter(|c| c.comment.user_id == user_id)
+                .filter(|c| c.comment.status == CommentStatus::Pending)
+                .for_each(|c| c.comment.status = CommentStatus::Rejected);
+        }
+        let comment = data.comment_mut(id)?;
+        comment.comment.status = match action {
+            ModerationAction::Approve => CommentStatus::Approved,
+            ModerationAction::Reject | ModerationAction::Ban => CommentStatus::Rejected,
+        };
+        Ok(comment.clone())
+    }
+
+    async fn get_locale(&self, _lang: &str) -> Result<HashMap<String, String>, RequestError> {
+        self.call("get_locale").map(|d| d.locale.clone())
+    }
+
+    async fn upload_img(
+        &self,
+        _token: &str,
+        img: web_sys::File,
+        folder: &str,
+    ) -> Result<String, RequestError> {
+        self.call("upload_img")
+            .map(|_| format!("https://storage.test/{}/{}", folder, img.name()))
+    }
+}
@@ -0,0 +1,560 @@
+use super::{
+    client::{ApiClient, LoginResponse, RequestError, Response, Revalidated},
+    error::Problem,
+};
+use crate::{
+    data::session::SessionStore,
+    models::{
+        bundle::{ConflictStrategy, ImportSummary},
+        comment::{CommentRequest, ModerationAction, ThreadComment},
+        credentials::Credentials,
+        invite::{Invite, InviteRequest},
+        page_meta::PageMeta,
+        profile::{Profile, PublicProfile},
+        registration::RegistrationPolicy,
+        resource_data::ResourceData,
+        search::SearchHit,
+        user::User,
+    },
+};
+use async_trait::async_trait;
+use futures::future::{self, Either};
+use gloo::timers::future::TimeoutFuture;
+use reqwasm::http::{Method, Request, RequestCredentials};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{collections::HashMap, pin::pin, rc::Rc};
+use wasm_bindgen::JsValue;
+use web_sys::{AbortSignal, UrlSearchParams};
+use yewdux::prelude::*;
+
+lazy_static::lazy_static! {
+    static ref API_URL: String = match std::option_env!("API_URL").unwrap_or_default() {
+        url if url.ends_with('/') => url.to_string(),
+        url => format!("{}/", url)
+    };
+    static ref AZURE_STORAGE_URL: String = match std::option_env!("AZURE_STORAGE_URL").unwrap_or_default() {
+        url if url.ends_with('/') => url.to_string(),
+        url => format!("{}/", url)
+    };
+}
+
+/// Errors are read from the problem details, the envelope is still understood as a fallback.
+const ACCEPT: &str = "application/problem+json, application/json;q=0.9";
+
+/// [`ApiClient`] talking to the API with `fetch`.
+#[derive(Clone)]
+pub struct HttpClient {
+    /// Time a single attempt can take, in milliseconds.
+    pub timeout: u32,
+    /// How many times idempotent requests are repeated after a network error or a 502-504 response.
+    pub retries: u32,
+    /// Delay before the first retry in milliseconds, doubled for every next one.
+    pub backoff: u32,
+    signal: Option<AbortSignal>,
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self {
+            timeout: 15_000,
+            retries: 2,
+            backoff: 300,
+            signal: None,
+        }
+    }
+}
+
+impl HttpClient {
+    pub(crate) fn get_api_url(path: &str) -> String {
+        format!("{}{}", *API_URL, path)
+    }
+
+    /// Attaches the credentials to the request.
+    /// The token is sent in the header, when the API is in the cookie mode it is empty
+    /// and the browser sends the cookie along with the CSRF token instead.
+    fn authorize(mut request: Request, token: Option<&str>) -> Request {
+        if let Some(token) = token.filter(|t| !t.is_empty()) {
+            request = request.header("Authorization", format!("Bearer {}", token).as_str());
+        }
+        if let Some(csrf) = &Dispatch::<SessionStore>::new().get().csrf {
+            request = request.header("X-CSRF-Token", csrf);
+        }
+        request.credentials(RequestCredentials::Include)
+    }
+
+    /// Sends the request built by `build`, applying the timeout and retrying idempotent methods.
+    async fn send(
+        &self,
+        method: Method,
+        build: impl Fn() -> Request,
+    ) -> Result<reqwasm::http::Response, RequestError> {
+        let retries = match method {
+            Method::GET | Method::PUT | Method::DELETE => self.retries,
+            _ => 0,
+        };
+        let mut attempt = 0;
+        loop {
+            let request = build()
+                .method(method)
+                .header("Accept", ACCEPT)
+                .abort_signal(self.signal.as_ref());
+            let timeout = TimeoutFuture::new(self.timeout);
+            let result = match future::select(pin!(request.send()), pin!(timeout)).await {
+                Either::Left((result, _)) => {
+                    result.map_err(|e| RequestError::Network(e.to_string()))
+                }
+                Either::Right(_) => Err(RequestError::Network(format!(
+                    "Request timed out after {} ms",
+                    self.timeout
+                ))),
+            };
+            if self.signal.as_ref().is_some_and(|s| s.aborted()) {
+                return Err(RequestError::Aborted);
+            }
+            let retryable = match &result {
+                Ok(response) => (502..=504).contains(&response.status()),
+                Err(_) => true,
+            };
+            if !retryable || attempt >= retries {
+                return result;
+            }
+            TimeoutFuture::new(self.backoff * 2u32.pow(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    async fn read<R: DeserializeOwned>(
+        response: reqwasm::http::Response,
+    ) -> Result<R, RequestError> {
+        let status_code = response.status();
+        let problem = response
+            .headers()
+            .get("Content-Type")
+            .is_some_and(|c| c.starts_with(Problem::CONTENT_TYPE));
+        let body = response
+            .json::<Value>()
+            .await
+            .map_err(|e| RequestError::Parse(e.to_string()))?;
+        let response = match problem {
+            true => Response::from_problem(body)?,
+            false => Response::from_body(status_code, body)?,
+        };
+        match response {
+            Response::Success(data) => Ok(data),
+            Response::Error(s, e) => Err(RequestError::Endpoint(s, e)),
+        }
+    }
+
+    async fn send_json<R: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        token: Option<&str>,
+        body: Option<&impl Serialize>,
+    ) -> Result<R, RequestError> {
+        let url = Self::get_api_url(path);
+        let body = body
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| RequestError::Parse(e.to_string()))?;
+        let response = self
+            .send(method, || {
+                let request = Self::authorize(Request::new(url.as_str()), token);
+                match &body {
+                    Some(body) => request
+                        .header("Content-Type", "application/json")
+                        .body(JsValue::from_str(body)),
+                    None => request,
+                }
+            })
+            .await?;
+        Self::read(response).await
+    }
+}
+
+#[async_trait(?Send)]
+impl ApiClient for HttpClient {
+    fn scoped(&self, signal: AbortSignal) -> Rc<dyn ApiClient> {
+        Rc::new(Self {
+            signal: Some(signal),
+            ..self.clone()
+        })
+    }
+
+    async fn login(&self, credentials: Credentials) -> Result<LoginResponse, RequestError> {
+        self.send_json(Method::POST, "api/v1/users/login", None, Some(&credentials))
+            .await
+    }
+
+    async fn logout(&self) -> Result<(), RequestError> {
+        self.send_json(
+            Method::POST,
+            "api/v1/users/logout",
+            None,
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn register(&self, credentials: Credentials) -> Result<(), RequestError> {
+        self.send_json::<User>(Method::POST, "api/v1/users", None, Some(&credentials))
+            .await
+            .map(|_| ())
+    }
+
+    async fn get_registration_policy(&self) -> Result<RegistrationPolicy, RequestError> {
+        self.send_json(
+            Method::GET,
+            "api/v1/users/registration",
+            None,
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn get_users(&self, token: &str) -> Result<Vec<User>, RequestError> {
+        self.send_json(
+            Method::GET,
+            "api/v1/users/all?range=all",
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+        .map(|u: Vec<Vec<User>>| u[0].clone())
+    }
+
+    async fn activate_user(&self, token: &str, id: i32) -> Result<(), RequestError> {
+        self.send_json::<User>(
+            Method::POST,
+            format!("api/v1/users/{}/activate", id).as_str(),
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn delete_user(&self, token: &str, id: i32) -> Result<(), RequestError> {
+        self.send_json::<User>(
+            Method::DELETE,
+            format!("api/v1/users/{}", id).as_str(),
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn get_invites(&self, token: &str) -> Result<Vec<Invite>, RequestError> {
+        self.send_json(
+            Method::GET,
+            "api/v1/invites",
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn create_invite(
+        &self,
+        token: &str,
+        request: &InviteRequest,
+    ) -> Result<Invite, RequestError> {
+        self.send_json(Method::POST, "api/v1/invites", Some(token), Some(request))
+            .await
+    }
+
+    async fn delete_invite(&self, token: &str, id: i32) -> Result<(), RequestError> {
+        self.send_json::<Invite>(
+            Method::DELETE,
+            format!("api/v1/invites/{}", id).as_str(),
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn export_bundle(&self, token: &str) -> Result<Value, RequestError> {
+        self.send_json(
+            Method::GET,
+            "api/v1/admin/export",
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn import_bundle(
+        &self,
+        token: &str,
+        bundle: &Value,
+        strategy: ConflictStrategy,
+        dry_run: bool,
+    ) -> Result<ImportSummary, RequestError> {
+        self.send_json(
+            Method::POST,
+            format!(
+                "api/v1/admin/import?strategy={}&dry_run={}",
+                strategy, dry_run
+            )
+            .as_str(),
+            Some(token),
+            Some(bundle),
+        )
+        .await
+    }
+
+    async fn export_user_data(&self, token: &str) -> Result<Value, RequestError> {
+        self.send_json(
+            Method::GET,
+            "api/v1/users/me/export",
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn delete_account(&self, token: &str, password: &str) -> Result<(), RequestError> {
+        self.send_json(
+            Method::DELETE,
+            "api/v1/users/me",
+            Some(token),
+            Some(&serde_json::json!({ "password": password })),
+        )
+        .await
+    }
+
+    async fn get_profile(&self, token: &str) -> Result<Profile, RequestError> {
+        self.send_json(
+            Method::GET,
+            "api/v1/users/profile",
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn update_profile(
+        &self,
+        token: &str,
+        profile: &Profile,
+    ) -> Result<Profile, RequestError> {
+        self.send_json(
+            Method::PUT,
+            "api/v1/users/profile",
+            Some(token),
+            Some(profile),
+        )
+        .await
+    }
+
+    async fn get_public_profile(&self, name: &str) -> Result<PublicProfile, RequestError> {
+        self.send_json(
+            Method::GET,
+            format!("api/v1/users/{}/profile", name).as_str(),
+            None,
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn get_resource(
+        &self,
+        key: &str,
+        lang: &str,
+        etag: Option<&str>,
+    ) -> Result<Revalidated<String>, RequestError> {
+        let url = Self::get_api_url(format!("api/v1/res/{}?lang={}", key, lang).as_str());
+        let response = self
+            .send(Method::GET, || match etag {
+                Some(etag) => Request::new(url.as_str()).header("If-None-Match", etag),
+                None => Request::new(url.as_str()),
+            })
+            .await?;
+        if response.status() == 304 {
+            return Ok(Revalidated::NotModified);
+        }
+        let etag = response.headers().get("ETag");
+        Ok(Revalidated::Modified {
+            value: Self::read(response).await?,
+            etag,
+        })
+    }
+
+    async fn get_resource_keys(&self, token: &str) -> Result<Vec<String>, RequestError> {
+        self.send_json(
+            Method::GET,
+            "api/v1/res/keys",
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn update_resource(
+        &self,
+        token: &str,
+        key: &str,
+        lang: &str,
+        value: &str,
+    ) -> Result<(), RequestError> {
+        let resource = ResourceData::new_from_lang(key, lang, value)
+            .ok_or(RequestError::Parse("Invalid language".into()))?;
+        self.send_json(
+            Method::POST,
+            format!("api/v1/res/{}", key).as_str(),
+            Some(token),
+            Some(&resource),
+        )
+        .await
+        .map(|_: ResourceData| ())
+    }
+
+    async fn get_page_meta(&self, key: &str, lang: &str) -> Result<PageMeta, RequestError> {
+        self.send_json(
+            Method::GET,
+            format!("api/v1/res/{}/meta?lang={}", key, lang).as_str(),
+            None,
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn update_page_meta(
+        &self,
+        token: &str,
+        meta: &PageMeta,
+    ) -> Result<PageMeta, RequestError> {
+        self.send_json(
+            Method::PUT,
+            format!("api/v1/res/{}/meta", meta.key).as_str(),
+            Some(token),
+            Some(meta),
+        )
+        .await
+    }
+
+    async fn search(&self, query: &str, lang: &str) -> Result<Vec<SearchHit>, RequestError> {
+        let params = UrlSearchParams::new().map_err(|e| RequestError::Parse(format!("{:?}", e)))?;
+        params.append("q", query);
+        params.append("lang", lang);
+        self.send_json(
+            Method::GET,
+            format!("api/v1/search?{}", String::from(params.to_string())).as_str(),
+            None,
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn get_comments(
+        &self,
+        token: Option<&str>,
+        key: &str,
+    ) -> Result<Vec<ThreadComment>, RequestError> {
+        self.send_json(
+            Method::GET,
+            format!("api/v1/comments/{}", key).as_str(),
+            token,
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn create_comment(
+        &self,
+        token: &str,
+        key: &str,
+        request: &CommentRequest,
+    ) -> Result<ThreadComment, RequestError> {
+        self.send_json(
+            Method::POST,
+            format!("api/v1/comments/{}", key).as_str(),
+            Some(token),
+            Some(request),
+        )
+        .await
+    }
+
+    async fn update_comment(
+        &self,
+        token: &str,
+        id: i32,
+        request: &CommentRequest,
+    ) -> Result<ThreadComment, RequestError> {
+        self.send_json(
+            Method::PUT,
+            format!("api/v1/comments/{}", id).as_str(),
+            Some(token),
+            Some(request),
+        )
+        .await
+    }
+
+    async fn delete_comment(&self, token: &str, id: i32) -> Result<ThreadComment, RequestError> {
+        self.send_json(
+            Method::DELETE,
+            format!("api/v1/comments/{}", id).as_str(),
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn get_comment_queue(&self, token: &str) -> Result<Vec<ThreadComment>, RequestError> {
+        self.send_json(
+            Method::GET,
+            "api/v1/comments/moderation/queue",
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn moderate_comment(
+        &self,
+        token: &str,
+        id: i32,
+        action: ModerationAction,
+    ) -> Result<ThreadComment, RequestError> {
+        self.send_json(
+            Method::POST,
+            format!("api/v1/comments/{}/moderate?action={}", id, action).as_str(),
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+    }
+
+    async fn get_locale(&self, lang: &str) -> Result<HashMap<String, String>, RequestError> {
+        let url = format!("/locales/{}.yml", lang);
+        let resp = self
+            .send(Method::GET, || Request::new(url.as_str()))
+            .await?;
+        let body = resp
+            .binary()
+            .await
+            .map_err(|e| RequestError::Network(e.to_string()))?;
+
+        serde_yaml::from_slice::<HashMap<String, String>>(&body)
+            .map_err(|e| RequestError::Parse(e.to_string()))
+    }
+
+    async fn upload_img(
+        &self,
+        token: &str,
+        img: web_sys::File,
+        folder: &str,
+    ) -> Result<String, RequestError> {
+        let url = Self::get_api_url(format!("api/v1/img/?folder={}", folder).as_str());
+        let resp = self
+            .send(Method::PUT, || {
+                Self::authorize(Request::new(url.as_str()), Some(token)).body(img.clone())
+            })
+            .await?;
+        let filename: String = Self::read(resp).await?;
+        Ok(format!("{}{}/{}", *AZURE_STORAGE_URL, folder, filename))
+    }
+}
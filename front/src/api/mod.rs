@@ -1,2 +1,5 @@
 pub mod client;
 pub mod error;
+#[cfg(feature = "test-utils")]
+pub mod fake;
+pub mod http;
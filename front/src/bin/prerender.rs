@@ -0,0 +1,174 @@
+use clap::Parser;
+use petompp_web_front::{
+    api::client::Response,
+    components::atoms::flag::Country,
+    data::resources::Key,
+    models::{
+        feed::FeedFormat,
+        page_meta::{self, HeadTag, PageMeta},
+    },
+    prerender::{Prerendered, PrerenderedResource, PAGES},
+};
+use reqwest::{
+    header::{ACCEPT, ETAG},
+    Client, StatusCode,
+};
+use std::{collections::HashMap, error::Error, fs, path::PathBuf};
+use strum::IntoEnumIterator;
+use yew_router::Routable;
+
+/// Renders the public pages of a `trunk build` ahead of time, with the resources from the API.
+/// Every page is written to `<dist>/<path>/index.html` and hydrated in the browser.
+#[derive(Parser)]
+#[command(name = "prerender")]
+struct Cli {
+    /// Output directory of `trunk build`, its `index.html` is the template of the pages
+    #[arg(default_value = "dist")]
+    dist: PathBuf,
+    /// URL of the API the resources are read from
+    #[arg(long, env = "API_URL")]
+    api_url: String,
+    /// Language of the pages
+    #[arg(long, default_value = "en")]
+    lang: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    let lang = Country::try_from(cli.lang.as_str())
+        .map_err(|lang| format!("Unsupported language: {}", lang))?;
+    let template = fs::read_to_string(cli.dist.join("index.html"))?;
+    let locale = serde_yaml::from_slice::<HashMap<String, String>>(&fs::read(
+        cli.dist.join("locales").join(format!("{}.yml", lang.key())),
+    )?)?;
+    let feeds = FeedFormat::iter()
+        .map(|format| format.link_html(&cli.api_url, lang.key()))
+        .collect::<String>();
+    let client = Client::new();
+    for (route, reskey) in PAGES {
+        let (mut resources, mut meta) = (vec![], PageMeta::new("", lang.key()));
+        if let Some(reskey) = reskey {
+            resources.extend(get_resource(&client, &cli.api_url, reskey, lang.key()).await?);
+            meta = get_meta(&client, &cli.api_url, reskey, lang.key()).await?;
+        }
+        let state = Prerendered {
+            path: route.to_path(),
+            lang,
+            locale: locale.clone(),
+            resources,
+        };
+        let dir = cli.dist.join(state.path.trim_start_matches('/'));
+        let page = page(&template, state, &meta, &feeds).await?;
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("index.html"), page)?;
+        println!("Prerendered {}", route.to_path());
+    }
+    Ok(())
+}
+
+/// The `template` with the rendered app in its body, the state, the `meta` tags and the `feeds` links in its head.
+async fn page(
+    template: &str,
+    state: Prerendered,
+    meta: &PageMeta,
+    feeds: &str,
+) -> Result<String, Box<dyn Error>> {
+    if !template.contains("<body></body>") || !template.contains("</head>") {
+        return Err("The template needs a head and an empty body, prerender a fresh build".into());
+    }
+    let lang = state.lang.key().to_string();
+    let head = meta
+        .head_tags()
+        .iter()
+        .map(HeadTag::to_html)
+        .chain([feeds.to_string(), state.to_script()])
+        .collect::<String>();
+    let body = state.render().await;
+    Ok(template
+        .replacen(
+            "<html lang=\"en\">",
+            &format!("<html lang=\"{}\">", lang),
+            1,
+        )
+        .replacen(
+            &format!("<title>{}</title>", PageMeta::SITE_NAME),
+            &format!(
+                "<title>{}</title>",
+                page_meta::escape(&meta.document_title())
+            ),
+            1,
+        )
+        .replacen("</head>", &format!("{}</head>", head), 1)
+        .replacen("<body></body>", &format!("<body>{}</body>", body), 1))
+}
+
+/// The resource from the API, `None` when it doesn't exist in the language.
+async fn get_resource(
+    client: &Client,
+    api_url: &str,
+    key: &str,
+    lang: &str,
+) -> Result<Option<PrerenderedResource>, Box<dyn Error>> {
+    let url = format!(
+        "{}/api/v1/res/{}?lang={}",
+        api_url.trim_end_matches('/'),
+        key,
+        lang
+    );
+    let response = client
+        .get(url)
+        .header(ACCEPT, "application/json")
+        .send()
+        .await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        eprintln!("Resource {} is missing in {}", key, lang);
+        return Ok(None);
+    }
+    let status = response.status().as_u16();
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(str::to_string);
+    match Response::<String>::from_body(status, response.json().await?)? {
+        Response::Success(value) => Ok(Some(PrerenderedResource {
+            key: Key {
+                reskey: key.to_string(),
+                lang: lang.to_string(),
+            },
+            value,
+            etag,
+        })),
+        Response::Error(status, error) => {
+            Err(format!("Resource {} failed with {}: {:?}", key, status, error).into())
+        }
+    }
+}
+
+/// Metadata of the page from the API, empty when the API has none.
+async fn get_meta(
+    client: &Client,
+    api_url: &str,
+    key: &str,
+    lang: &str,
+) -> Result<PageMeta, Box<dyn Error>> {
+    let url = format!(
+        "{}/api/v1/res/{}/meta?lang={}",
+        api_url.trim_end_matches('/'),
+        key,
+        lang
+    );
+    let response = client
+        .get(url)
+        .header(ACCEPT, "application/json")
+        .send()
+        .await?;
+    let status = response.status().as_u16();
+    match Response::<PageMeta>::from_body(status, response.json().await?)? {
+        Response::Success(meta) => Ok(meta),
+        Response::Error(status, error) => {
+            Err(format!("Metadata of {} failed with {}: {:?}", key, status, error).into())
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    api::{
+        client::{use_api, RequestError},
+        error::IntoLocalized,
+    },
+    async_event,
+    components::atoms::modal::show_error,
+    data::{
+        locales::{LocalesStore, TK},
+        session::SessionStore,
+    },
+    handle_api_error,
+    models::bundle::{ConflictStrategy, ImportSummary},
+    utils::download::download_json,
+};
+use gloo::file::futures::read_as_text;
+use strum::IntoEnumIterator;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[function_component(BundleManager)]
+pub fn bundle_manager() -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let token = session_store.token.clone().unwrap_or_default();
+    let file = use_mut_ref(|| Option::<web_sys::File>::None);
+    let strategy = use_mut_ref(ConflictStrategy::default);
+    let dry_run = use_mut_ref(|| true);
+    let summary = use_state_eq(|| Option::<ImportSummary>::None);
+    let error_state = use_state_eq(|| None);
+    let onexport = async_event!(|api, token, error_state| {
+        match api.export_bundle(&token).await {
+            Ok(bundle) => download_json(
+                &format!(
+                    "petompp-bundle-{}.json",
+                    chrono::Utc::now().format("%Y%m%d%H%M%S")
+                ),
+                bundle.to_string(),
+            ),
+            Err(error) => error_state.set(Some(error)),
+        }
+    });
+    let onchange_file = {
+        let file = file.clone();
+        Callback::from(move |e: Event| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            *file.borrow_mut() = target_element.files().and_then(|f| f.get(0));
+        })
+    };
+    let onchange_strategy = {
+        let strategy = strategy.clone();
+        Callback::from(move |e: Event| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            *strategy.borrow_mut() = ConflictStrategy::iter()
+                .find(|s| s.to_string() == target_element.value())
+                .unwrap_or_default();
+        })
+    };
+    let onchange_dry_run = {
+        let dry_run = dry_run.clone();
+        Callback::from(move |e: Event| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            *dry_run.borrow_mut() = target_element.checked();
+        })
+    };
+    let onimport =
+        async_event!(
+            |api, token, file, strategy, dry_run, summary, error_state, locales_store| {
+                let Some(selected) = file.borrow().clone() else {
+                    return;
+                };
+                let text = match read_as_text(&gloo::file::File::from(selected)).await {
+                    Ok(text) => text,
+                    Err(e) => return show_error(e.to_string(), false),
+                };
+                let bundle = match serde_json::from_str(&text) {
+                    Ok(bundle) => bundle,
+                    Err(e) => return show_error(e.to_string(), false),
+                };
+                let (strategy, dry_run) = (*strategy.borrow(), *dry_run.borrow());
+                match api.import_bundle(&token, &bundle, strategy, dry_run).await {
+                    Ok(result) => summary.set(Some(result)),
+                    Err(RequestError::Endpoint(400 | 413, e)) => {
+                        show_error(e.into_localized(&locales_store), false)
+                    }
+                    Err(error) => error_state.set(Some(error)),
+                }
+            }
+        );
+    handle_api_error!(error_state, session_dispatch, false);
+    let summary = (*summary).as_ref().map(|s| {
+        let prefix = match s.dry_run {
+            true => format!("{}: ", locales_store.get(TK::DryRun)),
+            false => String::new(),
+        };
+        prefix
+            + &locales_store.get(TK::ImportSummary(
+                s.created.len().to_string(),
+                s.updated.len().to_string(),
+                s.skipped.len().to_string(),
+                s.blobs.to_string(),
+            ))
+    });
+    html! {
+        <div class={"flex flex-col gap-2 mt-8"}>
+            <p class={"text-xl font-bold font-mono"}>{locales_store.get(TK::ContentBundle)}</p>
+            <div class={"flex flex-col lg:flex-row gap-2 lg:items-center"}>
+                <button class={"btn btn-sm btn-primary"} onclick={onexport}>{locales_store.get(TK::ExportBundle)}</button>
+                <input type={"file"} accept={"application/json"} class={"file-input file-input-bordered file-input-sm"} onchange={onchange_file} />
+                <label class={"label gap-2"}>
+                    <span class={"label-text"}>{locales_store.get(TK::ConflictStrategy)}</span>
+                    <select class={"select select-bordered select-sm"} onchange={onchange_strategy}>
+                        { for ConflictStrategy::iter().map(|s| html! { <option value={s.to_string()}>{s.to_string()}</option> }) }
+                    </select>
+                </label>
+                <label class={"label gap-2 cursor-pointer"}>
+                    <span class={"label-text"}>{locales_store.get(TK::DryRun)}</span>
+                    <input type={"checkbox"} class={"checkbox checkbox-sm"} checked={true} onchange={onchange_dry_run} />
+                </label>
+                <button class={"btn btn-sm btn-warning"} onclick={onimport}>{locales_store.get(TK::ImportBundle)}</button>
+            </div>
+            if let Some(summary) = summary {
+                <span class={"text-success"}>{summary}</span>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{
+    api::client::use_api,
+    async_event,
+    components::atoms::modal::{show_modal_callback, Buttons, ModalButton, ModalData, ModalStore},
+    data::{
+        locales::{LocalesStore, TK},
+        session::SessionStore,
+    },
+    handle_api_error,
+    models::comment::{ModerationAction, ThreadComment},
+};
+use yew::{platform::spawn_local, prelude::*};
+use yewdux::prelude::*;
+
+#[function_component(CommentModerator)]
+pub fn comment_moderator() -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let token = session_store.token.clone().unwrap_or_default();
+    let error_state = use_state_eq(|| None);
+    let reload = use_state_eq(|| true);
+    let queue = use_state_eq(Vec::new);
+    let mark_to_reload = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(true))
+    };
+    if *reload {
+        reload.set(false);
+        let error_state = error_state.clone();
+        let queue = queue.clone();
+        spawn_local(async move {
+            match api.get_comment_queue(&token).await {
+                Ok(comments) => queue.set(comments),
+                Err(error) => error_state.set(Some(error)),
+            };
+        })
+    }
+    handle_api_error!(error_state, session_dispatch, true);
+    html! {
+        <div class={"flex flex-col gap-2 mt-8"}>
+            <p class={"text-xl font-bold font-mono"}>{locales_store.get(TK::CommentModeration)}</p>
+            if queue.is_empty() {
+                <span class={"opacity-70"}>{locales_store.get(TK::NoPendingComments)}</span>
+            }
+            <ul class={"flex flex-col gap-4"}>
+                {for queue.iter().map(|comment| html!{<QueuedComment comment={comment.clone()} reload={mark_to_reload.clone()} />})}
+            </ul>
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct QueuedCommentProps {
+    pub comment: ThreadComment,
+    pub reload: Callback<()>,
+}
+
+#[function_component(QueuedComment)]
+fn queued_comment(props: &QueuedCommentProps) -> Html {
+    let created_at = props
+        .comment
+        .comment
+        .created_at
+        .map(|c| c.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    html! {
+        <li class={"flex flex-col gap-1 p-4 rounded-lg bg-base-200"}>
+            <div class={"flex flex-row flex-wrap gap-2 text-sm"}>
+                <span class={"font-bold"}>{props.comment.author.to_string()}</span>
+                <span class={"font-mono"}>{&props.comment.comment.key}</span>
+                <span class={"opacity-70"}>{created_at}</span>
+            </div>
+            <div class={"prose max-w-full"}>
+                {Html::from_html_unchecked(props.comment.html.clone().into())}
+            </div>
+            <div class={"flex flex-row"}>
+                <ModerateButton comment={props.comment.clone()} reload={props.reload.clone()} action={ModerationAction::Approve} />
+                <ModerateButton comment={props.comment.clone()} reload={props.reload.clone()} action={ModerationAction::Reject} />
+                <ModerateButton comment={props.comment.clone()} reload={props.reload.clone()} action={ModerationAction::Ban} />
+            </div>
+        </li>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ModerateButtonProps {
+    pub comment: ThreadComment,
+    pub reload: Callback<()>,
+    pub action: ModerationAction,
+}
+
+#[function_component(ModerateButton)]
+fn moderate_button(props: &ModerateButtonProps) -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let (_, dispatch) = use_store::<ModalStore>();
+    let error_state = use_state_eq(|| None);
+    let token = session_store.token.clone().unwrap_or_default();
+    let onclick = async_event!(|api, props, token, error_state| {
+        match api
+            .moderate_comment(
+                &token,
+                props.comment.comment.id.unwrap_or_default(),
+                props.action,
+            )
+            .await
+        {
+            Ok(_) => props.reload.emit(()),
+            Err(error) => error_state.set(Some(error)),
+        }
+    });
+    handle_api_error!(error_state, session_dispatch, true);
+    let (text, class) = match props.action {
+        ModerationAction::Approve => (TK::Approve, "btn btn-sm btn-success px-1 mr-1"),
+        ModerationAction::Reject => (TK::Reject, "btn btn-sm btn-warning px-1 mr-1"),
+        ModerationAction::Ban => (TK::Ban, "btn btn-sm btn-error px-1 mr-1"),
+    };
+    // Banning affects every pending comment of the author, so it asks first.
+    let onclick = match props.action {
+        ModerationAction::Ban => show_modal_callback(
+            ModalData {
+                title: locales_store.get(TK::Ban),
+                message: locales_store.get(TK::BanUserQuestion(props.comment.author.to_string())),
+                buttons: Buttons::RiskyCancel(
+                    ModalButton::new(locales_store.get(TK::Ban), Some(onclick)),
+                    ModalButton::new(locales_store.get(TK::Cancel), None),
+                ),
+            },
+            dispatch,
+        ),
+        _ => onclick,
+    };
+    html! {
+        <button {class} {onclick}>{locales_store.get(text)}</button>
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{
+    api::{
+        client::{use_api, RequestError},
+        error::{validation::Error as ValidationError, ApiError, IntoLocalized},
+    },
+    async_event,
+    components::atoms::modal::{show_modal_callback, Buttons, ModalButton, ModalData, ModalStore},
+    data::{
+        locales::{LocalesStore, TK},
+        session::SessionStore,
+    },
+    handle_api_error,
+    models::{
+        invite::{Invite, InviteRequest},
+        role::Role,
+    },
+    router::Route,
+};
+use chrono::{NaiveDate, NaiveTime};
+use strum::IntoEnumIterator;
+use web_sys::HtmlInputElement;
+use yew::{platform::spawn_local, prelude::*};
+use yew_router::prelude::*;
+use yewdux::prelude::*;
+
+#[function_component(InviteManager)]
+pub fn invite_manager() -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let token = session_store.token.clone().unwrap_or_default();
+    let error_state = use_state_eq(|| None);
+    let reload = use_state_eq(|| true);
+    let invite_data = use_state_eq(Vec::new);
+    let mark_to_reload = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(true))
+    };
+    if *reload {
+        reload.set(false);
+        let error_state = error_state.clone();
+        let invite_data = invite_data.clone();
+        spawn_local(async move {
+            match api.get_invites(&token).await {
+                Ok(invites) => invite_data.set(invites),
+                Err(error) => error_state.set(Some(error)),
+            };
+        })
+    }
+    handle_api_error!(error_state, session_dispatch, true);
+    html! {
+        <>
+        <InviteForm reload={mark_to_reload.clone()} />
+        <table class={"table"}>
+            <thead>
+                <tr>
+                    <th>{locales_store.get(TK::Code)}</th>
+                    <th>{locales_store.get(TK::Role)}</th>
+                    <th>{locales_store.get(TK::Uses)}</th>
+                    <th>{locales_store.get(TK::ExpiresAt)}</th>
+                    <th>{locales_store.get(TK::Actions)}</th>
+                </tr>
+            </thead>
+            <tbody class={"items-center"}>
+                {for invite_data.iter().map(|invite| html!{<InviteRow invite={invite.clone()} reload={mark_to_reload.clone()} />})}
+            </tbody>
+        </table>
+        </>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct InviteFormProps {
+    pub reload: Callback<()>,
+}
+
+#[function_component(InviteForm)]
+fn invite_form(props: &InviteFormProps) -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let token = session_store.token.clone().unwrap_or_default();
+    let form_data = use_mut_ref(InviteRequest::default);
+    let validation_error = use_state_eq(|| Option::<String>::None);
+    let error_state = use_state_eq(|| None);
+    let onchange_max_uses = {
+        let form_data = form_data.clone();
+        Callback::from(move |e: InputEvent| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            form_data.borrow_mut().max_uses = target_element.value().parse().unwrap_or_default();
+        })
+    };
+    let onchange_expires_at = {
+        let form_data = form_data.clone();
+        Callback::from(move |e: InputEvent| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            form_data.borrow_mut().expires_at =
+                NaiveDate::parse_from_str(&target_element.value(), "%Y-%m-%d")
+                    .ok()
+                    .map(|d| d.and_time(NaiveTime::MIN));
+        })
+    };
+    let onchange_role = {
+        let form_data = form_data.clone();
+        Callback::from(move |e: Event| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            form_data.borrow_mut().role = Role::iter()
+                .find(|r| r.to_string() == target_element.value())
+                .unwrap_or(Role::User);
+        })
+    };
+    let onsubmit = async_event!(
+        [prevent SubmitEvent] |api, form_data, token, props, validation_error, error_state, locales_store| {
+            let request = form_data.borrow().clone();
+            match api.create_invite(&token, &request).await {
+                Ok(_) => {
+                    validation_error.set(None);
+                    props.reload.emit(());
+                }
+                Err(RequestError::Endpoint(_, error @ ApiError::ValidationError(ValidationError::Invite(_)))) => {
+                    validation_error.set(Some(error.into_localized(&locales_store)))
+                }
+                Err(error) => error_state.set(Some(error)),
+            }
+        }
+    );
+    handle_api_error!(error_state, session_dispatch, false);
+    html! {
+        <form class={"flex flex-col lg:flex-row gap-2 lg:items-end mb-4"} {onsubmit}>
+            <div class={"form-control"}>
+                <label class={"label"}>
+                    <span class={"label-text"}>{locales_store.get(TK::MaxUses)}</span>
+                </label>
+                <input class={"input input-bordered input-sm shadow-md"} type={"number"} min={"1"} value={"1"} oninput={onchange_max_uses} />
+            </div>
+            <div class={"form-control"}>
+                <label class={"label"}>
+                    <span class={"label-text"}>{locales_store.get(TK::ExpiresAt)}</span>
+                </label>
+                <input class={"input input-bordered input-sm shadow-md"} type={"date"} oninput={onchange_expires_at} />
+            </div>
+            <div class={"form-control"}>
+                <label class={"label"}>
+                    <span class={"label-text"}>{locales_store.get(TK::Role)}</span>
+                </label>
+                <select class={"select select-bordered select-sm shadow-md"} onchange={onchange_role}>
+                    { for Role::iter().map(|r| html! { <option value={r.to_string()}>{r.to_string()}</option> }) }
+                </select>
+            </div>
+            <button class={"btn btn-sm btn-primary"}>{locales_store.get(TK::CreateInvite)}</button>
+            <span class={"text-error"}>{(*validation_error).clone().unwrap_or_default()}</span>
+        </form>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct InviteRowProps {
+    pub invite: Invite,
+    pub reload: Callback<()>,
+}
+
+#[function_component(InviteRow)]
+fn invite_row(props: &InviteRowProps) -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let expires_at = props
+        .invite
+        .expires_at
+        .map(|e| e.format("%Y-%m-%d").to_string())
+        .unwrap_or(locales_store.get(TK::Never));
+    html! {
+        <tr>
+            <td class={"font-mono break-all"}>{&props.invite.code}</td>
+            <td>{props.invite.role.to_string()}</td>
+            <td>{format!("{}/{}", props.invite.uses, props.invite.max_uses)}</td>
+            <td>{expires_at}</td>
+            <td>
+                <div class="flex flex-row">
+                    <CopyLinkButton invite={props.invite.clone()} reload={props.reload.clone()} />
+                    <DeleteInviteButton invite={props.invite.clone()} reload={props.reload.clone()} />
+                </div>
+            </td>
+        </tr>
+    }
+}
+
+#[function_component(CopyLinkButton)]
+fn copy_link_button(props: &InviteRowProps) -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let code = props.invite.code.clone();
+    let onclick = Callback::from(move |_| {
+        let window = web_sys::window().unwrap();
+        let link = format!(
+            "{}{}?invite={}",
+            window.location().origin().unwrap_or_default(),
+            Route::Register.to_path(),
+            code
+        );
+        if let Some(clipboard) = window.navigator().clipboard() {
+            let _ = clipboard.write_text(&link);
+        }
+    });
+    html! {
+        <button class={"btn btn-sm btn-accent px-1 mr-1"} {onclick}>{locales_store.get(TK::CopyLink)}</button>
+    }
+}
+
+#[function_component(DeleteInviteButton)]
+fn delete_invite_button(props: &InviteRowProps) -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let (_, dispatch) = use_store::<ModalStore>();
+    let error_state = use_state_eq(|| None);
+    let token = session_store.token.clone().unwrap_or_default();
+    let onclick = async_event!(|api, props, token, error_state| {
+        match api
+            .delete_invite(&token, props.invite.id.unwrap_or_default())
+            .await
+        {
+            Ok(()) => props.reload.emit(()),
+            Err(error) => error_state.set(Some(error)),
+        }
+    });
+    handle_api_error!(error_state, session_dispatch, true);
+    let onclick = show_modal_callback(
+        ModalData {
+            title: locales_store.get(TK::Delete),
+            message: locales_store.get(TK::DeleteInviteQuestion(props.invite.code.clone())),
+            buttons: Buttons::RiskyCancel(
+                ModalButton::new(locales_store.get(TK::Delete), Some(onclick)),
+                ModalButton::new(locales_store.get(TK::Cancel), None),
+            ),
+        },
+        dispatch,
+    );
+    html! {
+        <button class={"btn btn-sm btn-warning px-1 mr-1"} {onclick}>{locales_store.get(TK::Delete)}</button>
+    }
+}
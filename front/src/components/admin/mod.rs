@@ -1 +1,4 @@
+pub mod bundle_manager;
+pub mod comment_moderator;
+pub mod invite_manager;
 pub mod user_manager;
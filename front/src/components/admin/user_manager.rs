@@ -1,5 +1,5 @@
 use crate::{
-    api::client::Client,
+    api::client::use_api,
     async_event,
     components::atoms::modal::{show_modal_callback, Buttons, ModalButton, ModalData, ModalStore},
     data::{
@@ -14,6 +14,7 @@ use yewdux::prelude::*;
 
 #[function_component(UserManager)]
 pub fn user_manager() -> Html {
+    let api = use_api();
     let (locales_store, _) = use_store::<LocalesStore>();
     let (session_store, session_dispatch) = use_store::<SessionStore>();
     let token = session_store.token.clone().unwrap_or_default();
@@ -29,7 +30,7 @@ pub fn user_manager() -> Html {
         let error_state = error_state.clone();
         let user_data = user_data.clone();
         spawn_local(async move {
-            match Client::get_users(&token).await {
+            match api.get_users(&token).await {
                 Ok(users) => user_data.set(users),
                 Err(error) => error_state.set(Some(error)),
             };
@@ -62,8 +63,8 @@ pub struct UserRowProps {
 fn user_row(props: &UserRowProps) -> Html {
     html! {
         <tr>
-            <td>{&props.user.id}</td>
-            <td class={"break-all"}>{&props.user.name}</td>
+            <td>{props.user.id.unwrap_or_default()}</td>
+            <td class={"break-all"}>{props.user.name.to_string()}</td>
             <td>
                 <div class="flex flex-row">
                     <ActivateButton user={props.user.clone()} reload={props.reload.clone()} />
@@ -76,13 +77,17 @@ fn user_row(props: &UserRowProps) -> Html {
 
 #[function_component(ActivateButton)]
 fn activate_button(props: &UserRowProps) -> Html {
+    let api = use_api();
     let (locales_store, _) = use_store::<LocalesStore>();
     let (session_store, session_dispatch) = use_store::<SessionStore>();
     let (_, dispatch) = use_store::<ModalStore>();
     let error_state = use_state_eq(|| None);
     let token = session_store.token.clone().unwrap_or_default();
-    let onclick = async_event!(|props, token, error_state| {
-        match Client::activate_user(&token, props.user.id).await {
+    let onclick = async_event!(|api, props, token, error_state| {
+        match api
+            .activate_user(&token, props.user.id.unwrap_or_default())
+            .await
+        {
             Ok(()) => props.reload.emit(()),
             Err(error) => error_state.set(Some(error)),
         }
@@ -97,7 +102,8 @@ fn activate_button(props: &UserRowProps) -> Html {
             Some(show_modal_callback(
                 ModalData {
                     title: locales_store.get(TK::Activate),
-                    message: locales_store.get(TK::ActivateUserQuestion(props.user.name.clone())),
+                    message: locales_store
+                        .get(TK::ActivateUserQuestion(props.user.name.to_string())),
                     buttons: Buttons::ConfirmCancel(
                         ModalButton::new(locales_store.get(TK::Activate), Some(onclick)),
                         ModalButton::new(locales_store.get(TK::Cancel), None),
@@ -115,13 +121,17 @@ fn activate_button(props: &UserRowProps) -> Html {
 
 #[function_component(DeleteButton)]
 fn delete_button(props: &UserRowProps) -> Html {
+    let api = use_api();
     let (locales_store, _) = use_store::<LocalesStore>();
     let (session_store, session_dispatch) = use_store::<SessionStore>();
     let (_, dispatch) = use_store::<ModalStore>();
     let error_state = use_state_eq(|| None);
     let token = session_store.token.clone().unwrap_or_default();
-    let onclick = async_event!(|props, token, error_state| {
-        match Client::delete_user(&token, props.user.id).await {
+    let onclick = async_event!(|api, props, token, error_state| {
+        match api
+            .delete_user(&token, props.user.id.unwrap_or_default())
+            .await
+        {
             Ok(()) => props.reload.emit(()),
             Err(error) => error_state.set(Some(error)),
         }
@@ -136,7 +146,7 @@ fn delete_button(props: &UserRowProps) -> Html {
             Some(show_modal_callback(
                 ModalData {
                     title: locales_store.get(TK::Delete),
-                    message: locales_store.get(TK::DeleteUserQuestion(props.user.name.clone())),
+                    message: locales_store.get(TK::DeleteUserQuestion(props.user.name.to_string())),
                     buttons: Buttons::RiskyCancel(
                         ModalButton::new(locales_store.get(TK::Delete), Some(onclick)),
                         ModalButton::new(locales_store.get(TK::Cancel), None),
@@ -25,6 +25,10 @@ impl Country {
     }
 
     pub fn get_current() -> Self {
+        // There is no navigator on the server.
+        if cfg!(feature = "ssr") {
+            return Self::default();
+        }
         for lang in web_sys::window().unwrap().navigator().languages().to_vec() {
             let lang = lang.as_string().unwrap().to_lowercase();
             if lang.len() < 2 {
@@ -36,6 +40,15 @@ impl Country {
         }
         Self::default()
     }
+
+    /// Language asked for with the `lang` query parameter, the links of the sitemap carry it.
+    pub fn from_query() -> Option<Self> {
+        let search = web_sys::window()?.location().search().ok()?;
+        let lang = web_sys::UrlSearchParams::new_with_str(&search)
+            .ok()?
+            .get("lang")?;
+        Self::try_from(lang.as_str()).ok()
+    }
 }
 
 impl<'a> TryFrom<&'a str> for Country {
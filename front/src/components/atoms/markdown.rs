@@ -1,7 +1,4 @@
-use crate::{
-    router::{AdminRoute, Route},
-    use_effect_deps,
-};
+use crate::router::{AdminRoute, Route};
 use wasm_bindgen::{prelude::Closure, JsCast};
 use web_sys::Element;
 use yew::prelude::*;
@@ -19,6 +16,7 @@ pub struct MarkdownDisplayProps {
 #[function_component(Markdown)]
 pub fn markdown_display(props: &MarkdownDisplayProps) -> Html {
     let navigator = use_navigator().unwrap();
+    let node_ref = use_node_ref();
     let html = markdown::to_html_with_options(
         props.markdown.as_str(),
         &markdown::Options {
@@ -30,34 +28,45 @@ pub fn markdown_display(props: &MarkdownDisplayProps) -> Html {
         },
     )
     .unwrap();
-    let div = web_sys::window()
-        .unwrap()
-        .document()
-        .unwrap()
-        .create_element("div")
-        .unwrap();
-    div.set_inner_html(&html);
     let interactive = props.interactive;
     let class = match interactive {
         Some(()) => "prose w-full max-w-full",
         None => "prose w-full max-w-full pointer-events-none",
     };
-    use_effect_deps!(|interactive| {
-        if interactive.is_some() {
-            make_links_clickable(navigator.clone());
-        }
-        || {}
-    });
+    {
+        let node_ref = node_ref.clone();
+        use_effect_with_deps(
+            move |(html, interactive)| {
+                if let Some(element) = node_ref.cast::<Element>() {
+                    element.set_inner_html(html);
+                    if interactive.is_some() {
+                        make_links_clickable(&element, navigator);
+                    }
+                }
+                || {}
+            },
+            (html.clone(), interactive),
+        );
+    }
 
+    // Raw HTML can't be hydrated, on the client it's set after rendering.
     html! {
-        <div {class} id={ID}>
-            {Html::VRef(div.into())}
+        <div {class} id={ID} ref={node_ref}>
+            if cfg!(feature = "ssr") {
+                {Html::from_html_unchecked(html.into())}
+            }
         </div>
     }
 }
 
-fn make_links_clickable(navigator: Navigator) {
-    let element = get_display_element();
+/// Empties the display rendered ahead of time, so it can be hydrated.
+pub fn clear_prerendered() {
+    if let Some(element) = gloo::utils::document().get_element_by_id(ID) {
+        element.set_inner_html("");
+    }
+}
+
+fn make_links_clickable(element: &Element, navigator: Navigator) {
     let links = element.query_selector_all("a").unwrap();
     for i in 0..links.length() {
         let link: Element = links.get(i).unwrap().unchecked_into();
@@ -99,14 +108,3 @@ fn get_route_onclick<T: Routable + std::fmt::Debug + 'static>(
         navigator.push(&route);
     }))
 }
-
-fn get_display_element() -> Element {
-    let element: Element = web_sys::window()
-        .unwrap()
-        .document()
-        .unwrap()
-        .get_element_by_id(ID)
-        .unwrap()
-        .unchecked_into();
-    element
-}
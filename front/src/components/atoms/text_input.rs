@@ -6,6 +6,7 @@ pub struct TextInputProps {
     pub itype: String,
     pub placeholder: Option<String>,
     pub autocomplete: Option<String>,
+    pub value: Option<String>,
     pub onchange: Option<Callback<InputEvent>>,
     pub error: Option<String>,
 }
@@ -25,7 +26,7 @@ pub fn text_input(props: &TextInputProps) -> Html {
         <label class={"label"}>
             <span class={span_class}>{&props.label}</span>
         </label>
-        <input class={input_class} type={props.itype.clone()} placeholder={props.placeholder.clone()} autocomplete={props.autocomplete.clone()} oninput={props.onchange.clone()} />
+        <input class={input_class} type={props.itype.clone()} placeholder={props.placeholder.clone()} autocomplete={props.autocomplete.clone()} value={props.value.clone()} oninput={props.onchange.clone()} />
         <span class={"text-error mt-1"}>{if let Some(e) = &props.error {e.clone() } else { "".to_string() }}</span>
         </>
     }
@@ -0,0 +1,297 @@
+use crate::{
+    api::{
+        client::{use_api, RequestError},
+        error::{validation::Error as ValidationError, ApiError, IntoLocalized},
+    },
+    async_event,
+    components::atoms::modal::{show_modal_callback, Buttons, ModalButton, ModalData, ModalStore},
+    data::{
+        locales::{LocalesStore, TK},
+        session::SessionStore,
+    },
+    handle_api_error,
+    models::{
+        comment::{Comment, CommentRequest, CommentStatus, ThreadComment},
+        role::Role,
+    },
+};
+use web_sys::HtmlTextAreaElement;
+use yew::{platform::spawn_local, prelude::*};
+use yewdux::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct CommentThreadProps {
+    pub reskey: String,
+}
+
+/// Discussion under the page, loaded in an effect so the prerendered pages come without it.
+#[function_component(CommentThread)]
+pub fn comment_thread(props: &CommentThreadProps) -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, _) = use_store::<SessionStore>();
+    let comments = use_state_eq(|| Option::<Vec<ThreadComment>>::None);
+    let reload = use_state(|| 0);
+    let mark_to_reload = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+    {
+        let comments = comments.clone();
+        use_effect_with_deps(
+            move |(reskey, token, _)| {
+                let (reskey, token) = (reskey.clone(), token.clone());
+                spawn_local(async move {
+                    if let Ok(thread) = api.get_comments(token.as_deref(), &reskey).await {
+                        comments.set(Some(thread));
+                    }
+                });
+                || {}
+            },
+            (props.reskey.clone(), session_store.token.clone(), *reload),
+        );
+    }
+    let Some(comments) = (*comments).clone() else {
+        return html! {};
+    };
+    html! {
+        <div class={"flex flex-col gap-4 mt-10 pt-6 border-t border-base-300"}>
+            <h2 class={"text-2xl font-bold"}>{locales_store.get(TK::Comments)}</h2>
+            if session_store.user.is_some() {
+                <CommentForm reskey={props.reskey.clone()} done={mark_to_reload.clone()} />
+            } else {
+                <p class={"opacity-70"}>{locales_store.get(TK::LoginToComment)}</p>
+            }
+            if comments.is_empty() {
+                <p class={"opacity-70"}>{locales_store.get(TK::NoComments)}</p>
+            }
+            <CommentList reskey={props.reskey.clone()} {comments} parent={None} reload={mark_to_reload} />
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct CommentListProps {
+    pub reskey: String,
+    /// Whole thread, the list shows the replies to the `parent`.
+    pub comments: Vec<ThreadComment>,
+    pub parent: Option<i32>,
+    pub reload: Callback<()>,
+}
+
+#[function_component(CommentList)]
+fn comment_list(props: &CommentListProps) -> Html {
+    html! {
+        <ul class={"flex flex-col gap-3"}>
+            { for ThreadComment::replies(&props.comments, props.parent).into_iter().map(|comment| html! {
+                <li>
+                    <CommentItem reskey={props.reskey.clone()} comment={comment.clone()}
+                        comments={props.comments.clone()} reload={props.reload.clone()} />
+                </li>
+            }) }
+        </ul>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct CommentItemProps {
+    pub reskey: String,
+    pub comment: ThreadComment,
+    pub comments: Vec<ThreadComment>,
+    pub reload: Callback<()>,
+}
+
+#[function_component(CommentItem)]
+fn comment_item(props: &CommentItemProps) -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, _) = use_store::<SessionStore>();
+    let replying = use_state_eq(|| false);
+    let editing = use_state_eq(|| false);
+    let comment = &props.comment.comment;
+    let (is_author, is_admin) = match &session_store.user {
+        Some(user) => (user.id == Some(comment.user_id), user.role == Role::Admin),
+        None => (false, false),
+    };
+    let can_reply = session_store.user.is_some()
+        && comment.status == CommentStatus::Approved
+        && !comment.is_deleted();
+    let can_edit = is_author && comment.is_editable(chrono::Utc::now().naive_utc());
+    let can_delete = (is_author || is_admin) && !comment.is_deleted();
+    let has_replies = !ThreadComment::replies(&props.comments, comment.id).is_empty();
+    let toggle = |state: UseStateHandle<bool>| Callback::from(move |_| state.set(!*state));
+    let done = |state: UseStateHandle<bool>| {
+        let reload = props.reload.clone();
+        Callback::from(move |_| {
+            state.set(false);
+            reload.emit(());
+        })
+    };
+    let created_at = comment
+        .created_at
+        .map(|c| c.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    html! {
+        <div class={"flex flex-col gap-1"}>
+            <div class={"flex flex-row flex-wrap items-center gap-2 text-sm"}>
+                <span class={"font-bold"}>{props.comment.author.to_string()}</span>
+                <span class={"opacity-70"}>{created_at}</span>
+                if comment.edited_at.is_some() && !comment.is_deleted() {
+                    <span class={"opacity-70 italic"}>{locales_store.get(TK::Edited)}</span>
+                }
+                if comment.status == CommentStatus::Pending {
+                    <span class={"badge badge-warning badge-sm"}>{locales_store.get(TK::AwaitingModeration)}</span>
+                }
+            </div>
+            if comment.is_deleted() {
+                <p class={"italic opacity-70"}>{locales_store.get(TK::CommentDeleted)}</p>
+            } else if *editing {
+                <CommentForm reskey={props.reskey.clone()} edit={props.comment.comment.clone()}
+                    done={done(editing.clone())} />
+            } else {
+                <div class={"prose max-w-full"}>
+                    {Html::from_html_unchecked(props.comment.html.clone().into())}
+                </div>
+            }
+            <div class={"flex flex-row gap-1"}>
+                if can_reply {
+                    <button class={"btn btn-xs btn-ghost"} onclick={toggle(replying.clone())}>{locales_store.get(TK::Reply)}</button>
+                }
+                if can_edit {
+                    <button class={"btn btn-xs btn-ghost"} onclick={toggle(editing.clone())}>{locales_store.get(TK::Edit)}</button>
+                }
+                if can_delete {
+                    <DeleteCommentButton comment={props.comment.clone()} reload={props.reload.clone()} />
+                }
+            </div>
+            if *replying {
+                <CommentForm reskey={props.reskey.clone()} parent_id={comment.id} done={done(replying.clone())} />
+            }
+            if has_replies {
+                <div class={"ml-4 pl-4 border-l border-base-300"}>
+                    <CommentList reskey={props.reskey.clone()} comments={props.comments.clone()}
+                        parent={comment.id} reload={props.reload.clone()} />
+                </div>
+            }
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct CommentFormProps {
+    pub reskey: String,
+    #[prop_or_default]
+    pub parent_id: Option<i32>,
+    /// Comment changed by the form instead of posting a new one.
+    #[prop_or_default]
+    pub edit: Option<Comment>,
+    pub done: Callback<()>,
+}
+
+#[function_component(CommentForm)]
+fn comment_form(props: &CommentFormProps) -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let token = session_store.token.clone().unwrap_or_default();
+    let body = use_state_eq(|| {
+        props
+            .edit
+            .as_ref()
+            .map(|c| c.body.clone())
+            .unwrap_or_default()
+    });
+    let comment_error = use_state_eq(|| Option::<String>::None);
+    let error_state = use_state_eq(|| None);
+    let oninput = {
+        let body = body.clone();
+        Callback::from(move |e: InputEvent| {
+            let target_element = e.target_unchecked_into::<HtmlTextAreaElement>();
+            body.set(target_element.value());
+        })
+    };
+    let onsubmit = async_event!(
+        [prevent SubmitEvent] |api, token, props, body, comment_error, error_state, locales_store| {
+            let request = CommentRequest {
+                body: (*body).clone(),
+                parent_id: props.parent_id,
+            };
+            let result = match &props.edit {
+                Some(comment) => {
+                    api.update_comment(&token, comment.id.unwrap_or_default(), &request)
+                        .await
+                }
+                None => api.create_comment(&token, &props.reskey, &request).await,
+            };
+            match result {
+                Ok(_) => {
+                    body.set(String::new());
+                    comment_error.set(None);
+                    props.done.emit(());
+                }
+                // Refusals are shown next to the form, not handled like an expired session.
+                Err(RequestError::Endpoint(
+                    _,
+                    error @ (ApiError::ValidationError(ValidationError::Comment(_))
+                    | ApiError::CommentingBanned
+                    | ApiError::CommentEditWindowClosed(_)),
+                )) => comment_error.set(Some(error.into_localized(&locales_store))),
+                Err(error) => error_state.set(Some(error)),
+            }
+        }
+    );
+    handle_api_error!(error_state, session_dispatch, false);
+    let submit_text = match props.edit {
+        Some(_) => locales_store.get(TK::Save),
+        None => locales_store.get(TK::PostComment),
+    };
+    html! {
+        <form class={"form-control w-full gap-2"} {onsubmit}>
+            <textarea class={"textarea textarea-bordered shadow-md h-24"} placeholder={locales_store.get(TK::WriteComment)}
+                value={(*body).clone()} {oninput} />
+            <div class={"flex flex-row justify-end items-center gap-2"}>
+                <span class={"text-error"}>{(*comment_error).clone().unwrap_or_default()}</span>
+                <button class={"btn btn-sm btn-primary"}>{submit_text}</button>
+            </div>
+        </form>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct DeleteCommentButtonProps {
+    pub comment: ThreadComment,
+    pub reload: Callback<()>,
+}
+
+#[function_component(DeleteCommentButton)]
+fn delete_comment_button(props: &DeleteCommentButtonProps) -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let (_, dispatch) = use_store::<ModalStore>();
+    let error_state = use_state_eq(|| None);
+    let token = session_store.token.clone().unwrap_or_default();
+    let onclick = async_event!(|api, props, token, error_state| {
+        match api
+            .delete_comment(&token, props.comment.comment.id.unwrap_or_default())
+            .await
+        {
+            Ok(_) => props.reload.emit(()),
+            Err(error) => error_state.set(Some(error)),
+        }
+    });
+    handle_api_error!(error_state, session_dispatch, false);
+    let onclick = show_modal_callback(
+        ModalData {
+            title: locales_store.get(TK::Delete),
+            message: locales_store.get(TK::DeleteCommentQuestion),
+            buttons: Buttons::RiskyCancel(
+                ModalButton::new(locales_store.get(TK::Delete), Some(onclick)),
+                ModalButton::new(locales_store.get(TK::Cancel), None),
+            ),
+        },
+        dispatch,
+    );
+    html! {
+        <button class={"btn btn-xs btn-ghost text-warning"} {onclick}>{locales_store.get(TK::Delete)}</button>
+    }
+}
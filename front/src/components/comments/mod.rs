@@ -0,0 +1 @@
+pub mod comment_thread;
@@ -1,5 +1,5 @@
 use crate::{
-    api::client::Client,
+    api::client::use_api,
     async_event,
     components::atoms::{
         flag::{Country, FlagSelect},
@@ -27,6 +27,7 @@ pub struct InnerControlProps {
 
 #[function_component(Control)]
 pub fn control(props: &InnerControlProps) -> Html {
+    let api = use_api();
     let error_state = use_state_eq(|| None);
     let (locales_store, _) = use_store::<LocalesStore>();
     let (session_store, session_dispatch) = use_store::<SessionStore>();
@@ -37,14 +38,15 @@ pub fn control(props: &InnerControlProps) -> Html {
     let state = props.state.clone();
     let token = session_store.token.clone().unwrap_or_default();
     let save_available = res_store.get_state(&props.reskey) != Some(&state);
-    let save = async_event!(|state, token, props, error_state, dispatch| {
-        match Client::update_resource(
-            token.as_str(),
-            props.reskey.reskey.as_str(),
-            props.reskey.lang.as_str(),
-            state.as_str(),
-        )
-        .await
+    let save = async_event!(|api, state, token, props, error_state, dispatch| {
+        match api
+            .update_resource(
+                token.as_str(),
+                props.reskey.reskey.as_str(),
+                props.reskey.lang.as_str(),
+                state.as_str(),
+            )
+            .await
         {
             Ok(_) => dispatch.reduce_mut(|s| s.remove_state(&props.reskey)),
             Err(e) => error_state.set(Some(e)),
@@ -153,6 +155,7 @@ pub struct KeySelectProps {
 
 #[function_component(KeySelect)]
 pub fn key_select(props: &KeySelectProps) -> Html {
+    let api = use_api();
     let error_state = use_state_eq(|| None);
     let (session_store, session_dispatch) = use_store::<SessionStore>();
     let (_, modal_dispatch) = use_store::<ModalStore>();
@@ -160,9 +163,9 @@ pub fn key_select(props: &KeySelectProps) -> Html {
     let navigator = use_navigator().unwrap();
     let keys = use_state_eq(|| vec![props.reskey.reskey.clone()]);
     let token = session_store.token.clone().unwrap_or_default();
-    use_effect_deps!(|keys, error_state, token| {
+    use_effect_deps!(|api, keys, error_state, token| {
         spawn_local(async move {
-            match Client::get_resource_keys(&token).await {
+            match api.get_resource_keys(&token).await {
                 Ok(k) => keys.set(k),
                 Err(e) => error_state.set(Some(e)),
             }
@@ -1,5 +1,5 @@
 use crate::{
-    api::client::{Client, RequestError},
+    api::client::{use_api, RequestError},
     components::atoms::markdown::Markdown,
     data::{editor::EditorStore, resources::Key, session::SessionStore},
     handle_api_error,
@@ -22,6 +22,7 @@ pub struct InnerEditProps {
 
 #[function_component(Editor)]
 pub fn editor(props: &InnerEditProps) -> Html {
+    let api = use_api();
     const UPLOAD_FOLDER: &str = "editor";
     let props = props.clone();
     let error_state = use_state_eq(|| None);
@@ -67,6 +68,7 @@ pub fn editor(props: &InnerEditProps) -> Html {
         let session_store = session_store.clone();
         let error_state = error_state.clone();
         Callback::from(move |e: Event| {
+            let api = api.clone();
             let last_state = last_state.clone();
             let last_mod_state = last_mod_state.clone();
             let props = props.clone();
@@ -86,12 +88,13 @@ pub fn editor(props: &InnerEditProps) -> Html {
                 return;
             };
             spawn_local(async move {
-                match Client::upload_img(
-                    session_store.token.as_deref().unwrap_or_default(),
-                    file,
-                    UPLOAD_FOLDER,
-                )
-                .await
+                match api
+                    .upload_img(
+                        session_store.token.as_deref().unwrap_or_default(),
+                        file,
+                        UPLOAD_FOLDER,
+                    )
+                    .await
                 {
                     Ok(url) => {
                         let element: HtmlInputElement = e.target_unchecked_into();
@@ -1,6 +1,6 @@
 use super::atoms::{control::Control, editor::Editor as EditorInner};
 use crate::{
-    api::client::Client,
+    api::client::{use_api, Revalidated},
     components::editor::atoms::editor::save_editor_state,
     data::{
         editor::EditorStore,
@@ -26,6 +26,7 @@ pub struct InnerProps {
 
 #[function_component(Editor)]
 pub fn editor(props: &EditorProps) -> Html {
+    let api = use_api();
     let (_, session_dispatch) = use_store::<SessionStore>();
     let (store, dispatch) = use_store::<EditorStore>();
     let (res_store, res_dispatch) = use_store::<ResourceStore>();
@@ -34,7 +35,7 @@ pub fn editor(props: &EditorProps) -> Html {
     let preview = use_state_eq(|| false);
     let state = use_state_eq(String::new);
     let reskey = props.reskey.clone();
-    use_effect_deps!(|state, reskey, store, error_state| {
+    use_effect_deps!(|api, state, reskey, store, error_state| {
         match store.get_state(&reskey) {
             Some(s) => state.set(s.clone()),
             None => match res_store.get_state(&reskey) {
@@ -43,14 +44,23 @@ pub fn editor(props: &EditorProps) -> Html {
             },
         }
         spawn_local(async move {
-            match Client::get_resource(reskey.reskey.as_str(), reskey.lang.as_str()).await {
-                Ok(val) => {
-                    if res_store.get_state(&reskey) != Some(&val) {
+            let etag = res_store
+                .get_state(&reskey)
+                .and(res_store.get_etag(&reskey));
+            match api
+                .get_resource(reskey.reskey.as_str(), reskey.lang.as_str(), etag)
+                .await
+            {
+                Ok(Revalidated::Modified { value, etag }) => {
+                    if res_store.get_state(&reskey) != Some(&value)
+                        || res_store.get_etag(&reskey) != etag.as_deref()
+                    {
                         res_dispatch.reduce_mut(|store| {
-                            store.add_or_update_state(&reskey, val);
+                            store.add_or_update_fetched(&reskey, value, etag);
                         });
                     }
                 }
+                Ok(Revalidated::NotModified) => {}
                 Err(e) => error_state.set(Some(e)),
             }
         });
@@ -0,0 +1,143 @@
+use crate::{
+    api::{
+        client::{use_api, RequestError},
+        error::{
+            validation::{Error as ValidationError, PageMetaError},
+            ApiError, IntoLocalized,
+        },
+    },
+    async_event,
+    components::atoms::text_input::TextInput,
+    data::{
+        locales::{LocalesStore, TK},
+        resources::Key,
+        session::SessionStore,
+    },
+    handle_api_error,
+    models::page_meta::PageMeta,
+};
+use web_sys::HtmlInputElement;
+use yew::{platform::spawn_local, prelude::*};
+use yewdux::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct MetaEditorProps {
+    pub reskey: Key,
+}
+
+/// Form of the [`PageMeta`] of the edited page, in the language of the content.
+#[function_component(MetaEditor)]
+pub fn meta_editor(props: &MetaEditorProps) -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let token = session_store.token.clone().unwrap_or_default();
+    let meta = use_state_eq(|| PageMeta::new(&props.reskey.reskey, &props.reskey.lang));
+    let validation_error = use_state_eq(|| Option::<PageMetaError>::None);
+    let saved = use_state_eq(|| false);
+    let error_state = use_state_eq(|| None);
+    {
+        let api = api.clone();
+        let meta = meta.clone();
+        let error_state = error_state.clone();
+        use_effect_with_deps(
+            move |reskey| {
+                let reskey = reskey.clone();
+                spawn_local(async move {
+                    match api
+                        .get_page_meta(reskey.reskey.as_str(), reskey.lang.as_str())
+                        .await
+                    {
+                        Ok(m) => meta.set(m),
+                        Err(e) => error_state.set(Some(e)),
+                    }
+                });
+                || {}
+            },
+            props.reskey.clone(),
+        );
+    }
+
+    let get_oninput = |set: fn(&mut PageMeta, String)| {
+        let meta = meta.clone();
+        let saved = saved.clone();
+        Callback::from(move |e: InputEvent| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            let mut new_meta = (*meta).clone();
+            set(&mut new_meta, target_element.value());
+            meta.set(new_meta);
+            saved.set(false);
+        })
+    };
+    let onsubmit = async_event!(
+        [prevent SubmitEvent] |api, meta, token, validation_error, saved, error_state| {
+            match api.update_page_meta(&token, &*meta).await {
+                Ok(m) => {
+                    validation_error.set(None);
+                    meta.set(m);
+                    saved.set(true);
+                }
+                Err(RequestError::Endpoint(_, ApiError::ValidationError(ValidationError::PageMeta(e)))) => {
+                    validation_error.set(Some(e))
+                }
+                Err(error) => error_state.set(Some(error)),
+            }
+        }
+    );
+    handle_api_error!(error_state, session_dispatch, false);
+    let error_of = |is_field: fn(&PageMetaError, &PageMeta) -> bool| match &*validation_error {
+        Some(e) if is_field(e, &meta) => Some(
+            ApiError::ValidationError(ValidationError::PageMeta(e.clone()))
+                .into_localized(&locales_store),
+        ),
+        _ => None,
+    };
+    let title_error = error_of(|e, _| matches!(e, PageMetaError::TitleTooLong(_)));
+    let description_error = error_of(|e, _| matches!(e, PageMetaError::DescriptionTooLong(_)));
+    fn is_too_long(url: &Option<String>) -> bool {
+        url.as_ref()
+            .is_some_and(|url| url.chars().count() > PageMeta::MAX_URL_LENGTH)
+    }
+    let canonical_error = error_of(|e, m| match e {
+        PageMetaError::InvalidUrl(url) => m.canonical.as_ref() == Some(url),
+        PageMetaError::UrlTooLong(_) => is_too_long(&m.canonical),
+        _ => false,
+    });
+    let image_error = error_of(|e, m| match e {
+        PageMetaError::InvalidUrl(url) => m.image.as_ref() == Some(url),
+        PageMetaError::UrlTooLong(_) => is_too_long(&m.image),
+        _ => false,
+    });
+    let lang_error = error_of(|e, _| matches!(e, PageMetaError::LangInvalid(_)));
+    html! {
+        <form class={"form-control w-full mt-4"} {onsubmit}>
+            <h2 class={"text-xl font-bold"}>{locales_store.get(TK::PageMeta)}</h2>
+            <TextInput
+                label={locales_store.get(TK::MetaTitle)} itype={"text".to_string()}
+                placeholder={locales_store.get(TK::TypeMetaTitle)}
+                value={meta.title.clone().unwrap_or_default()} error={title_error}
+                onchange={get_oninput(|m, v| m.title = Some(v))} />
+            <label class={"label"}>
+                <span class={"label-text lg:text-lg"}>{locales_store.get(TK::MetaDescription)}</span>
+            </label>
+            <textarea class={"textarea textarea-bordered shadow-md h-24"} placeholder={locales_store.get(TK::TypeMetaDescription)}
+                value={meta.description.clone().unwrap_or_default()} oninput={get_oninput(|m, v| m.description = Some(v))} />
+            <span class={"text-error mt-1"}>{description_error.unwrap_or_default()}</span>
+            <TextInput
+                label={locales_store.get(TK::CanonicalUrl)} itype={"url".to_string()}
+                value={meta.canonical.clone().unwrap_or_default()} error={canonical_error}
+                onchange={get_oninput(|m, v| m.canonical = Some(v))} />
+            <TextInput
+                label={locales_store.get(TK::PreviewImage)} itype={"url".to_string()}
+                value={meta.image.clone().unwrap_or_default()} error={image_error}
+                onchange={get_oninput(|m, v| m.image = Some(v))} />
+            <span class={"text-error mt-1"}>{lang_error.unwrap_or_default()}</span>
+            <div class={"flex flex-row justify-end items-center gap-2 mt-4"}>
+                if *saved {
+                    <span class={"text-success"}>{locales_store.get(TK::MetaSaved)}</span>
+                }
+                <button class={"btn btn-primary"}>{locales_store.get(TK::Save)}</button>
+            </div>
+        </form>
+    }
+}
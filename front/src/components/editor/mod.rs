@@ -1,4 +1,5 @@
 #[allow(clippy::module_inception)]
 pub mod editor;
+pub mod meta_editor;
 
 mod atoms;
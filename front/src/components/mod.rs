@@ -1,4 +1,6 @@
 pub mod admin;
 pub mod atoms;
+pub mod comments;
 pub mod editor;
 pub mod organisms;
+pub mod profile;
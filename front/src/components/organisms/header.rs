@@ -1,7 +1,7 @@
 use crate::{
     components::{
         atoms::{flag::FlagSelect, logo::Logo},
-        organisms::menu::Menu,
+        organisms::{menu::Menu, search_box::SearchBox},
     },
     data::locales::{LocalesStore, TK},
     router::Route,
@@ -30,7 +30,10 @@ pub fn header() -> Html {
                         <Link<Route> to={Route::Contact} classes={"btn btn-neutral borderen-none bordered-b-lg"}>{locales_store.get(TK::Contact)}</Link<Route>>
                     </div>
                 </div>
-            <FlagSelect country={locales_store.curr} {onselectedchanged}/>
+            <div class={"flex flex-row gap-2 items-center"}>
+                <SearchBox />
+                <FlagSelect country={locales_store.curr} {onselectedchanged}/>
+            </div>
         </div>
         </div>
         </div>
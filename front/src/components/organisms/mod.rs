@@ -1,3 +1,4 @@
 pub mod header;
 pub mod menu;
+pub mod search_box;
 pub mod user_box;
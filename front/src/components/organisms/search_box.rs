@@ -0,0 +1,33 @@
+use crate::{
+    data::locales::{LocalesStore, TK},
+    router::{Route, SearchQuery},
+};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+use yewdux::prelude::*;
+
+#[function_component(SearchBox)]
+pub fn search_box() -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let navigator = use_navigator().unwrap();
+    let query = use_mut_ref(String::new);
+    let oninput = {
+        let query = query.clone();
+        Callback::from(move |e: InputEvent| {
+            *query.borrow_mut() = e.target_unchecked_into::<HtmlInputElement>().value();
+        })
+    };
+    let onsubmit = Callback::from(move |e: SubmitEvent| {
+        e.prevent_default();
+        let q = query.borrow().trim().to_string();
+        if !q.is_empty() {
+            _ = navigator.push_with_query(&Route::Search, &SearchQuery { q });
+        }
+    });
+    html! {
+        <form role={"search"} {onsubmit}>
+            <input class={"input input-bordered input-sm w-28 lg:w-48"} type={"search"} placeholder={locales_store.get(TK::TypeSearch)} aria-label={locales_store.get(TK::Search)} {oninput} />
+        </form>
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    api::client::use_api,
     async_event,
     components::{
         atoms::modal::{show_modal_callback, Buttons, ModalButton, ModalData, ModalStore},
@@ -8,10 +9,10 @@ use crate::{
         locales::{LocalesStore, TK},
         session::SessionStore,
     },
-    models::user::{Role, User},
+    models::{role::Role, user::User},
     router::Route,
 };
-use yew::prelude::*;
+use yew::{platform::spawn_local, prelude::*};
 use yew_router::prelude::*;
 use yewdux::prelude::*;
 
@@ -24,6 +25,9 @@ pub fn user_box() -> Html {
         return html! {
             <>
                 <UserButton user={user.clone()}/>
+                if user.role == Role::Admin {
+                    <AdminButton/>
+                }
                 <LogoutButton/>
             </>
         };
@@ -53,13 +57,16 @@ fn login_button() -> Html {
 
 #[function_component(LogoutButton)]
 fn logout_button() -> Html {
+    let api = use_api();
     let (_, session_dispatch) = use_store::<SessionStore>();
     let (_, dispatch) = use_store::<ModalStore>();
     let (locales_store, _) = use_store::<LocalesStore>();
     let navigator = use_navigator().unwrap();
     let style = "-webkit-mask: url(/img/ui/logout.svg) no-repeat center;mask: url(/img/ui/logout.svg) no-repeat center;";
-    let onclick = async_event!(|session_dispatch, navigator| {
+    let onclick = async_event!(|api, session_dispatch, navigator| {
         close_menu();
+        // Clears the auth cookies, when the API is in the cookie mode.
+        let _ = api.logout().await;
         session_dispatch.reduce(|_| SessionStore::default().into());
         navigator.push(&Route::Login);
     });
@@ -103,15 +110,52 @@ struct UserButtonProps {
 
 #[function_component(UserButton)]
 fn user_button(props: &UserButtonProps) -> Html {
+    let api = use_api();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
     let navigator = use_navigator().unwrap();
-    let onclick = match props.user.role {
-        Role::Admin => Some(Callback::from(move |_| {
-            close_menu();
-            navigator.push(&Route::AdminPanelRoot);
-        })),
-        _ => None,
-    };
+    {
+        let session_store = session_store.clone();
+        use_effect_with_deps(
+            move |_| {
+                if session_store.profile.is_none() {
+                    let token = session_store.token.clone().unwrap_or_default();
+                    spawn_local(async move {
+                        if let Ok(profile) = api.get_profile(&token).await {
+                            session_dispatch.reduce_mut(|s| s.profile = Some(profile));
+                        }
+                    });
+                }
+                || {}
+            },
+            props.user.id,
+        );
+    }
+    let name = props.user.name.to_string();
+    let onclick = Callback::from(move |_| {
+        close_menu();
+        navigator.push(&Route::Profile { name: name.clone() });
+    });
+    let display_name = session_store
+        .profile
+        .as_ref()
+        .and_then(|p| p.display_name.clone())
+        .unwrap_or(props.user.name.to_string());
     html! {
-        <a {onclick} class={"btn btn-primary font-mono text-xl normal-case"}>{&props.user.name}</a>
+        <a {onclick} class={"btn btn-primary font-mono text-xl normal-case"}>{display_name}</a>
+    }
+}
+
+#[function_component(AdminButton)]
+fn admin_button() -> Html {
+    let navigator = use_navigator().unwrap();
+    let onclick = Callback::from(move |_| {
+        close_menu();
+        navigator.push(&Route::AdminPanelRoot);
+    });
+    let style = "-webkit-mask: url(/img/ui/admin.svg) no-repeat center;mask: url(/img/ui/admin.svg) no-repeat center;";
+    html! {
+        <div class={"btn btn-secondary p-1"} {onclick}>
+            <a class={"aspect-square h-full bg-secondary-content"} {style}/>
+        </div>
     }
 }
@@ -0,0 +1,101 @@
+use crate::{
+    api::{
+        client::{use_api, RequestError},
+        error::IntoLocalized,
+    },
+    async_event,
+    components::atoms::{
+        modal::{show_modal_callback, Buttons, ModalButton, ModalData, ModalStore},
+        text_input::TextInput,
+    },
+    data::{
+        locales::{LocalesStore, TK},
+        session::SessionStore,
+    },
+    handle_api_error,
+    router::Route,
+    utils::download::download_json,
+};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+use yewdux::prelude::*;
+
+/// Personal data export and account deletion, shown on the own profile only.
+#[function_component(AccountData)]
+pub fn account_data() -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let (_, modal_dispatch) = use_store::<ModalStore>();
+    let navigator = use_navigator().unwrap();
+    let token = session_store.token.clone().unwrap_or_default();
+    let name = session_store
+        .user
+        .as_ref()
+        .map(|u| u.name.to_lowercase())
+        .unwrap_or_default();
+    let password = use_state_eq(String::new);
+    let password_error = use_state_eq(|| Option::<String>::None);
+    let error_state = use_state_eq(|| None);
+    let onexport = async_event!(|api, token, name, error_state| {
+        match api.export_user_data(&token).await {
+            Ok(data) => download_json(&format!("petompp-{}.json", name), data.to_string()),
+            Err(error) => error_state.set(Some(error)),
+        }
+    });
+    let onchange_password = {
+        let password = password.clone();
+        Callback::from(move |e: InputEvent| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            password.set(target_element.value());
+        })
+    };
+    let ondelete = async_event!(|api,
+                                 token,
+                                 password,
+                                 password_error,
+                                 error_state,
+                                 session_dispatch,
+                                 navigator,
+                                 locales_store| {
+        match api.delete_account(&token, &password).await {
+            Ok(()) => {
+                session_dispatch.reduce(|_| SessionStore::default().into());
+                navigator.push(&Route::Home);
+            }
+            // Wrong password must not end the session.
+            Err(RequestError::Endpoint(401, e)) => {
+                password_error.set(Some(e.into_localized(&locales_store)))
+            }
+            Err(error) => error_state.set(Some(error)),
+        }
+    });
+    let ondelete = show_modal_callback(
+        ModalData {
+            title: locales_store.get(TK::DeleteAccount),
+            message: locales_store.get(TK::DeleteAccountQuestion),
+            buttons: Buttons::RiskyCancel(
+                ModalButton::new(locales_store.get(TK::DeleteAccount), Some(ondelete)),
+                ModalButton::new(locales_store.get(TK::Cancel), None),
+            ),
+        },
+        modal_dispatch,
+    );
+    handle_api_error!(error_state, session_dispatch, false);
+    let delete_class = match password.is_empty() {
+        true => "btn btn-sm btn-error w-fit mt-2 btn-disabled",
+        false => "btn btn-sm btn-error w-fit mt-2",
+    };
+    html! {
+        <div class={"flex flex-col mt-8"}>
+            <p class={"text-xl font-bold font-mono"}>{locales_store.get(TK::AccountData)}</p>
+            <button class={"btn btn-sm btn-primary w-fit mt-2"} onclick={onexport}>{locales_store.get(TK::ExportData)}</button>
+            <TextInput
+                label={locales_store.get(TK::ConfirmWithPassword)} itype={"password".to_string()}
+                placeholder={locales_store.get(TK::TypePassword)} autocomplete={"current-password"}
+                value={(*password).clone()} onchange={onchange_password} error={(*password_error).clone()} />
+            <a class={delete_class} onclick={ondelete}>{locales_store.get(TK::DeleteAccount)}</a>
+        </div>
+    }
+}
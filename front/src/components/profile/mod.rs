@@ -0,0 +1,3 @@
+pub mod account_data;
+pub mod profile_card;
+pub mod profile_editor;
@@ -0,0 +1,68 @@
+use crate::{
+    components::atoms::link::HrefLink,
+    data::locales::{LocalesStore, TK},
+    models::profile::PublicProfile,
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct ProfileCardProps {
+    pub profile: PublicProfile,
+}
+
+#[function_component(ProfileCard)]
+pub fn profile_card(props: &ProfileCardProps) -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let profile = &props.profile;
+    let member_since = profile
+        .created_at
+        .map(|c| c.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    html! {
+        <div class={"flex flex-col lg:flex-row gap-6 items-center lg:items-start"}>
+            <Avatar src={profile.profile.avatar.clone()} name={profile.name.to_string()} />
+            <div class={"flex flex-col gap-2 w-full"}>
+                <p class={"text-2xl font-bold break-all"}>{profile.display_name()}</p>
+                <p class={"text-sm font-mono opacity-70 break-all"}>{format!("@{}", &profile.name)}</p>
+                <p class={"text-sm opacity-70"}>{locales_store.get(TK::MemberSince(member_since))}</p>
+                if let Some(bio) = &profile.profile.bio {
+                    <p class={"whitespace-pre-line break-words mt-2"}>{bio}</p>
+                }
+                if !profile.profile.links.is_empty() {
+                    <ul class={"flex flex-col gap-1 mt-2"}>
+                        { for profile.profile.links.iter().map(|link| html! {
+                            <li class={"break-all"}><HrefLink href={link.clone()} /></li>
+                        }) }
+                    </ul>
+                }
+            </div>
+        </div>
+    }
+}
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct AvatarProps {
+    pub src: Option<String>,
+    pub name: String,
+}
+
+#[function_component(Avatar)]
+pub fn avatar(props: &AvatarProps) -> Html {
+    match &props.src {
+        Some(src) => html! {
+            <div class={"avatar"}>
+                <div class={"w-32 rounded-full ring ring-primary"}>
+                    <img src={src.clone()} alt={props.name.clone()} />
+                </div>
+            </div>
+        },
+        None => html! {
+            <div class={"avatar placeholder"}>
+                <div class={"w-32 rounded-full ring ring-primary bg-neutral-focus text-neutral-content"}>
+                    <span class={"text-5xl uppercase"}>{props.name.chars().take(1).collect::<String>()}</span>
+                </div>
+            </div>
+        },
+    }
+}
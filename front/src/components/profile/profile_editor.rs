@@ -0,0 +1,185 @@
+use super::profile_card::Avatar;
+use crate::{
+    api::{
+        client::{use_api, RequestError},
+        error::{validation::Error as ValidationError, ApiError, IntoLocalized},
+    },
+    async_event,
+    components::atoms::{modal::show_error, text_input::TextInput},
+    data::{
+        locales::{LocalesStore, TK},
+        session::SessionStore,
+    },
+    handle_api_error,
+    models::profile::Profile,
+};
+use web_sys::HtmlInputElement;
+use yew::{platform::spawn_local, prelude::*};
+use yewdux::prelude::*;
+
+const AVATAR_FOLDER: &str = "avatars";
+const MAX_LINKS: usize = 5;
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct ProfileEditorProps {
+    pub name: String,
+    pub profile: Profile,
+    pub onsaved: Callback<Profile>,
+    pub oncancel: Callback<MouseEvent>,
+}
+
+#[function_component(ProfileEditor)]
+pub fn profile_editor(props: &ProfileEditorProps) -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let token = session_store.token.clone().unwrap_or_default();
+    let profile = use_state_eq(|| props.profile.clone());
+    let validation_error = use_state_eq(|| Option::<String>::None);
+    let error_state = use_state_eq(|| None);
+
+    let onchange_display_name = {
+        let profile = profile.clone();
+        Callback::from(move |e: InputEvent| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            let mut new_profile = (*profile).clone();
+            new_profile.display_name = Some(target_element.value());
+            profile.set(new_profile);
+        })
+    };
+    let onchange_bio = {
+        let profile = profile.clone();
+        Callback::from(move |e: InputEvent| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            let mut new_profile = (*profile).clone();
+            new_profile.bio = Some(target_element.value());
+            profile.set(new_profile);
+        })
+    };
+    let onchange_avatar = {
+        let profile = profile.clone();
+        let api = api.clone();
+        let token = token.clone();
+        let error_state = error_state.clone();
+        let locales_store = locales_store.clone();
+        Callback::from(move |e: Event| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            let Some(file) = target_element.files().and_then(|f| f.get(0)) else {
+                return;
+            };
+            let api = api.clone();
+            let profile = profile.clone();
+            let token = token.clone();
+            let error_state = error_state.clone();
+            let locales_store = locales_store.clone();
+            spawn_local(async move {
+                match api.upload_img(&token, file, AVATAR_FOLDER).await {
+                    Ok(url) => {
+                        let mut new_profile = (*profile).clone();
+                        new_profile.avatar = Some(url);
+                        profile.set(new_profile);
+                    }
+                    Err(RequestError::Endpoint(413, e)) => {
+                        show_error(e.into_localized(&locales_store), false)
+                    }
+                    Err(e) => error_state.set(Some(e)),
+                }
+            });
+        })
+    };
+    let remove_avatar = {
+        let profile = profile.clone();
+        Callback::from(move |_| {
+            let mut new_profile = (*profile).clone();
+            new_profile.avatar = None;
+            profile.set(new_profile);
+        })
+    };
+    let add_link = {
+        let profile = profile.clone();
+        Callback::from(move |_| {
+            let mut new_profile = (*profile).clone();
+            new_profile.links.push(String::new());
+            profile.set(new_profile);
+        })
+    };
+    let get_onchange_link = |index: usize| {
+        let profile = profile.clone();
+        Callback::from(move |e: InputEvent| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            let mut new_profile = (*profile).clone();
+            new_profile.links[index] = target_element.value();
+            profile.set(new_profile);
+        })
+    };
+    let get_remove_link = |index: usize| {
+        let profile = profile.clone();
+        Callback::from(move |_| {
+            let mut new_profile = (*profile).clone();
+            new_profile.links.remove(index);
+            profile.set(new_profile);
+        })
+    };
+    let onsubmit = async_event!(
+        [prevent SubmitEvent] |api, profile, token, props, validation_error, error_state, session_dispatch, locales_store| {
+            match api.update_profile(&token, &*profile).await {
+                Ok(saved) => {
+                    validation_error.set(None);
+                    session_dispatch.reduce_mut(|s| s.profile = Some(saved.clone()));
+                    props.onsaved.emit(saved);
+                }
+                Err(RequestError::Endpoint(_, error @ ApiError::ValidationError(ValidationError::Profile(_)))) => {
+                    validation_error.set(Some(error.into_localized(&locales_store)))
+                }
+                Err(error) => error_state.set(Some(error)),
+            }
+        }
+    );
+    handle_api_error!(error_state, session_dispatch, false);
+    let add_link_class = match profile.links.len() < MAX_LINKS {
+        true => "btn btn-sm btn-accent w-fit mt-2",
+        false => "btn btn-sm btn-accent w-fit mt-2 btn-disabled",
+    };
+    html! {
+        <form class={"form-control w-full"} {onsubmit}>
+            <div class={"flex flex-row gap-4 items-center"}>
+                <Avatar src={profile.avatar.clone()} name={props.name.clone()} />
+                <div class={"flex flex-col gap-2"}>
+                    <label class={"label"}>
+                        <span class={"label-text lg:text-lg"}>{locales_store.get(TK::Avatar)}</span>
+                    </label>
+                    <input type={"file"} accept={"image/png, image/jpeg, image/bmp"} class={"file-input file-input-bordered file-input-sm"} onchange={onchange_avatar} />
+                    if profile.avatar.is_some() {
+                        <a class={"btn btn-sm btn-warning w-fit"} onclick={remove_avatar}>{locales_store.get(TK::Delete)}</a>
+                    }
+                </div>
+            </div>
+            <TextInput
+                label={locales_store.get(TK::DisplayName)} itype={"text".to_string()}
+                placeholder={locales_store.get(TK::TypeDisplayName)} autocomplete={"nickname"}
+                value={profile.display_name.clone().unwrap_or_default()} onchange={onchange_display_name} />
+            <label class={"label"}>
+                <span class={"label-text lg:text-lg"}>{locales_store.get(TK::Bio)}</span>
+            </label>
+            <textarea class={"textarea textarea-bordered shadow-md h-32"} placeholder={locales_store.get(TK::TypeBio)}
+                value={profile.bio.clone().unwrap_or_default()} oninput={onchange_bio} />
+            <label class={"label"}>
+                <span class={"label-text lg:text-lg"}>{locales_store.get(TK::Links)}</span>
+            </label>
+            <div class={"flex flex-col gap-2"}>
+                { for profile.links.iter().enumerate().map(|(i, link)| html! {
+                    <div class={"flex flex-row gap-2"}>
+                        <input class={"input input-bordered input-sm shadow-md grow"} type={"url"} value={link.clone()} oninput={get_onchange_link(i)} />
+                        <a class={"btn btn-sm btn-warning"} onclick={get_remove_link(i)}>{locales_store.get(TK::Delete)}</a>
+                    </div>
+                }) }
+            </div>
+            <a class={add_link_class} onclick={add_link}>{locales_store.get(TK::AddLink)}</a>
+            <span class={"text-error mt-1"}>{(*validation_error).clone().unwrap_or_default()}</span>
+            <div class={"flex flex-row justify-end gap-2 mt-4"}>
+                <a class={"btn btn-neutral"} onclick={props.oncancel.clone()}>{locales_store.get(TK::Cancel)}</a>
+                <button class={"btn btn-primary"}>{locales_store.get(TK::Save)}</button>
+            </div>
+        </form>
+    }
+}
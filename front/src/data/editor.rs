@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use yewdux::prelude::*;
 
 #[derive(Default, PartialEq, Clone, Debug, Store, Serialize, Deserialize)]
-#[store(storage = "local", storage_tab_sync)]
+#[cfg_attr(not(feature = "ssr"), store(storage = "local", storage_tab_sync))]
 pub struct EditorStore {
     values: HashMap<String, String>,
 }
@@ -1,12 +1,13 @@
-use crate::{api::error::validation::PasswordRequirements, components::atoms::flag::Country};
+use crate::{api::error::ApiError, components::atoms::flag::Country};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use strum::{EnumIter, IntoEnumIterator};
 use wasm_bindgen::{prelude::Closure, JsCast};
 use yewdux::prelude::*;
 
+// Nothing is stored on the server, the prerendered pages put their state in the stores.
 #[derive(PartialEq, Clone, Debug, Store, Serialize, Deserialize)]
-#[store(storage = "local")]
+#[cfg_attr(not(feature = "ssr"), store(storage = "local"))]
 pub struct LocalesStore {
     pub curr: Country,
     #[serde(skip_serializing, skip_deserializing)]
@@ -43,39 +44,44 @@ impl LocalesStore {
         match key {
             TK::ActivateUserQuestion(s)
             | TK::DeleteUserQuestion(s)
-            | TK::E_Auth_MissingClaim(s)
-            | TK::E_Auth_InvalidFormat(s)
-            | TK::E_Auth_JwtError(s)
-            | TK::E_Database(s)
-            | TK::E_DatabaseConnection(s)
-            | TK::E_UserNameTaken(s)
-            | TK::E_UserNotFound(s)
-            | TK::E_UserNotConfirmed(s)
-            | TK::E_Validation_Username_InvalidCharacters(s)
-            | TK::E_Validation_Query_InvalidColumn(s) => val.replace("%{0}", &s),
-            TK::E_Auth_TokenExpiredS(s) => val.replace("%{0}", &s.to_string()),
-            TK::E_Validation_Username_InvalidLength(min, max) => val
-                .replace("%{0}", &min.to_string())
-                .replace("%{1}", &max.to_string()),
+            | TK::DeleteInviteQuestion(s)
+            | TK::BanUserQuestion(s)
+            | TK::MemberSince(s) => val.replace("%{0}", &s),
             TK::E_Validation_PasswordRequirement(min, max, s) => val
                 .replace("%{0}", &min.to_string())
                 .replace("%{1}", &max.to_string())
                 .replace("%{2}", &s),
-            TK::E_Validation_ResourceData_KeyMismatch(exp, act) => {
-                val.replace("%{0}", &exp).replace("%{1}", &act)
-            }
-            TK::E_Validation_Password(pr) => pr.into_localized(self),
+            TK::ImportSummary(created, updated, skipped, files) => val
+                .replace("%{0}", &created)
+                .replace("%{1}", &updated)
+                .replace("%{2}", &skipped)
+                .replace("%{3}", &files),
             _ => val,
         }
     }
 
+    /// Message of the API error with the `code`, `None` when the locale doesn't know it.
+    pub fn get_code(&self, code: &str, params: &[String]) -> Option<String> {
+        let val = match self.curr {
+            Country::Poland => self.pl.get(code),
+            Country::UnitedKingdom => self.en.get(code),
+        }?;
+        Some(params.iter().enumerate().fold(val.clone(), |val, (i, p)| {
+            val.replace(&format!("%{{{}}}", i), p)
+        }))
+    }
+
     pub fn load(&mut self, country: Country, data: HashMap<String, String>) {
         let data = data.iter().map(|(k, v)| (k.into(), v.clone())).collect();
         if let Err(diff) = Self::validate_data(&data) {
-            gloo::console::warn!(&format!(
+            let warning = format!(
                 "Invalid data for country {:?}:\nmissing: {:?}\nextra: {:?}",
                 country, diff.missing, diff.extra
-            ));
+            );
+            #[cfg(feature = "ssr")]
+            eprintln!("{}", warning);
+            #[cfg(not(feature = "ssr"))]
+            gloo::console::warn!(&warning);
         }
         match country {
             Country::Poland => self.pl = data,
@@ -95,7 +101,10 @@ impl LocalesStore {
     }
 
     fn validate_data(data: &HashMap<String, String>) -> Result<(), DataDiff> {
-        let tks = TK::iter().map(|tk| tk.to_string()).collect::<HashSet<_>>();
+        let tks = TK::iter()
+            .map(|tk| tk.to_string())
+            .chain(ApiError::CODES.iter().map(|code| code.to_string()))
+            .collect::<HashSet<_>>();
         let data_tks = data.keys().cloned().collect::<HashSet<_>>();
         let missing = tks.difference(&data_tks).cloned().collect::<Vec<_>>();
         let extra = data_tks.difference(&tks).cloned().collect::<Vec<_>>();
@@ -148,28 +157,72 @@ pub enum TK {
     Editor,
     Preview,
     SaveDraft,
-    E_Auth_MissingClaim(String),
-    E_Auth_InvalidFormat(String),
-    E_Auth_TokenExpiredS(i32),
-    E_Auth_JwtError(String),
-    E_Database(String),
-    E_DatabaseConnection(String),
-    E_UserNameTaken(String),
-    E_UserNotFound(String),
-    E_InvalidCredentials,
-    E_UserNotConfirmed(String),
-    E_Validation_Username_InvalidLength(i32, i32),
-    E_Validation_Username_InvalidCharacters(String),
-    E_Validation_Password(PasswordRequirements),
+    Profile,
+    EditProfile,
+    DisplayName,
+    TypeDisplayName,
+    Bio,
+    TypeBio,
+    Avatar,
+    Links,
+    AddLink,
+    MemberSince(String),
+    InviteManagement,
+    InviteCode,
+    TypeInviteCode,
+    Code,
+    Role,
+    Uses,
+    MaxUses,
+    ExpiresAt,
+    Never,
+    CreateInvite,
+    CopyLink,
+    DeleteInviteQuestion(String),
+    ContentBundle,
+    ExportBundle,
+    ImportBundle,
+    ConflictStrategy,
+    DryRun,
+    ImportSummary(String, String, String, String),
+    AccountData,
+    ExportData,
+    DeleteAccount,
+    DeleteAccountQuestion,
+    ConfirmWithPassword,
+    Search,
+    TypeSearch,
+    SearchResults,
+    NoSearchResults,
+    PageMeta,
+    MetaTitle,
+    TypeMetaTitle,
+    MetaDescription,
+    TypeMetaDescription,
+    CanonicalUrl,
+    PreviewImage,
+    MetaSaved,
+    Comments,
+    NoComments,
+    WriteComment,
+    PostComment,
+    Reply,
+    LoginToComment,
+    AwaitingModeration,
+    Edited,
+    CommentDeleted,
+    DeleteCommentQuestion,
+    CommentModeration,
+    NoPendingComments,
+    Approve,
+    Reject,
+    Ban,
+    BanUserQuestion(String),
     E_Validation_PasswordRequirement(i32, i32, String),
     E_Validation_PasswordRequirement_ContainsLowercase,
     E_Validation_PasswordRequirement_ContainsUppercase,
     E_Validation_PasswordRequirement_ContainsNumber,
     E_Validation_PasswordRequirement_ContainsSpecialCharacter,
-    E_Validation_Query_InvalidColumn(String),
-    E_Validation_ResourceData_KeyMismatch(String, String),
-    E_Validation_ResourceData_KeyMissing,
-    E_Validation_ResourceData_ValueMissing,
 }
 
 impl std::fmt::Display for TK {
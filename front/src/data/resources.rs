@@ -16,9 +16,12 @@ impl Display for Key {
 }
 
 #[derive(Default, PartialEq, Clone, Debug, Store, Serialize, Deserialize)]
-#[store(storage = "local", storage_tab_sync)]
+#[cfg_attr(not(feature = "ssr"), store(storage = "local", storage_tab_sync))]
 pub struct ResourceStore {
     values: HashMap<String, String>,
+    /// `ETag` of the values as received from the API.
+    #[serde(default)]
+    etags: HashMap<String, String>,
 }
 
 impl ResourceStore {
@@ -26,7 +29,22 @@ impl ResourceStore {
         self.values.get(&key.to_string())
     }
 
+    pub fn get_etag<'a>(&'a self, key: &Key) -> Option<&'a str> {
+        self.etags.get(&key.to_string()).map(String::as_str)
+    }
+
+    /// Stores a value changed locally, it has to be downloaded again to be revalidated.
     pub fn add_or_update_state(&mut self, key: &Key, state: String) {
+        self.etags.remove(&key.to_string());
+        self.values.insert(key.to_string(), state);
+    }
+
+    /// Stores a value received from the API along with its `ETag`.
+    pub fn add_or_update_fetched(&mut self, key: &Key, state: String, etag: Option<String>) {
+        match etag {
+            Some(etag) => self.etags.insert(key.to_string(), etag),
+            None => self.etags.remove(&key.to_string()),
+        };
         self.values.insert(key.to_string(), state);
     }
 }
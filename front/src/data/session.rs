@@ -1,10 +1,14 @@
-use crate::models::user::User;
+use crate::models::{profile::Profile, user::User};
 use serde::{Deserialize, Serialize};
 use yewdux::prelude::*;
 
 #[derive(Default, PartialEq, Clone, Debug, Store, Serialize, Deserialize)]
-#[store(storage = "session", storage_tab_sync)]
+#[cfg_attr(not(feature = "ssr"), store(storage = "session", storage_tab_sync))]
 pub struct SessionStore {
     pub user: Option<User>,
     pub token: Option<String>,
+    #[serde(default)]
+    pub csrf: Option<String>,
+    #[serde(default)]
+    pub profile: Option<Profile>,
 }
@@ -1,32 +1,61 @@
 use crate::{
-    api::client::Client,
+    api::{client::ApiContext, http::HttpClient},
     components::{
         atoms::modal::{ErrorModal, Modal},
         organisms::header::Header,
     },
     data::locales::LocalesStore,
     router::{switch, Route},
+    utils::head,
 };
 use yew::{platform::spawn_local, prelude::*};
-use yew_router::{BrowserRouter, Switch};
+use yew_router::{
+    history::{AnyHistory, BrowserHistory},
+    Router, Switch,
+};
 use yewdux::prelude::*;
 
-mod api;
-mod components;
-mod data;
-mod models;
-mod pages;
+pub mod api;
+pub mod components;
+pub mod data;
+pub mod models;
+pub mod pages;
+pub mod prerender;
 mod router;
 mod utils;
 
+#[derive(Properties, PartialEq, Default)]
+pub struct AppProps {
+    /// History of the router, the browser's one when not set.
+    #[prop_or_default]
+    pub history: Option<AnyHistory>,
+}
+
 #[function_component(App)]
-pub fn app() -> Html {
+pub fn app(props: &AppProps) -> Html {
+    let api = use_memo(|_| ApiContext::new(HttpClient::default()), ());
+    let history = use_memo(
+        |history| {
+            history
+                .clone()
+                .unwrap_or_else(|| AnyHistory::from(BrowserHistory::new()))
+        },
+        props.history.clone(),
+    );
     let error_state = use_state(|| None);
     let (locale_store, locale_dispatch) = use_store::<LocalesStore>();
+    use_effect_with_deps(
+        |lang| {
+            head::apply_feeds(&HttpClient::get_api_url(""), lang.key());
+            || {}
+        },
+        locale_store.curr,
+    );
     if !locale_store.is_loaded(locale_store.curr) || error_state.is_some() {
         let locale_dispatch = locale_dispatch.clone();
+        let api = api.0.clone();
         spawn_local(async move {
-            match Client::get_locale(locale_store.curr.key()).await {
+            match api.get_locale(locale_store.curr.key()).await {
                 Ok(data) => {
                     locale_dispatch.reduce_mut(|l| l.load(locale_store.curr, data));
                 }
@@ -36,19 +65,27 @@ pub fn app() -> Html {
             };
         })
     }
-    LocalesStore::add_lang_change_event_listener(locale_dispatch);
+    use_effect_with_deps(
+        move |_| {
+            LocalesStore::add_lang_change_event_listener(locale_dispatch);
+            || {}
+        },
+        (),
+    );
 
     html! {
-        <BrowserRouter>
-            <body class={"flex flex-col min-h-screen bg-base-300"}>
-                <Header />
-                <img src={"/img/coast.jpg"} class={"w-full w-max-full h-max-full absolute top-10 opacity-40 my-4 h-90"} />
-                <div class={"m-auto w-5/6 xl:w-2/3 flex flex-col grow items-center"}>
-                    <Switch<Route> render={switch}/>
+        <ContextProvider<ApiContext> context={(*api).clone()}>
+            <Router history={(*history).clone()}>
+                <div class={"flex flex-col min-h-screen bg-base-300"}>
+                    <Header />
+                    <img src={"/img/coast.jpg"} class={"w-full w-max-full h-max-full absolute top-10 opacity-40 my-4 h-90"} />
+                    <div class={"m-auto w-5/6 xl:w-2/3 flex flex-col grow items-center"}>
+                        <Switch<Route> render={switch}/>
+                    </div>
                 </div>
-            </body>
-            <Modal />
-            <ErrorModal />
-        </BrowserRouter>
+                <Modal />
+                <ErrorModal />
+            </Router>
+        </ContextProvider<ApiContext>>
     }
 }
@@ -1,5 +1,24 @@
-use petompp_web_front::App;
+use petompp_web_front::{
+    components::atoms::flag::Country, data::locales::LocalesStore, prerender::Prerendered, App,
+};
+use yewdux::prelude::*;
 
 fn main() {
-    yew::Renderer::<App>::new().render();
+    if let Some(lang) = Country::from_query() {
+        Dispatch::<LocalesStore>::new().reduce_mut(|store| store.curr = lang);
+    }
+    let renderer = yew::Renderer::<App>::new();
+    match Prerendered::read() {
+        Some(state) if state.is_current() => {
+            state.hydrate();
+            renderer.hydrate();
+        }
+        Some(_) => {
+            Prerendered::discard();
+            renderer.render();
+        }
+        None => {
+            renderer.render();
+        }
+    }
 }
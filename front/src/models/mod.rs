@@ -1,3 +1,4 @@
-pub mod credentials;
-pub mod resource_data;
-pub mod user;
+pub use petompp_web_models::models::{
+    bundle, comment, credentials, feed, invite, page_meta, profile, registration, resource_data,
+    role, search, sitemap, user, user_name,
+};
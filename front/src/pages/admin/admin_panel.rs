@@ -1,6 +1,7 @@
 use crate::{
+    components::admin::{bundle_manager::BundleManager, comment_moderator::CommentModerator},
     data::session::SessionStore,
-    models::user::Role,
+    models::role::Role,
     pages::{not_found::NotFound, page_base::EditablePage},
 };
 use yew::prelude::*;
@@ -14,6 +15,9 @@ pub fn admin_panel() -> Html {
         return html! {<NotFound />};
     };
     html! {
-        <EditablePage reskey={RES_KEY.to_string()}/ >
+        <EditablePage reskey={RES_KEY.to_string()} comments={false}>
+            <BundleManager />
+            <CommentModerator />
+        </EditablePage>
     }
 }
@@ -1,2 +1,3 @@
 pub mod admin_panel;
+pub mod invite_management;
 pub mod user_management;
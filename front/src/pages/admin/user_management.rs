@@ -4,7 +4,7 @@ use crate::{
         locales::{LocalesStore, TK},
         session::SessionStore,
     },
-    models::user::Role,
+    models::role::Role,
     pages::{not_found::NotFound, page_base::PageBase},
 };
 use yew::prelude::*;
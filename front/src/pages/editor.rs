@@ -1,4 +1,5 @@
 use crate::components::editor::editor::Editor as EditorInner;
+use crate::components::editor::meta_editor::MetaEditor;
 use crate::data::resources::Key;
 use crate::pages::page_base::PageBase;
 use yew::prelude::*;
@@ -23,7 +24,8 @@ pub fn editor(props: &EditorProps) -> Html {
     let reskey: Key = props.clone().into();
     html! {
         <PageBase>
-            <EditorInner {reskey} />
+            <EditorInner reskey={reskey.clone()} />
+            <MetaEditor {reskey} />
         </PageBase>
     }
 }
@@ -1,5 +1,9 @@
 use crate::{
-    api, async_event,
+    api::{
+        client::{use_api, RequestError},
+        error::IntoLocalized,
+    },
+    async_event,
     components::atoms::{modal::show_error, text_input::TextInput},
     data::{
         locales::{LocalesStore, TK},
@@ -16,6 +20,7 @@ use yewdux::prelude::use_store;
 
 #[function_component(Login)]
 pub fn login() -> Html {
+    let api = use_api();
     let form_data = use_mut_ref(Credentials::default);
     let error_state = use_state_eq(|| Option::None);
     let history = use_navigator().unwrap();
@@ -41,14 +46,16 @@ pub fn login() -> Html {
         })
     };
     let onsubmit = async_event!(
-        [prevent SubmitEvent] |form_data, error_state, history, session_dispatch, locales_store| {
+        [prevent SubmitEvent] |api, form_data, error_state, history, session_dispatch, locales_store| {
             let creds = form_data.borrow().clone();
-            match api::client::Client::login(creds).await {
+            match api.login(creds).await {
                 Ok(response) => {
                     session_dispatch.reduce(|_| {
                         SessionStore {
-                            token: Some(response.token),
+                            token: response.token,
+                            csrf: response.csrf,
                             user: Some(response.user),
+                            profile: None,
                         }
                         .into()
                     });
@@ -56,10 +63,11 @@ pub fn login() -> Html {
                     history.push(&Route::Home);
                 }
                 Err(error) => match error {
-                    api::client::RequestError::Endpoint(_, message) => error_state.set(Some(message.into_localized(locales_store.clone()))),
-                    api::client::RequestError::Parse(message) | api::client::RequestError::Network(message) => {
+                    RequestError::Endpoint(_, message) => error_state.set(Some(message.into_localized(&locales_store))),
+                    RequestError::Parse(message) | RequestError::Network(message) => {
                         show_error(message, true)
                     }
+                    RequestError::Aborted => {}
                 },
             }
         }
@@ -6,5 +6,7 @@ pub mod home;
 pub mod login;
 pub mod not_found;
 pub mod page_base;
+pub mod profile;
 pub mod projects;
 pub mod register;
+pub mod search;
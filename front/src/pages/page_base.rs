@@ -1,14 +1,16 @@
 use crate::{
-    api::client::Client,
-    components::atoms::markdown::Markdown,
+    api::client::{use_api, Revalidated},
+    components::{atoms::markdown::Markdown, comments::comment_thread::CommentThread},
     data::{
         locales::{LocalesStore, TK},
         resources::{Key, ResourceStore},
         session::SessionStore,
     },
-    models::user::Role,
+    models::role::Role,
     router::Route,
+    utils::head,
 };
+use std::{cell::Cell, rc::Rc};
 use yew::{platform::spawn_local, prelude::*};
 use yew_router::prelude::*;
 use yewdux::prelude::*;
@@ -33,10 +35,16 @@ pub fn page_base(props: &PageBaseProps) -> Html {
 #[derive(PartialEq, Properties)]
 pub struct EditablePageBaseProps {
     pub reskey: String,
+    /// Shows the discussion of the page under its content.
+    #[prop_or(true)]
+    pub comments: bool,
+    #[prop_or_default]
+    pub children: Children,
 }
 
 #[function_component(EditablePage)]
 pub fn editable_page_base(props: &EditablePageBaseProps) -> Html {
+    let api = use_api();
     let (locales_store, _) = use_store::<LocalesStore>();
     let (session_store, _) = use_store::<SessionStore>();
     let (res_store, res_dispatch) = use_store::<ResourceStore>();
@@ -61,20 +69,66 @@ pub fn editable_page_base(props: &EditablePageBaseProps) -> Html {
         _ => "hidden",
     };
     let markdown = res_store.get_state(&reskey).cloned().unwrap_or_default();
-    spawn_local(async move {
-        if let Ok(md) = Client::get_resource(reskey.reskey.as_str(), reskey.lang.as_str()).await {
-            if res_store.get_state(&reskey) != Some(&md) {
-                res_dispatch.reduce_mut(|store| {
-                    store.add_or_update_state(&reskey, md);
-                });
-            }
-        }
-    });
+    // Revalidated in an effect, so nothing is requested on the server.
+    {
+        let api = api.clone();
+        use_effect_with_deps(
+            move |reskey| {
+                let (reskey, current) = (reskey.clone(), Rc::new(Cell::new(true)));
+                {
+                    let current = current.clone();
+                    spawn_local(async move {
+                        if let Ok(meta) = api
+                            .get_page_meta(reskey.reskey.as_str(), reskey.lang.as_str())
+                            .await
+                        {
+                            if current.get() {
+                                head::apply(&meta);
+                            }
+                        }
+                    });
+                }
+                move || {
+                    current.set(false);
+                    head::reset();
+                }
+            },
+            reskey.clone(),
+        );
+    }
+    use_effect_with_deps(
+        move |reskey| {
+            let reskey = reskey.clone();
+            spawn_local(async move {
+                let etag = res_store
+                    .get_state(&reskey)
+                    .and(res_store.get_etag(&reskey));
+                if let Ok(Revalidated::Modified { value, etag }) = api
+                    .get_resource(reskey.reskey.as_str(), reskey.lang.as_str(), etag)
+                    .await
+                {
+                    if res_store.get_state(&reskey) != Some(&value)
+                        || res_store.get_etag(&reskey) != etag.as_deref()
+                    {
+                        res_dispatch.reduce_mut(|store| {
+                            store.add_or_update_fetched(&reskey, value, etag);
+                        });
+                    }
+                }
+            });
+            || {}
+        },
+        reskey,
+    );
 
     html! {
         <PageBase>
             <button class={edit_class} onclick={edit_onclick}>{locales_store.get(TK::Edit)}</button>
             <Markdown {markdown} interactive={Some(())} allowhtml={true}/>
+            { for props.children.iter() }
+            if props.comments {
+                <CommentThread reskey={props.reskey.clone()} />
+            }
         </PageBase>
     }
 }
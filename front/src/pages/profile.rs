@@ -0,0 +1,103 @@
+use crate::{
+    api::client::{use_api, RequestError},
+    components::profile::{
+        account_data::AccountData, profile_card::ProfileCard, profile_editor::ProfileEditor,
+    },
+    data::{
+        locales::{LocalesStore, TK},
+        session::SessionStore,
+    },
+    handle_api_error,
+    models::profile::PublicProfile,
+    pages::{not_found::NotFound, page_base::PageBase},
+};
+use yew::{platform::spawn_local, prelude::*};
+use yewdux::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct ProfileProps {
+    pub name: String,
+}
+
+#[function_component(Profile)]
+pub fn profile(props: &ProfileProps) -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, session_dispatch) = use_store::<SessionStore>();
+    let error_state = use_state_eq(|| None);
+    let profile = use_state_eq(|| Option::<PublicProfile>::None);
+    let editing = use_state_eq(|| false);
+    {
+        let api = api.clone();
+        let profile = profile.clone();
+        let error_state = error_state.clone();
+        let editing = editing.clone();
+        use_effect_with_deps(
+            move |name| {
+                let name = name.clone();
+                editing.set(false);
+                spawn_local(async move {
+                    match api.get_public_profile(&name).await {
+                        Ok(p) => profile.set(Some(p)),
+                        Err(e) => error_state.set(Some(e)),
+                    }
+                });
+                || {}
+            },
+            props.name.clone(),
+        );
+    }
+    if let Some(RequestError::Endpoint(404, _)) = &*error_state {
+        return html! { <NotFound /> };
+    }
+    handle_api_error!(error_state, session_dispatch, true);
+    let Some(public_profile) = (*profile).clone() else {
+        return html! { <PageBase /> };
+    };
+    let is_own = session_store
+        .user
+        .as_ref()
+        .is_some_and(|u| u.name.to_lowercase() == public_profile.name.to_lowercase());
+    let content = match (*editing, is_own) {
+        (true, true) => {
+            let onsaved = {
+                let profile = profile.clone();
+                let editing = editing.clone();
+                let public_profile = public_profile.clone();
+                Callback::from(move |saved| {
+                    profile.set(Some(PublicProfile {
+                        profile: saved,
+                        ..public_profile.clone()
+                    }));
+                    editing.set(false);
+                })
+            };
+            let oncancel = {
+                let editing = editing.clone();
+                Callback::from(move |_| editing.set(false))
+            };
+            html! {
+                <ProfileEditor name={public_profile.name.to_string()} profile={public_profile.profile.clone()} {onsaved} {oncancel} />
+            }
+        }
+        _ => html! { <ProfileCard profile={public_profile} /> },
+    };
+    let show_account = is_own && !*editing;
+    let edit_class = match show_account {
+        true => "btn absolute top-5 right-5 btn-accent btn-xs btn-outline",
+        false => "hidden",
+    };
+    let edit_onclick = Callback::from(move |_| editing.set(true));
+    html! {
+        <PageBase>
+            <button class={edit_class} onclick={edit_onclick}>{locales_store.get(TK::EditProfile)}</button>
+            <div class="flex flex-col lg:w-3/4 w-full mx-auto">
+                <p class={"text-2xl font-bold font-mono mb-4"}>{locales_store.get(TK::Profile)}</p>
+                {content}
+                if show_account {
+                    <AccountData />
+                }
+            </div>
+        </PageBase>
+    }
+}
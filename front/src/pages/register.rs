@@ -1,20 +1,21 @@
 use crate::api::error::{
     validation::{Error as ValidationError, UsernameError},
-    ApiError as AppError,
+    ApiError as AppError, IntoLocalized,
 };
 use crate::components::atoms::text_input::TextInput;
 use crate::{
-    api::{self, client::RequestError},
+    api::client::{use_api, RequestError},
     async_event,
     components::atoms::modal::show_error,
     data::locales::{LocalesStore, TK},
-    models::credentials::Credentials,
+    models::{credentials::Credentials, registration::RegistrationPolicy},
     pages::page_base::PageBase,
     router::Route,
 };
+use serde::Deserialize;
 use std::fmt::Display;
 use web_sys::HtmlInputElement;
-use yew::prelude::*;
+use yew::{platform::spawn_local, prelude::*};
 use yew_router::prelude::*;
 use yewdux::prelude::*;
 
@@ -22,6 +23,7 @@ use yewdux::prelude::*;
 enum Error {
     Username(String),
     Password(String),
+    Invite(String),
 }
 
 impl Display for Error {
@@ -29,16 +31,50 @@ impl Display for Error {
         match self {
             Error::Username(e) => write!(f, "{}", e),
             Error::Password(e) => write!(f, "{}", e),
+            Error::Invite(e) => write!(f, "{}", e),
         }
     }
 }
 
+#[derive(Deserialize)]
+struct RegisterQuery {
+    invite: Option<String>,
+}
+
 #[function_component(Register)]
 pub fn register() -> Html {
-    let form_data = use_mut_ref(Credentials::default);
+    let api = use_api();
+    let query_invite = use_location()
+        .and_then(|l| l.query::<RegisterQuery>().ok())
+        .and_then(|q| q.invite);
+    let form_data = {
+        let invite = query_invite.clone();
+        use_mut_ref(|| Credentials {
+            invite,
+            ..Default::default()
+        })
+    };
     let error_state = use_state_eq(|| Option::None);
+    let policy = use_state_eq(|| Option::<RegistrationPolicy>::None);
     let (locales_store, _) = use_store::<LocalesStore>();
     let history = use_navigator().unwrap();
+    {
+        let api = api.clone();
+        let policy = policy.clone();
+        use_effect_with_deps(
+            move |_| {
+                spawn_local(async move {
+                    match api.get_registration_policy().await {
+                        Ok(p) => policy.set(Some(p)),
+                        Err(RequestError::Aborted) => {}
+                        Err(e) => show_error(e.to_string(), true),
+                    }
+                });
+                || {}
+            },
+            (),
+        );
+    }
 
     let onchange_username = {
         let error_state = error_state.clone();
@@ -58,10 +94,19 @@ pub fn register() -> Html {
             error_state.set(Option::None);
         })
     };
+    let onchange_invite = {
+        let error_state = error_state.clone();
+        let form_data = form_data.clone();
+        Callback::from(move |e: InputEvent| {
+            let target_element = e.target_unchecked_into::<HtmlInputElement>();
+            form_data.borrow_mut().invite = Some(target_element.value());
+            error_state.set(Option::None);
+        })
+    };
     let onsubmit = async_event!(
-    [prevent SubmitEvent] |form_data, history, error_state, locales_store| {
+    [prevent SubmitEvent] |api, form_data, history, error_state, locales_store| {
             let creds = form_data.borrow().clone();
-            match api::client::Client::register(creds).await {
+            match api.register(creds).await {
             Ok(()) => {
                 error_state.set(Option::None);
                 history.push(&Route::Login);
@@ -70,21 +115,26 @@ pub fn register() -> Html {
                 match error {
                     RequestError::Endpoint(_, error) => {
                         match &error {
-                            AppError::UserNameTaken(_) => error_state.set(Some(Error::Username(error.into_localized(locales_store.clone())))),
+                            AppError::UserNameTaken(_) => error_state.set(Some(Error::Username(error.into_localized(&locales_store)))),
+                            AppError::InviteCodeRequired |
+                            AppError::InviteCodeInvalid(_) |
+                            AppError::InviteCodeExpired(_) |
+                            AppError::InviteCodeUsedUp(_) => error_state.set(Some(Error::Invite(error.into_localized(&locales_store)))),
                             AppError::ValidationError(ve) => match ve {
                                 ValidationError::Username(ue) => match ue {
                                     UsernameError::InvalidLength(_, _) |
-                                    UsernameError::InvalidCharacters(_) => error_state.set(Some(Error::Username(error.into_localized(locales_store.clone())))),
+                                    UsernameError::InvalidCharacters(_) => error_state.set(Some(Error::Username(error.into_localized(&locales_store)))),
                                 },
-                                ValidationError::Password(_) => error_state.set(Some(Error::Password(error.into_localized(locales_store.clone())))),
-                                _ => show_error(error.into_localized(locales_store.clone()), true),
+                                ValidationError::Password(_) => error_state.set(Some(Error::Password(error.into_localized(&locales_store)))),
+                                _ => show_error(error.into_localized(&locales_store), true),
                             },
-                            _ => show_error(error.into_localized(locales_store.clone()), true),
+                            _ => show_error(error.into_localized(&locales_store), true),
                         }
                     }
                     RequestError::Parse(error) | RequestError::Network(error) => {
                         show_error(error, true)
                     }
+                    RequestError::Aborted => {}
                 }
             }
         }
@@ -97,6 +147,18 @@ pub fn register() -> Html {
         Some(Error::Password(error)) => Some(error.clone()),
         _ => None,
     };
+    let invite_error = match &*error_state {
+        Some(Error::Invite(error)) => Some(error.clone()),
+        _ => None,
+    };
+    let show_invite = *policy == Some(RegistrationPolicy::InviteOnly) || query_invite.is_some();
+    if *policy == Some(RegistrationPolicy::Closed) {
+        return html! {
+            <PageBase>
+                <p class={"text-lg lg:text-2xl mx-auto mt-8 lg:mt-16"}>{AppError::RegistrationClosed.into_localized(&locales_store)}</p>
+            </PageBase>
+        };
+    }
     html! {
         <PageBase>
         <form class={"form-control mx-auto mt-8 lg:mt-16 w-5/6 lg:w-3/4 xl:w-1/2"} {onsubmit}>
@@ -111,6 +173,12 @@ pub fn register() -> Html {
                 label={locales_store.get(TK::Password)} itype={"password".to_string()}
                 placeholder={locales_store.get(TK::TypePassword)} autocomplete={"new-password"}
                 onchange={onchange_password} error={password_error}/>
+            if show_invite {
+                <TextInput
+                    label={locales_store.get(TK::InviteCode)} itype={"text".to_string()}
+                    placeholder={locales_store.get(TK::TypeInviteCode)} autocomplete={"off"}
+                    value={query_invite.clone()} onchange={onchange_invite} error={invite_error}/>
+            }
             <button class={"btn btn-primary shadow-md lg:text-xl mt-4"}>{locales_store.get(TK::Register)}</button>
         </form>
         </PageBase>
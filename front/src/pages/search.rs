@@ -0,0 +1,107 @@
+use crate::{
+    api::{
+        client::{use_api, RequestError},
+        error::IntoLocalized,
+    },
+    components::atoms::modal::show_error,
+    data::locales::{LocalesStore, TK},
+    models::search::SearchHit,
+    pages::page_base::PageBase,
+    router::{Route, SearchQuery},
+};
+use yew::{platform::spawn_local, prelude::*};
+use yew_router::prelude::*;
+use yewdux::prelude::*;
+
+/// Page showing the resource with the `key` and its title.
+fn page_of(key: &str) -> Option<(Route, TK)> {
+    match key {
+        "home-content" => Some((Route::Home, TK::Home)),
+        "about-content" => Some((Route::About, TK::About)),
+        "contact-content" => Some((Route::Contact, TK::Contact)),
+        _ => None,
+    }
+}
+
+#[function_component(Search)]
+pub fn search() -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let query = use_location()
+        .and_then(|l| l.query::<SearchQuery>().ok())
+        .map(|q| q.q)
+        .unwrap_or_default();
+    html! {
+        <PageBase>
+            <p class={"text-2xl font-bold font-mono mb-4"}>{locales_store.get(TK::SearchResults)}</p>
+            <SearchResults {query} />
+        </PageBase>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct SearchResultsProps {
+    pub query: String,
+}
+
+/// Matches of the query in the content of the current language.
+#[function_component(SearchResults)]
+pub fn search_results(props: &SearchResultsProps) -> Html {
+    let api = use_api();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let hits = use_state_eq(|| Option::<Vec<SearchHit>>::None);
+    let error_state = use_state_eq(|| Option::<String>::None);
+    {
+        let hits = hits.clone();
+        let error_state = error_state.clone();
+        let locales_store = locales_store.clone();
+        use_effect_with_deps(
+            move |(query, lang)| {
+                let (query, lang) = (query.clone(), lang.clone());
+                hits.set(None);
+                error_state.set(None);
+                spawn_local(async move {
+                    match api.search(&query, &lang).await {
+                        Ok(h) => hits.set(Some(h)),
+                        Err(RequestError::Endpoint(_, error)) => {
+                            error_state.set(Some(error.into_localized(&locales_store)))
+                        }
+                        Err(RequestError::Aborted) => {}
+                        Err(error) => show_error(error.to_string(), false),
+                    }
+                });
+                || {}
+            },
+            (props.query.clone(), locales_store.curr.key().to_string()),
+        );
+    }
+    if let Some(error) = &*error_state {
+        return html! { <p class={"text-error"}>{error}</p> };
+    }
+    let Some(hits) = &*hits else {
+        return html! {};
+    };
+    if hits.is_empty() {
+        return html! { <p>{locales_store.get(TK::NoSearchResults)}</p> };
+    }
+    let hits = hits.iter().map(|hit| {
+        let title = match page_of(&hit.key) {
+            Some((route, title)) => html! {
+                <Link<Route> to={route} classes={"link link-hover text-lg font-bold"}>{locales_store.get(title)}</Link<Route>>
+            },
+            None => html! { <span class={"text-lg font-bold"}>{&hit.key}</span> },
+        };
+        let snippet = hit.snippet_parts().into_iter().map(|(matched, part)| match matched {
+            true => html! { <mark class={"bg-accent text-accent-content"}>{part}</mark> },
+            false => html! { <>{part}</> },
+        });
+        html! {
+            <li class={"flex flex-col"}>
+                {title}
+                <p class={"text-sm"}>{for snippet}</p>
+            </li>
+        }
+    });
+    html! {
+        <ul class={"flex flex-col gap-4"}>{for hits}</ul>
+    }
+}
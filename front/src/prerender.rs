@@ -0,0 +1,134 @@
+//! Public pages rendered ahead of time by the `prerender` binary and hydrated in the browser.
+use crate::{
+    components::atoms::{flag::Country, markdown},
+    data::{
+        locales::LocalesStore,
+        resources::{Key, ResourceStore},
+        session::SessionStore,
+    },
+    pages::{about, contact, home},
+    router::Route,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use yew_router::Routable;
+use yewdux::prelude::*;
+
+/// Id of the script element holding the [`Prerendered`] state of the page.
+pub const STATE_ID: &str = "prerendered-state";
+
+/// Routes rendered ahead of time along with the resources they show.
+pub const PAGES: [(Route, Option<&str>); 4] = [
+    (Route::Home, Some(home::RES_KEY)),
+    (Route::Projects, None),
+    (Route::About, Some(about::RES_KEY)),
+    (Route::Contact, Some(contact::RES_KEY)),
+];
+
+/// Resource shown on a prerendered page.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PrerenderedResource {
+    pub key: Key,
+    pub value: String,
+    pub etag: Option<String>,
+}
+
+/// State the page was rendered with, the stores get it before the hydration.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Prerendered {
+    pub path: String,
+    pub lang: Country,
+    /// Texts of the `lang` locale.
+    pub locale: HashMap<String, String>,
+    pub resources: Vec<PrerenderedResource>,
+}
+
+impl Prerendered {
+    /// State of the current page, `None` when it wasn't rendered ahead of time.
+    pub fn read() -> Option<Self> {
+        let element = gloo::utils::document().get_element_by_id(STATE_ID)?;
+        serde_json::from_str(&element.text_content()?).ok()
+    }
+
+    /// Whether the page can be hydrated, the visitor's language and session win over the prerendered ones.
+    /// The server falls back to the home page for the other routes, these are never hydrated.
+    pub fn is_current(&self) -> bool {
+        let path = gloo::utils::window()
+            .location()
+            .pathname()
+            .unwrap_or_default();
+        let (locales_store, session_store) = (
+            Dispatch::<LocalesStore>::new().get(),
+            Dispatch::<SessionStore>::new().get(),
+        );
+        Route::recognize(trim_path(&path)) == Route::recognize(&self.path)
+            && locales_store.curr == self.lang
+            && session_store.user.is_none()
+    }
+
+    /// Puts the state into the stores, so the app renders like it did ahead of time.
+    pub fn seed(&self) {
+        Dispatch::<LocalesStore>::new().reduce_mut(|store| {
+            store.curr = self.lang;
+            store.load(self.lang, self.locale.clone());
+        });
+        Dispatch::<ResourceStore>::new().reduce_mut(|store| {
+            for resource in &self.resources {
+                store.add_or_update_fetched(
+                    &resource.key,
+                    resource.value.clone(),
+                    resource.etag.clone(),
+                );
+            }
+        });
+    }
+
+    /// Prepares the prerendered page for the hydration.
+    pub fn hydrate(&self) {
+        self.seed();
+        markdown::clear_prerendered();
+    }
+
+    /// Removes the prerendered markup, for the pages rendered from scratch.
+    pub fn discard() {
+        gloo::utils::body().set_inner_html("");
+    }
+
+    /// The state as the content of a script element, safe to put into the page.
+    pub fn to_script(&self) -> String {
+        let json = serde_json::to_string(self)
+            .unwrap_or_default()
+            .replace("</", "<\\/");
+        format!(
+            "<script id=\"{}\" type=\"application/json\">{}</script>",
+            STATE_ID, json
+        )
+    }
+
+    /// Renders the app at the `path` with the state.
+    #[cfg(feature = "ssr")]
+    pub async fn render(self) -> String {
+        use crate::{App, AppProps};
+        use yew_router::history::{AnyHistory, History, MemoryHistory};
+
+        yew::ServerRenderer::<App>::with_props(move || {
+            // Runs on the rendering thread, the stores are local to it.
+            self.seed();
+            let history = AnyHistory::from(MemoryHistory::new());
+            history.push(self.path.as_str());
+            AppProps {
+                history: Some(history),
+            }
+        })
+        .render()
+        .await
+    }
+}
+
+/// Path without its trailing slash, added by the servers to the directories.
+fn trim_path(path: &str) -> &str {
+    match path.trim_end_matches('/') {
+        "" => "/",
+        path => path,
+    }
+}
@@ -1,14 +1,20 @@
 use crate::pages::{
     about::About,
-    admin::{admin_panel::AdminPanel, user_management::UserManagement},
+    admin::{
+        admin_panel::AdminPanel, invite_management::InviteManagement,
+        user_management::UserManagement,
+    },
     contact::Contact,
     editor::Editor,
     home::Home,
     login::Login,
     not_found::NotFound,
+    profile::Profile,
     projects::Projects,
     register::Register,
+    search::Search,
 };
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use yew::prelude::*;
 use yew_router::prelude::*;
@@ -27,10 +33,14 @@ pub enum Route {
     Login,
     #[at("/register")]
     Register,
+    #[at("/search")]
+    Search,
     #[at("/admin")]
     AdminPanelRoot,
     #[at("/admin/*")]
     AdminPanel,
+    #[at("/profile/:name")]
+    Profile { name: String },
     #[at("/editor/:key/:lang")]
     Editor { key: String, lang: String },
     #[not_found]
@@ -44,11 +54,19 @@ pub enum AdminRoute {
     AdminPanel,
     #[at("/admin/user_management")]
     UserManagement,
+    #[at("/admin/invites")]
+    Invites,
     #[not_found]
     #[at("/admin/404")]
     NotFound,
 }
 
+/// Query of [`Route::Search`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+}
+
 impl Display for Route {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("{:?}", self))
@@ -63,9 +81,11 @@ pub fn switch(route: Route) -> Html {
         Route::Contact => html! {<Contact />},
         Route::Login => html! {<Login />},
         Route::Register => html! {<Register />},
+        Route::Search => html! {<Search />},
         Route::AdminPanelRoot | Route::AdminPanel => {
             html! { <Switch<AdminRoute> render={admin_switch} />}
         }
+        Route::Profile { name } => html! { <Profile {name} />},
         Route::Editor { key, lang } => html! { <Editor reskey={key} lang={lang} />},
         Route::NotFound => html! {  <NotFound />},
     }
@@ -75,6 +95,7 @@ pub fn admin_switch(route: AdminRoute) -> Html {
     match route {
         AdminRoute::AdminPanel => html! { <AdminPanel />},
         AdminRoute::UserManagement => html! { <UserManagement />},
+        AdminRoute::Invites => html! { <InviteManagement />},
         AdminRoute::NotFound => html! {<NotFound />},
     }
 }
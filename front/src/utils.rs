@@ -61,23 +61,121 @@ pub mod macros {
             use yew_router::prelude::*;
             use $crate::components::atoms::modal::show_error;
             use $crate::router::Route;
-            if let Some(error) = &*$error {
-                if let $crate::api::client::RequestError::Endpoint(401..=403, _) = error {
+            match &*$error {
+                // A refused request (403) keeps the session, it is shown like any other error.
+                Some($crate::api::client::RequestError::Endpoint(401..=402, _)) => {
                     $session_dispatch.reduce(|_| {
                         SessionStore {
                             token: None,
+                            csrf: None,
                             user: None,
+                            profile: None,
                         }
                         .into()
                     });
                     return html! { <Redirect<Route> to={Route::Login} />};
                 }
-                show_error(error.to_string(), $redirect);
+                // The component is gone, there is no one to show it to.
+                Some($crate::api::client::RequestError::Aborted) | None => {}
+                Some(error) => show_error(error.to_string(), $redirect),
             }
         };
     }
 }
 
+pub mod download {
+    use gloo::{
+        file::{Blob, ObjectUrl},
+        timers::callback::Timeout,
+    };
+    use wasm_bindgen::JsCast;
+    use web_sys::HtmlAnchorElement;
+
+    /// Makes the browser save the json as a file.
+    pub fn download_json(filename: &str, json: String) {
+        let url = ObjectUrl::from(Blob::new_with_options(
+            json.as_str(),
+            Some("application/json"),
+        ));
+        let anchor = gloo::utils::document()
+            .create_element("a")
+            .unwrap()
+            .unchecked_into::<HtmlAnchorElement>();
+        anchor.set_href(&url);
+        anchor.set_download(filename);
+        anchor.click();
+        // The url has to outlive the download start.
+        Timeout::new(1000, move || drop(url)).forget();
+    }
+}
+
+pub mod head {
+    use crate::models::{
+        feed::FeedFormat,
+        page_meta::{HeadTag, PageMeta},
+    };
+    use gloo::utils::document;
+    use strum::IntoEnumIterator;
+
+    /// Sets the title of the document and replaces the tags of the previous page with the ones of the `meta`.
+    pub fn apply(meta: &PageMeta) {
+        reset();
+        document().set_title(&meta.document_title());
+        let Some(head) = document().head() else {
+            return;
+        };
+        for tag in meta.head_tags() {
+            let (name, attributes) = tag.attributes();
+            let Ok(element) = document().create_element(name) else {
+                continue;
+            };
+            let _ = element.set_attribute(HeadTag::MARKER, "");
+            for (attribute, value) in attributes {
+                let _ = element.set_attribute(attribute, value);
+            }
+            let _ = head.append_child(&element);
+        }
+    }
+
+    /// Removes the tags of the page, the pages without metadata only keep the site's name.
+    pub fn reset() {
+        document().set_title(PageMeta::SITE_NAME);
+        remove(HeadTag::MARKER);
+    }
+
+    /// Replaces the discovery links of the feeds with the ones of the `lang`.
+    pub fn apply_feeds(api_url: &str, lang: &str) {
+        remove(FeedFormat::MARKER);
+        let Some(head) = document().head() else {
+            return;
+        };
+        for format in FeedFormat::iter() {
+            let Ok(element) = document().create_element("link") else {
+                continue;
+            };
+            let _ = element.set_attribute(FeedFormat::MARKER, "");
+            for (attribute, value) in format.link_attributes(api_url, lang) {
+                let _ = element.set_attribute(attribute, &value);
+            }
+            let _ = head.append_child(&element);
+        }
+    }
+
+    /// Removes the tags with the `marker` attribute.
+    fn remove(marker: &str) {
+        let Ok(tags) = document().query_selector_all(&format!("[{}]", marker)) else {
+            return;
+        };
+        for i in 0..tags.length() {
+            if let Some(tag) = tags.item(i) {
+                if let Some(parent) = tag.parent_node() {
+                    let _ = parent.remove_child(&tag);
+                }
+            }
+        }
+    }
+}
+
 pub mod ext {
     use yew::Callback;
 
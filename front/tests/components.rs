@@ -0,0 +1,242 @@
+#![cfg(target_arch = "wasm32")]
+
+use gloo::timers::future::TimeoutFuture;
+use petompp_web_front::{
+    api::{
+        client::ApiContext,
+        fake::{FakeClient, FakeData},
+    },
+    components::{
+        admin::{
+            comment_moderator::CommentModerator, invite_manager::InviteManager,
+            user_manager::UserManager,
+        },
+        comments::comment_thread::CommentThread,
+        editor::editor::Editor,
+    },
+    data::resources::{Key, ResourceStore},
+    models::{
+        comment::{Comment, CommentStatus, ThreadComment},
+        invite::Invite,
+        page_meta::PageMeta,
+        resource_data::ResourceData,
+        user::User,
+        user_name::UserName,
+    },
+    pages::{page_base::EditablePage, search::SearchResults},
+};
+use wasm_bindgen_test::*;
+use yew::prelude::*;
+use yew_router::BrowserRouter;
+use yewdux::prelude::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Properties, PartialEq)]
+struct HostProps {
+    api: ApiContext,
+    children: Children,
+}
+
+#[function_component(Host)]
+fn host(props: &HostProps) -> Html {
+    html! {
+        <ContextProvider<ApiContext> context={props.api.clone()}>
+            <BrowserRouter>
+                {props.children.clone()}
+            </BrowserRouter>
+        </ContextProvider<ApiContext>>
+    }
+}
+
+/// Renders the component with the fake client and waits for its requests to finish.
+async fn render(fake: &FakeClient, content: Html) -> web_sys::Element {
+    let document = gloo::utils::document();
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+    yew::Renderer::<Host>::with_root_and_props(
+        root.clone(),
+        HostProps {
+            api: ApiContext::new(fake.clone()),
+            children: Children::new(vec![content]),
+        },
+    )
+    .render();
+    TimeoutFuture::new(50).await;
+    root
+}
+
+fn user(id: i32, name: &str) -> User {
+    User {
+        id: Some(id),
+        name: UserName::new(name.to_string()).unwrap(),
+        normalized_name: name.to_lowercase(),
+        ..Default::default()
+    }
+}
+
+fn comment(id: i32, parent_id: Option<i32>, status: CommentStatus, body: &str) -> ThreadComment {
+    ThreadComment::new(
+        Comment {
+            id: Some(id),
+            key: "home".to_string(),
+            parent_id,
+            user_id: 1,
+            body: body.to_string(),
+            status,
+            created_at: chrono::NaiveDate::from_ymd_opt(2023, 10, 29)
+                .unwrap()
+                .and_hms_opt(12, id as u32, 0),
+            ..Default::default()
+        },
+        UserName::new("Alice".to_string()).unwrap(),
+    )
+}
+
+#[wasm_bindgen_test]
+async fn user_manager_lists_users() {
+    let fake = FakeClient::new(FakeData {
+        users: vec![user(1, "Alice"), user(2, "Bob")],
+        ..Default::default()
+    });
+
+    let root = render(&fake, html! { <UserManager /> }).await;
+
+    let text = root.text_content().unwrap_or_default();
+    assert!(text.contains("Alice"));
+    assert!(text.contains("Bob"));
+    assert_eq!(fake.data().calls, vec!["get_users"]);
+}
+
+#[wasm_bindgen_test]
+async fn invite_manager_lists_invites() {
+    let fake = FakeClient::new(FakeData {
+        invites: vec![Invite {
+            id: Some(1),
+            code: "0123456789ab".to_string(),
+            max_uses: 1,
+            ..Default::default()
+        }],
+        ..Default::default()
+    });
+
+    let root = render(&fake, html! { <InviteManager /> }).await;
+
+    assert!(root
+        .text_content()
+        .unwrap_or_default()
+        .contains("0123456789ab"));
+    assert!(fake.data().calls.contains(&"get_invites"));
+}
+
+#[wasm_bindgen_test]
+async fn editor_loads_resource() {
+    let fake = FakeClient::new(FakeData {
+        resources: vec![ResourceData::new_from_lang("home", "en", "# Hello").unwrap()],
+        ..Default::default()
+    });
+    let key = Key {
+        reskey: "home".to_string(),
+        lang: "en".to_string(),
+    };
+
+    render(&fake, html! { <Editor reskey={key.clone()} /> }).await;
+
+    let calls = fake.data().calls.clone();
+    assert!(calls.contains(&"get_resource"));
+    assert!(calls.contains(&"get_resource_keys"));
+    assert_eq!(
+        Dispatch::<ResourceStore>::new().get().get_state(&key),
+        Some(&"# Hello".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+async fn search_results_mark_the_matches() {
+    let fake = FakeClient::new(FakeData {
+        resources: vec![
+            ResourceData::new_from_lang("about-content", "en", "I write Rust").unwrap(),
+            ResourceData::new_from_lang("home-content", "en", "Hello").unwrap(),
+        ],
+        ..Default::default()
+    });
+
+    let root = render(&fake, html! { <SearchResults query={"rust"} /> }).await;
+
+    let marks = root.query_selector_all("mark").unwrap();
+    assert_eq!(marks.length(), 1);
+    assert_eq!(marks.get(0).unwrap().text_content().unwrap(), "Rust");
+    assert_eq!(root.query_selector_all("li").unwrap().length(), 1);
+    assert_eq!(fake.data().calls, vec!["search"]);
+}
+
+#[wasm_bindgen_test]
+async fn editable_page_applies_its_meta() {
+    let fake = FakeClient::new(FakeData {
+        resources: vec![ResourceData::new_from_lang("about-content", "en", "About").unwrap()],
+        metas: vec![PageMeta {
+            title: Some("About".to_string()),
+            description: Some("Who I am".to_string()),
+            ..PageMeta::new("about-content", "en")
+        }],
+        ..Default::default()
+    });
+
+    render(&fake, html! { <EditablePage reskey={"about-content"} /> }).await;
+
+    let document = gloo::utils::document();
+    assert_eq!(document.title(), "About | PetoMPP.NET");
+    let description = document
+        .query_selector("meta[data-page-meta][name=description]")
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        description.get_attribute("content").as_deref(),
+        Some("Who I am")
+    );
+    assert!(fake.data().calls.contains(&"get_page_meta"));
+}
+
+#[wasm_bindgen_test]
+async fn comment_thread_nests_the_approved_replies() {
+    let fake = FakeClient::new(FakeData {
+        comments: vec![
+            comment(1, None, CommentStatus::Approved, "First *thought*"),
+            comment(2, Some(1), CommentStatus::Approved, "A reply"),
+            comment(3, None, CommentStatus::Rejected, "Spam"),
+        ],
+        ..Default::default()
+    });
+
+    let root = render(&fake, html! { <CommentThread reskey={"home"} /> }).await;
+
+    let first = root.query_selector("li").unwrap().unwrap();
+    assert!(first.inner_html().contains("<em>thought</em>"));
+    assert!(first
+        .query_selector("li")
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap_or_default()
+        .contains("A reply"));
+    assert!(!root.text_content().unwrap_or_default().contains("Spam"));
+    assert_eq!(fake.data().calls, vec!["get_comments"]);
+}
+
+#[wasm_bindgen_test]
+async fn comment_moderator_lists_the_queue() {
+    let fake = FakeClient::new(FakeData {
+        comments: vec![
+            comment(1, None, CommentStatus::Pending, "Waiting"),
+            comment(2, None, CommentStatus::Approved, "Shown"),
+        ],
+        ..Default::default()
+    });
+
+    let root = render(&fake, html! { <CommentModerator /> }).await;
+
+    let text = root.text_content().unwrap_or_default();
+    assert!(text.contains("Waiting"));
+    assert!(!text.contains("Shown"));
+    assert!(fake.data().calls.contains(&"get_comment_queue"));
+}
@@ -0,0 +1,85 @@
+//! Pages rendered ahead of time, run with `cargo test --features ssr`.
+#![cfg(feature = "ssr")]
+
+use petompp_web_front::{
+    components::atoms::flag::Country,
+    data::resources::Key,
+    models::sitemap::PUBLIC_PAGES,
+    prerender::{Prerendered, PrerenderedResource, PAGES},
+};
+use yew_router::Routable;
+
+fn state(path: &str, lang: Country, resources: &[(&str, &str)]) -> Prerendered {
+    let locale = match lang {
+        Country::UnitedKingdom => include_str!("../locales/en.yml"),
+        Country::Poland => include_str!("../locales/pl.yml"),
+    };
+    Prerendered {
+        path: path.to_string(),
+        lang,
+        locale: serde_yaml::from_str(locale).unwrap(),
+        resources: resources
+            .iter()
+            .map(|(reskey, value)| PrerenderedResource {
+                key: Key {
+                    reskey: reskey.to_string(),
+                    lang: lang.key().to_string(),
+                },
+                value: value.to_string(),
+                etag: Some("\"1\"".to_string()),
+            })
+            .collect(),
+    }
+}
+
+#[tokio::test]
+async fn page_is_rendered_with_its_resource() {
+    let html = state(
+        "/about",
+        Country::UnitedKingdom,
+        &[("about-content", "# About\n\nI write *Rust*.")],
+    )
+    .render()
+    .await;
+    assert!(html.contains("<h1>About</h1>"), "{}", html);
+    assert!(html.contains("<em>Rust</em>"), "{}", html);
+    // The header is there as well.
+    assert!(html.contains("Projects"), "{}", html);
+}
+
+#[tokio::test]
+async fn page_is_rendered_in_its_language() {
+    let html = state("/projects", Country::Poland, &[]).render().await;
+    assert!(
+        html.contains("Tutaj będę umieszczał swoje projekty."),
+        "{}",
+        html
+    );
+}
+
+#[test]
+fn state_cannot_close_its_script() {
+    let state = state(
+        "/",
+        Country::UnitedKingdom,
+        &[("home-content", "</script><script>alert(1)</script>")],
+    );
+    let script = state.to_script();
+    assert_eq!(script.matches("</script>").count(), 1);
+    assert!(script.ends_with("</script>"));
+    let json = &script[script.find('>').unwrap() + 1..script.len() - "</script>".len()];
+    assert_eq!(serde_json::from_str::<Prerendered>(json).unwrap(), state);
+}
+
+#[test]
+fn public_pages_are_prerendered() {
+    let prerendered = PAGES
+        .iter()
+        .map(|(route, reskey)| (route.to_path(), *reskey))
+        .collect::<Vec<_>>();
+    let public = PUBLIC_PAGES
+        .iter()
+        .map(|page| (page.path.to_string(), page.reskey))
+        .collect::<Vec<_>>();
+    assert_eq!(prerendered, public);
+}
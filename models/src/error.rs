@@ -0,0 +1,523 @@
+use crate::models::password::PasswordRequirements;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub enum Error {
+    AuthError(AuthError),
+    DatabaseError(String),
+    DatabaseConnectionError(String),
+    UserNameTaken(String),
+    UserNotFound(String),
+    InvalidCredentials,
+    UserNotConfirmed(String),
+    RegistrationClosed,
+    InviteCodeRequired,
+    InviteCodeInvalid(String),
+    InviteCodeExpired(String),
+    InviteCodeUsedUp(String),
+    InviteNotFound(String),
+    BundleInvalid(String),
+    BundleVersionUnsupported(u32),
+    CommentNotFound(String),
+    CommentEditWindowClosed(i64),
+    CommentingBanned,
+    ValidationError(ValidationError),
+    Status(u16, String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub enum ValidationError {
+    Username(UsernameValidationError),
+    Password(PasswordRequirements),
+    Query(QueryValidationError),
+    ResourceData(ResourceDataValidationError),
+    Profile(ProfileValidationError),
+    Invite(InviteValidationError),
+    Search(SearchValidationError),
+    PageMeta(PageMetaValidationError),
+    Comment(CommentValidationError),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub enum UsernameValidationError {
+    InvalidLength(i32, i32),
+    InvalidCharacters(Vec<char>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub enum QueryValidationError {
+    InvalidColumn(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub enum ResourceDataValidationError {
+    KeyMismatch(String, String),
+    KeyMissing,
+    ValueMissing,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub enum ProfileValidationError {
+    DisplayNameTooLong(i32),
+    BioTooLong(i32),
+    TooManyLinks(i32),
+    InvalidLink(String),
+    AvatarTooLong(i32),
+    LinkTooLong(i32),
+    AvatarNotUploaded(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub enum InviteValidationError {
+    InvalidMaxUses,
+    ExpiryInPast,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub enum SearchValidationError {
+    QueryEmpty,
+    QueryTooLong(i32),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub enum PageMetaValidationError {
+    LangInvalid(String),
+    TitleTooLong(i32),
+    DescriptionTooLong(i32),
+    InvalidUrl(String),
+    UrlTooLong(i32),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub enum CommentValidationError {
+    BodyEmpty,
+    BodyTooLong(i32),
+    ParentInvalid(i32),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub enum AuthError {
+    MissingClaim(String),
+    InvalidFormat(String),
+    InvalidClaim(String),
+    TokenExpiredS(i64),
+    TokenNotYetValidS(i64),
+    UnknownKey(String),
+    InvalidKey(String),
+    JwtError(String),
+}
+
+impl Error {
+    /// Every [`Error::code`], the front expects a localized message for each of them.
+    pub const CODES: &'static [&'static str] = &[
+        "auth.missing_claim",
+        "auth.invalid_format",
+        "auth.invalid_claim",
+        "auth.token_expired",
+        "auth.token_not_yet_valid",
+        "auth.unknown_key",
+        "auth.invalid_key",
+        "auth.jwt",
+        "database",
+        "database.connection",
+        "user.name_taken",
+        "user.not_found",
+        "user.invalid_credentials",
+        "user.not_confirmed",
+        "registration.closed",
+        "invite.code_required",
+        "invite.code_invalid",
+        "invite.code_expired",
+        "invite.code_used_up",
+        "invite.not_found",
+        "bundle.invalid",
+        "bundle.version_unsupported",
+        "comment.not_found",
+        "comment.edit_window_closed",
+        "comment.banned",
+        "validation.username.invalid_length",
+        "validation.username.invalid_characters",
+        "validation.password",
+        "validation.query.invalid_column",
+        "validation.resource_data.key_mismatch",
+        "validation.resource_data.key_missing",
+        "validation.resource_data.value_missing",
+        "validation.profile.display_name_too_long",
+        "validation.profile.bio_too_long",
+        "validation.profile.too_many_links",
+        "validation.profile.invalid_link",
+        "validation.profile.avatar_too_long",
+        "validation.profile.link_too_long",
+        "validation.profile.avatar_not_uploaded",
+        "validation.invite.invalid_max_uses",
+        "validation.invite.expiry_in_past",
+        "validation.search.query_empty",
+        "validation.search.query_too_long",
+        "validation.page_meta.lang_invalid",
+        "validation.page_meta.title_too_long",
+        "validation.page_meta.description_too_long",
+        "validation.page_meta.invalid_url",
+        "validation.page_meta.url_too_long",
+        "validation.comment.body_empty",
+        "validation.comment.body_too_long",
+        "validation.comment.parent_invalid",
+        "status",
+    ];
+
+    /// Stable identifier of the error, it doesn't change when the variant is renamed.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::AuthError(e) => match e {
+                AuthError::MissingClaim(_) => "auth.missing_claim",
+                AuthError::InvalidFormat(_) => "auth.invalid_format",
+                AuthError::InvalidClaim(_) => "auth.invalid_claim",
+                AuthError::TokenExpiredS(_) => "auth.token_expired",
+                AuthError::TokenNotYetValidS(_) => "auth.token_not_yet_valid",
+                AuthError::UnknownKey(_) => "auth.unknown_key",
+                AuthError::InvalidKey(_) => "auth.invalid_key",
+                AuthError::JwtError(_) => "auth.jwt",
+            },
+            Error::DatabaseError(_) => "database",
+            Error::DatabaseConnectionError(_) => "database.connection",
+            Error::UserNameTaken(_) => "user.name_taken",
+            Error::UserNotFound(_) => "user.not_found",
+            Error::InvalidCredentials => "user.invalid_credentials",
+            Error::UserNotConfirmed(_) => "user.not_confirmed",
+            Error::RegistrationClosed => "registration.closed",
+            Error::InviteCodeRequired => "invite.code_required",
+            Error::InviteCodeInvalid(_) => "invite.code_invalid",
+            Error::InviteCodeExpired(_) => "invite.code_expired",
+            Error::InviteCodeUsedUp(_) => "invite.code_used_up",
+            Error::InviteNotFound(_) => "invite.not_found",
+            Error::BundleInvalid(_) => "bundle.invalid",
+            Error::BundleVersionUnsupported(_) => "bundle.version_unsupported",
+            Error::CommentNotFound(_) => "comment.not_found",
+            Error::CommentEditWindowClosed(_) => "comment.edit_window_closed",
+            Error::CommentingBanned => "comment.banned",
+            Error::ValidationError(e) => match e {
+                ValidationError::Username(e) => match e {
+                    UsernameValidationError::InvalidLength(..) => {
+                        "validation.username.invalid_length"
+                    }
+                    UsernameValidationError::InvalidCharacters(_) => {
+                        "validation.username.invalid_characters"
+                    }
+                },
+                ValidationError::Password(_) => "validation.password",
+                ValidationError::Query(QueryValidationError::InvalidColumn(_)) => {
+                    "validation.query.invalid_column"
+                }
+                ValidationError::ResourceData(e) => match e {
+                    ResourceDataValidationError::KeyMismatch(..) => {
+                        "validation.resource_data.key_mismatch"
+                    }
+                    ResourceDataValidationError::KeyMissing => {
+                        "validation.resource_data.key_missing"
+                    }
+                    ResourceDataValidationError::ValueMissing => {
+                        "validation.resource_data.value_missing"
+                    }
+                },
+                ValidationError::Profile(e) => match e {
+                    ProfileValidationError::DisplayNameTooLong(_) => {
+                        "validation.profile.display_name_too_long"
+                    }
+                    ProfileValidationError::BioTooLong(_) => "validation.profile.bio_too_long",
+                    ProfileValidationError::TooManyLinks(_) => "validation.profile.too_many_links",
+                    ProfileValidationError::InvalidLink(_) => "validation.profile.invalid_link",
+                    ProfileValidationError::AvatarTooLong(_) => {
+                        "validation.profile.avatar_too_long"
+                    }
+                    ProfileValidationError::LinkTooLong(_) => "validation.profile.link_too_long",
+                    ProfileValidationError::AvatarNotUploaded(_) => {
+                        "validation.profile.avatar_not_uploaded"
+                    }
+                },
+                ValidationError::Invite(e) => match e {
+                    InviteValidationError::InvalidMaxUses => "validation.invite.invalid_max_uses",
+                    InviteValidationError::ExpiryInPast => "validation.invite.expiry_in_past",
+                },
+                ValidationError::Search(e) => match e {
+                    SearchValidationError::QueryEmpty => "validation.search.query_empty",
+                    SearchValidationError::QueryTooLong(_) => "validation.search.query_too_long",
+                },
+                ValidationError::PageMeta(e) => match e {
+                    PageMetaValidationError::LangInvalid(_) => "validation.page_meta.lang_invalid",
+                    PageMetaValidationError::TitleTooLong(_) => {
+                        "validation.page_meta.title_too_long"
+                    }
+                    PageMetaValidationError::DescriptionTooLong(_) => {
+                        "validation.page_meta.description_too_long"
+                    }
+                    PageMetaValidationError::InvalidUrl(_) => "validation.page_meta.invalid_url",
+                    PageMetaValidationError::UrlTooLong(_) => "validation.page_meta.url_too_long",
+                },
+                ValidationError::Comment(e) => match e {
+                    CommentValidationError::BodyEmpty => "validation.comment.body_empty",
+                    CommentValidationError::BodyTooLong(_) => "validation.comment.body_too_long",
+                    CommentValidationError::ParentInvalid(_) => "validation.comment.parent_invalid",
+                },
+            },
+            Error::Status(..) => "status",
+        }
+    }
+
+    /// Values of the `%{n}` placeholders in the localized message, in order.
+    pub fn params(&self) -> Vec<String> {
+        match self {
+            Error::AuthError(e) => match e {
+                AuthError::TokenExpiredS(s) | AuthError::TokenNotYetValidS(s) => {
+                    vec![s.to_string()]
+                }
+                AuthError::MissingClaim(s)
+                | AuthError::InvalidFormat(s)
+                | AuthError::InvalidClaim(s)
+                | AuthError::UnknownKey(s)
+                | AuthError::InvalidKey(s)
+                | AuthError::JwtError(s) => vec![s.clone()],
+            },
+            Error::DatabaseError(s)
+            | Error::DatabaseConnectionError(s)
+            | Error::UserNameTaken(s)
+            | Error::UserNotFound(s)
+            | Error::UserNotConfirmed(s)
+            | Error::InviteCodeInvalid(s)
+            | Error::InviteCodeExpired(s)
+            | Error::InviteCodeUsedUp(s)
+            | Error::InviteNotFound(s)
+            | Error::BundleInvalid(s)
+            | Error::CommentNotFound(s) => vec![s.clone()],
+            Error::BundleVersionUnsupported(v) => vec![v.to_string()],
+            Error::CommentEditWindowClosed(minutes) => vec![minutes.to_string()],
+            Error::InvalidCredentials
+            | Error::RegistrationClosed
+            | Error::InviteCodeRequired
+            | Error::CommentingBanned => vec![],
+            Error::ValidationError(e) => match e {
+                ValidationError::Username(UsernameValidationError::InvalidLength(min, max)) => {
+                    vec![min.to_string(), max.to_string()]
+                }
+                ValidationError::Username(UsernameValidationError::InvalidCharacters(chars)) => {
+                    vec![chars
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")]
+                }
+                ValidationError::Password(_) => vec![],
+                ValidationError::Query(QueryValidationError::InvalidColumn(c)) => vec![c.clone()],
+                ValidationError::ResourceData(ResourceDataValidationError::KeyMismatch(
+                    expected,
+                    actual,
+                )) => vec![expected.clone(), actual.clone()],
+                ValidationError::ResourceData(_) => vec![],
+                ValidationError::Profile(e) => match e {
+                    ProfileValidationError::DisplayNameTooLong(max)
+                    | ProfileValidationError::BioTooLong(max)
+                    | ProfileValidationError::TooManyLinks(max)
+                    | ProfileValidationError::AvatarTooLong(max)
+                    | ProfileValidationError::LinkTooLong(max) => vec![max.to_string()],
+                    ProfileValidationError::InvalidLink(link)
+                    | ProfileValidationError::AvatarNotUploaded(link) => vec![link.clone()],
+                },
+                ValidationError::Invite(_) => vec![],
+                ValidationError::Search(SearchValidationError::QueryTooLong(max)) => {
+                    vec![max.to_string()]
+                }
+                ValidationError::Search(SearchValidationError::QueryEmpty) => vec![],
+                ValidationError::PageMeta(e) => match e {
+                    PageMetaValidationError::TitleTooLong(max)
+                    | PageMetaValidationError::DescriptionTooLong(max)
+                    | PageMetaValidationError::UrlTooLong(max) => vec![max.to_string()],
+                    PageMetaValidationError::LangInvalid(value)
+                    | PageMetaValidationError::InvalidUrl(value) => vec![value.clone()],
+                },
+                ValidationError::Comment(e) => match e {
+                    CommentValidationError::BodyEmpty => vec![],
+                    CommentValidationError::BodyTooLong(max) => vec![max.to_string()],
+                    CommentValidationError::ParentInvalid(id) => vec![id.to_string()],
+                },
+            },
+            Error::Status(_, message) => vec![message.clone()],
+        }
+    }
+
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Error::AuthError(AuthError::JwtError(_)) => 500,
+            Error::AuthError(_) => 400,
+            Error::DatabaseError(_) => 500,
+            Error::DatabaseConnectionError(_) => 500,
+            Error::UserNameTaken(_) => 400,
+            Error::UserNotFound(_) => 404,
+            Error::InvalidCredentials => 401,
+            Error::UserNotConfirmed(_) => 402,
+            Error::RegistrationClosed => 403,
+            Error::InviteCodeRequired => 400,
+            Error::InviteCodeInvalid(_) => 400,
+            Error::InviteCodeExpired(_) => 400,
+            Error::InviteCodeUsedUp(_) => 400,
+            Error::InviteNotFound(_) => 404,
+            Error::BundleInvalid(_) => 400,
+            Error::BundleVersionUnsupported(_) => 400,
+            Error::CommentNotFound(_) => 404,
+            Error::CommentEditWindowClosed(_) => 403,
+            Error::CommentingBanned => 403,
+            Error::ValidationError(_) => 400,
+            Error::Status(status, _) => *status,
+        }
+    }
+
+    /// Short English summary of the error kind, for clients without a localized message.
+    pub fn title(&self) -> &'static str {
+        match self {
+            Error::AuthError(AuthError::JwtError(_)) => "Token could not be processed",
+            Error::AuthError(_) => "Authentication failed",
+            Error::DatabaseError(_) | Error::DatabaseConnectionError(_) => "Database error",
+            Error::UserNameTaken(_) => "Username is already taken",
+            Error::UserNotFound(_) => "User not found",
+            Error::InvalidCredentials => "Invalid credentials",
+            Error::UserNotConfirmed(_) => "User is not confirmed",
+            Error::RegistrationClosed => "Registration is closed",
+            Error::InviteCodeRequired => "Invite code is required",
+            Error::InviteCodeInvalid(_)
+            | Error::InviteCodeExpired(_)
+            | Error::InviteCodeUsedUp(_) => "Invite code can't be used",
+            Error::InviteNotFound(_) => "Invite not found",
+            Error::BundleInvalid(_) | Error::BundleVersionUnsupported(_) => {
+                "Content bundle can't be imported"
+            }
+            Error::CommentNotFound(_) => "Comment not found",
+            Error::CommentEditWindowClosed(_) => "Comment can't be edited anymore",
+            Error::CommentingBanned => "Commenting is not allowed",
+            Error::ValidationError(_) => "Validation failed",
+            Error::Status(..) => "Request failed",
+        }
+    }
+}
+
+#[cfg(feature = "api")]
+impl Error {
+    pub fn status(&self) -> rocket::http::Status {
+        rocket::http::Status::from_code(self.status_code())
+            .unwrap_or(rocket::http::Status::InternalServerError)
+    }
+}
+
+/// [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807) problem details of an [`Error`],
+/// sent instead of the `{ status, data }` envelope when `application/problem+json` is accepted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct Problem {
+    #[serde(rename = "type", default = "Problem::about_blank")]
+    pub kind: String,
+    pub title: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<String>,
+    /// The error itself, `None` when it is unknown to the reading side.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "Problem::known_error"
+    )]
+    pub error: Option<Error>,
+}
+
+impl Problem {
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+
+    fn about_blank() -> String {
+        "about:blank".to_string()
+    }
+
+    fn known_error<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Error>, D::Error> {
+        Ok(Option::<serde_json::Value>::deserialize(deserializer)?
+            .and_then(|value| serde_json::from_value(value).ok()))
+    }
+
+    /// The error, or a [`Error::Status`] with the problem's description when it's unknown.
+    pub fn into_error(self) -> Error {
+        match self.error {
+            Some(error) => error,
+            None => Error::Status(self.status, self.detail.unwrap_or(self.title)),
+        }
+    }
+}
+
+impl From<Error> for Problem {
+    fn from(value: Error) -> Self {
+        Self {
+            kind: Self::about_blank(),
+            title: value.title().to_string(),
+            status: value.status_code(),
+            detail: match &value {
+                Error::Status(_, message) => Some(message.clone()),
+                _ => None,
+            },
+            code: value.code().to_string(),
+            params: value.params(),
+            error: Some(value),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self))
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<AuthError> for Error {
+    fn from(value: AuthError) -> Self {
+        Error::AuthError(value)
+    }
+}
+
+#[cfg(feature = "api")]
+impl From<r2d2::Error> for Error {
+    fn from(e: r2d2::Error) -> Self {
+        Self::DatabaseConnectionError(e.to_string())
+    }
+}
+
+#[cfg(feature = "api")]
+impl From<diesel::result::Error> for Error {
+    fn from(e: diesel::result::Error) -> Self {
+        Self::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(feature = "api")]
+impl From<rocket::http::Status> for Error {
+    fn from(value: rocket::http::Status) -> Self {
+        Error::Status(value.code, value.to_string())
+    }
+}
+
+impl Display for AuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self))
+    }
+}
+
+impl std::error::Error for AuthError {}
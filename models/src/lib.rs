@@ -0,0 +1,9 @@
+//! Types sent between the API and the front, shared so both sides always agree on the format.
+//! The `api` feature adds the database, request and OpenAPI implementations,
+//! the `postgres` and `sqlite` features the backends they can be stored in.
+pub mod error;
+pub mod models;
+#[cfg(feature = "api")]
+pub mod schema;
+#[cfg(feature = "api")]
+pub mod sql;
@@ -0,0 +1,88 @@
+use super::resource_data::ResourceData;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+/// Version of the bundle format, increased on breaking changes.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Portable snapshot of the site content.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct Bundle {
+    pub version: u32,
+    pub created_at: chrono::NaiveDateTime,
+    /// Storage url the blob paths are relative to, rewritten to the target storage on import.
+    pub storage_url: String,
+    pub resources: Vec<ResourceData>,
+    pub blobs: Vec<BundleBlob>,
+    /// Paths of the linked blobs that weren't found in the storage, left out of the bundle.
+    #[serde(default)]
+    pub missing_blobs: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct BundleBlob {
+    pub path: String,
+    pub content_type: String,
+    /// Base64 encoded content.
+    pub data: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter, Display)]
+#[cfg_attr(feature = "api", derive(rocket::FromFormField, utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// Existing resources are left untouched.
+    #[default]
+    Skip,
+    /// Languages present in the bundle replace the existing values.
+    Overwrite,
+    /// Only languages missing in the existing resource are filled.
+    Merge,
+}
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct ImportSummary {
+    pub dry_run: bool,
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub skipped: Vec<String>,
+    pub blobs: usize,
+}
+
+pub enum Resolution {
+    Create(ResourceData),
+    Update(ResourceData),
+    Skip,
+}
+
+impl ConflictStrategy {
+    /// Decides what happens with the incoming resource, language by language.
+    pub fn resolve(&self, existing: Option<&ResourceData>, incoming: ResourceData) -> Resolution {
+        let Some(existing) = existing else {
+            return Resolution::Create(incoming);
+        };
+        let pick = |current: &Option<String>, new: Option<String>| match self {
+            ConflictStrategy::Skip => None,
+            ConflictStrategy::Overwrite => new.filter(|n| Some(n) != current.as_ref()),
+            ConflictStrategy::Merge => match current.as_ref().is_some_and(|c| !c.is_empty()) {
+                true => None,
+                false => new.filter(|n| !n.is_empty()),
+            },
+        };
+        let en = pick(&existing.en, incoming.en);
+        let pl = pick(&existing.pl, incoming.pl);
+        match (&en, &pl) {
+            (None, None) => Resolution::Skip,
+            _ => Resolution::Update(ResourceData {
+                key: incoming.key,
+                en,
+                pl,
+                updated_at: None,
+            }),
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Discussion under the editable pages, moderated before anyone else can read it.
+use super::user_name::UserName;
+use crate::error::{CommentValidationError, Error, ValidationError};
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+/// Stage of the moderation, only the approved comments are shown to everyone.
+#[derive(Default, Debug, Serialize, Deserialize, Clone, Copy, EnumIter, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "api",
+    derive(
+        diesel::AsExpression,
+        diesel::FromSqlRow,
+        num_derive::FromPrimitive,
+        utoipa::ToSchema
+    ),
+    diesel(sql_type = diesel::sql_types::Integer)
+)]
+pub enum CommentStatus {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[cfg(feature = "api")]
+mod sql {
+    use super::CommentStatus;
+    use diesel::{backend::Backend, deserialize::FromSql, serialize::ToSql, sql_types::Integer};
+    use num_traits::FromPrimitive;
+
+    impl<DB> ToSql<Integer, DB> for CommentStatus
+    where
+        DB: Backend,
+        i32: ToSql<Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            let value: &'static i32 = match self {
+                CommentStatus::Pending => &0,
+                CommentStatus::Approved => &1,
+                CommentStatus::Rejected => &2,
+            };
+            <i32 as ToSql<Integer, DB>>::to_sql(value, out)
+        }
+    }
+
+    impl<DB> FromSql<Integer, DB> for CommentStatus
+    where
+        DB: Backend,
+        i32: FromSql<Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            let value = <i32 as FromSql<Integer, DB>>::from_sql(bytes)?;
+            FromPrimitive::from_i32(value).ok_or(Box::new(
+                diesel::result::Error::DeserializationError("Invalid comment status".into()),
+            ))
+        }
+    }
+}
+
+/// Comment on the page showing the resource `key`, a reply when it has a parent.
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+    feature = "api",
+    derive(diesel::Queryable, diesel::Insertable, utoipa::ToSchema),
+    diesel(table_name = crate::schema::comments)
+)]
+pub struct Comment {
+    #[cfg_attr(feature = "api", diesel(deserialize_as = i32))]
+    pub id: Option<i32>,
+    pub key: String,
+    pub parent_id: Option<i32>,
+    pub user_id: i32,
+    /// Markdown, empty in the threads once the comment is deleted.
+    pub body: String,
+    pub status: CommentStatus,
+    #[cfg_attr(feature = "api", diesel(deserialize_as = chrono::NaiveDateTime))]
+    pub created_at: Option<NaiveDateTime>,
+    pub edited_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+/// Body of a new comment or of an edit, the parent of an edited one doesn't change.
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct CommentRequest {
+    pub body: String,
+    #[serde(default)]
+    pub parent_id: Option<i32>,
+}
+
+/// What a moderator does with a pending comment, banning rejects all pending comments of the author.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter, Display)]
+#[cfg_attr(feature = "api", derive(rocket::FromFormField, utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ModerationAction {
+    Approve,
+    Reject,
+    Ban,
+}
+
+impl Comment {
+    pub const MAX_BODY_LENGTH: usize = 2000;
+    /// How long after posting the author can still change the comment.
+    pub const EDIT_WINDOW_MINUTES: i64 = 15;
+
+    /// Trimmed body of the `request`, checked against the limits.
+    pub fn validate_body(request: &CommentRequest) -> Result<String, Error> {
+        let body = request.body.trim();
+        if body.is_empty() {
+            return Err(Error::ValidationError(ValidationError::Comment(
+                CommentValidationError::BodyEmpty,
+            )));
+        }
+        if body.chars().count() > Self::MAX_BODY_LENGTH {
+            return Err(Error::ValidationError(ValidationError::Comment(
+                CommentValidationError::BodyTooLong(Self::MAX_BODY_LENGTH as i32),
+            )));
+        }
+        Ok(body.to_string())
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Whether the author can still edit the comment at `now`.
+    pub fn is_editable(&self, now: NaiveDateTime) -> bool {
+        !self.is_deleted()
+            && self
+                .created_at
+                .is_some_and(|c| now < c + Duration::minutes(Self::EDIT_WINDOW_MINUTES))
+    }
+
+    /// Whether the `parent` can be replied to with this comment.
+    pub fn can_reply_to(&self, parent: &Comment) -> bool {
+        parent.key == self.key && parent.status == CommentStatus::Approved && !parent.is_deleted()
+    }
+}
+
+/// Comment as shown in a thread, with its author and the sanitized HTML of the body.
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct ThreadComment {
+    pub comment: Comment,
+    pub author: UserName,
+    pub html: String,
+}
+
+impl ThreadComment {
+    /// Deleted comments keep their place for the replies, without the content.
+    pub fn new(comment: Comment, author: UserName) -> Self {
+        match comment.is_deleted() {
+            true => Self {
+                comment: Comment {
+                    body: String::new(),
+                    ..comment
+                },
+                author,
+                html: String::new(),
+            },
+            false => Self {
+                html: render(&comment.body),
+                comment,
+                author,
+            },
+        }
+    }
+
+    /// Replies to the comment with the `parent` id, the top-level comments for `None`, oldest first.
+    pub fn replies(comments: &[Self], parent: Option<i32>) -> Vec<&Self> {
+        let mut replies = comments
+            .iter()
+            .filter(|c| c.comment.parent_id == parent)
+            .collect::<Vec<_>>();
+        replies.sort_by_key(|c| c.comment.created_at);
+        replies
+    }
+}
+
+/// HTML of the comment's markdown.
+/// Raw HTML, images and headings are not parsed, links with unsafe protocols are dropped
+/// and the rest are marked as user content the site doesn't vouch for.
+pub fn render(markdown: &str) -> String {
+    let options = markdown::Options {
+        parse: markdown::ParseOptions {
+            constructs: markdown::Constructs {
+                html_flow: false,
+                html_text: false,
+                label_start_image: false,
+                heading_atx: false,
+                heading_setext: false,
+                ..markdown::Constructs::default()
+            },
+            ..markdown::ParseOptions::default()
+        },
+        compile: markdown::CompileOptions::default(),
+    };
+    markdown::to_html_with_options(markdown, &options)
+        .unwrap_or_default()
+        // Any other `<a` is escaped, the raw HTML is not parsed.
+        .replace("<a href=", "<a rel=\"nofollow ugc\" href=")
+}
@@ -0,0 +1,263 @@
+//! Feeds of the content updates, in the formats the feed readers understand.
+use super::{
+    page_meta::{escape, PageMeta},
+    sitemap::page_url,
+};
+use chrono::NaiveDateTime;
+use serde_json::json;
+use strum::EnumIter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum FeedFormat {
+    Atom,
+    Rss,
+    Json,
+}
+
+impl FeedFormat {
+    /// Attribute marking the discovery links of the feeds, they are replaced with the language.
+    pub const MARKER: &'static str = "data-feed";
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Atom => "Atom",
+            Self::Rss => "RSS",
+            Self::Json => "JSON Feed",
+        }
+    }
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Atom => "atom.xml",
+            Self::Rss => "rss.xml",
+            Self::Json => "feed.json",
+        }
+    }
+
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        match file_name {
+            "atom.xml" => Some(Self::Atom),
+            "rss.xml" => Some(Self::Rss),
+            "feed.json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::Atom => "application/atom+xml",
+            Self::Rss => "application/rss+xml",
+            Self::Json => "application/feed+json",
+        }
+    }
+
+    /// Path of the feed of the `lang` at the API.
+    pub fn path(self, lang: &str) -> String {
+        format!("/api/v1/feeds/{}/{}", lang, self.file_name())
+    }
+
+    /// URL of the feed of the `lang` at the API at `api_url`.
+    pub fn url(self, api_url: &str, lang: &str) -> String {
+        format!("{}{}", api_url.trim_end_matches('/'), self.path(lang))
+    }
+
+    /// Attributes of the `<link rel="alternate">` the feed of the API at `api_url` is discovered with.
+    pub fn link_attributes(self, api_url: &str, lang: &str) -> [(&'static str, String); 4] {
+        [
+            ("rel", "alternate".to_string()),
+            ("type", self.media_type().to_string()),
+            (
+                "title",
+                format!("{} ({})", PageMeta::SITE_NAME, self.name()),
+            ),
+            ("href", self.url(api_url, lang)),
+        ]
+    }
+
+    /// The discovery link as HTML, for the pages rendered on the server.
+    pub fn link_html(self, api_url: &str, lang: &str) -> String {
+        let attributes = self
+            .link_attributes(api_url, lang)
+            .iter()
+            .map(|(name, value)| format!(" {}=\"{}\"", name, escape(value)))
+            .collect::<String>();
+        format!("<link {}{}>", Self::MARKER, attributes)
+    }
+}
+
+/// Page of the site in a feed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedEntry {
+    pub path: String,
+    pub title: String,
+    /// HTML of the beginning of the page.
+    pub summary: String,
+    pub updated: NaiveDateTime,
+}
+
+/// Recent updates of the site in one language.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feed {
+    pub lang: String,
+    pub site_url: String,
+    /// Public URL of the API serving the feed.
+    pub api_url: String,
+    /// Newest first.
+    pub entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    pub const MAX_ENTRIES: usize = 20;
+    const RFC_3339: &'static str = "%Y-%m-%dT%H:%M:%SZ";
+    const RFC_822: &'static str = "%a, %d %b %Y %H:%M:%S GMT";
+
+    /// Keeps the [`Feed::MAX_ENTRIES`] most recently updated `entries`.
+    pub fn new(lang: &str, site_url: &str, api_url: &str, mut entries: Vec<FeedEntry>) -> Self {
+        entries.sort_by(|a, b| b.updated.cmp(&a.updated));
+        entries.truncate(Self::MAX_ENTRIES);
+        Self {
+            lang: lang.to_string(),
+            site_url: site_url.to_string(),
+            api_url: api_url.to_string(),
+            entries,
+        }
+    }
+
+    /// Time of the latest update, the epoch for an empty feed.
+    pub fn updated(&self) -> NaiveDateTime {
+        self.entries
+            .first()
+            .map(|entry| entry.updated)
+            .unwrap_or_default()
+    }
+
+    pub fn render(&self, format: FeedFormat) -> String {
+        match format {
+            FeedFormat::Atom => self.to_atom(),
+            FeedFormat::Rss => self.to_rss(),
+            FeedFormat::Json => self.to_json(),
+        }
+    }
+
+    fn description(&self) -> String {
+        match self.lang.as_str() {
+            "pl" => format!("Aktualizacje {}", PageMeta::SITE_NAME),
+            _ => format!("Updates of {}", PageMeta::SITE_NAME),
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        page_url(&self.site_url, path, Some(&self.lang))
+    }
+
+    fn self_url(&self, format: FeedFormat) -> String {
+        format.url(&self.api_url, &self.lang)
+    }
+
+    pub fn to_atom(&self) -> String {
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let url = escape(&self.url(&entry.path));
+                format!(
+                    "  <entry>\n    <id>{}</id>\n    <title>{}</title>\n    <link rel=\"alternate\" href=\"{}\"/>\n    <updated>{}</updated>\n    <summary type=\"html\">{}</summary>\n  </entry>\n",
+                    url,
+                    escape(&entry.title),
+                    url,
+                    entry.updated.format(Self::RFC_3339),
+                    escape(&entry.summary)
+                )
+            })
+            .collect::<String>();
+        let home = escape(&self.url("/"));
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\" xml:lang=\"{}\">\n  <id>{}</id>\n  <title>{}</title>\n  <subtitle>{}</subtitle>\n  <link rel=\"alternate\" href=\"{}\"/>\n  <link rel=\"self\" href=\"{}\"/>\n  <updated>{}</updated>\n  <author><name>{}</name></author>\n{}</feed>\n",
+            escape(&self.lang),
+            home,
+            PageMeta::SITE_NAME,
+            escape(&self.description()),
+            home,
+            escape(&self.self_url(FeedFormat::Atom)),
+            self.updated().format(Self::RFC_3339),
+            PageMeta::SITE_NAME,
+            entries
+        )
+    }
+
+    pub fn to_rss(&self) -> String {
+        let items = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let url = escape(&self.url(&entry.path));
+                format!(
+                    "    <item>\n      <title>{}</title>\n      <link>{}</link>\n      <guid isPermaLink=\"true\">{}</guid>\n      <pubDate>{}</pubDate>\n      <description>{}</description>\n    </item>\n",
+                    escape(&entry.title),
+                    url,
+                    url,
+                    entry.updated.format(Self::RFC_822),
+                    escape(&entry.summary)
+                )
+            })
+            .collect::<String>();
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n  <channel>\n    <title>{}</title>\n    <link>{}</link>\n    <description>{}</description>\n    <language>{}</language>\n    <lastBuildDate>{}</lastBuildDate>\n    <atom:link href=\"{}\" rel=\"self\" type=\"{}\"/>\n{}  </channel>\n</rss>\n",
+            PageMeta::SITE_NAME,
+            escape(&self.url("/")),
+            escape(&self.description()),
+            escape(&self.lang),
+            self.updated().format(Self::RFC_822),
+            escape(&self.self_url(FeedFormat::Rss)),
+            FeedFormat::Rss.media_type(),
+            items
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        let items = self
+            .entries
+            .iter()
+            .map(|entry| {
+                json!({
+                    "id": self.url(&entry.path),
+                    "url": self.url(&entry.path),
+                    "title": entry.title,
+                    "content_html": entry.summary,
+                    "date_modified": entry.updated.format(Self::RFC_3339).to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": PageMeta::SITE_NAME,
+            "description": self.description(),
+            "home_page_url": self.url("/"),
+            "feed_url": self.self_url(FeedFormat::Json),
+            "language": self.lang,
+            "items": items,
+        })
+        .to_string()
+    }
+}
+
+/// Text of the first heading of the `markdown`.
+pub fn title(markdown: &str) -> Option<String> {
+    markdown
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with('#'))
+        .map(|line| line.trim_start_matches('#').trim().to_string())
+        .filter(|title| !title.is_empty())
+}
+
+/// The first paragraph of the `markdown` rendered to HTML, the raw HTML in it is escaped.
+pub fn summary(markdown: &str) -> String {
+    let markdown = markdown.replace("\r\n", "\n");
+    markdown
+        .split("\n\n")
+        .map(str::trim)
+        .find(|block| !block.is_empty() && !block.starts_with(['#', '<', '!', '`', '|']))
+        .map(|block| markdown::to_html(block).trim().to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,90 @@
+use super::role::Role;
+use crate::error::Error;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+    feature = "api",
+    derive(diesel::Queryable, diesel::Insertable, utoipa::ToSchema),
+    diesel(table_name = crate::schema::invites)
+)]
+pub struct Invite {
+    #[cfg_attr(feature = "api", diesel(deserialize_as = i32))]
+    pub id: Option<i32>,
+    pub code: String,
+    pub role: Role,
+    pub max_uses: i32,
+    pub uses: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_by: i32,
+    #[cfg_attr(feature = "api", diesel(deserialize_as = chrono::NaiveDateTime))]
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct InviteRequest {
+    pub max_uses: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub role: Role,
+}
+
+impl Default for InviteRequest {
+    fn default() -> Self {
+        Self {
+            max_uses: 1,
+            expires_at: None,
+            role: Role::User,
+        }
+    }
+}
+
+impl Invite {
+    #[cfg(feature = "api")]
+    pub fn new(request: InviteRequest, created_by: i32) -> Result<Self, Error> {
+        use crate::error::{InviteValidationError, ValidationError};
+
+        if request.max_uses < 1 {
+            return Err(Error::ValidationError(ValidationError::Invite(
+                InviteValidationError::InvalidMaxUses,
+            )));
+        }
+        if let Some(expires_at) = request.expires_at {
+            if expires_at <= chrono::Utc::now().naive_utc() {
+                return Err(Error::ValidationError(ValidationError::Invite(
+                    InviteValidationError::ExpiryInPast,
+                )));
+            }
+        }
+        Ok(Self {
+            code: generate_code(),
+            role: request.role,
+            max_uses: request.max_uses,
+            expires_at: request.expires_at,
+            created_by,
+            ..Default::default()
+        })
+    }
+
+    /// Checks if the invite can still be used to register an account.
+    pub fn check(&self) -> Result<(), Error> {
+        if let Some(expires_at) = self.expires_at {
+            if expires_at <= chrono::Utc::now().naive_utc() {
+                return Err(Error::InviteCodeExpired(self.code.clone()));
+            }
+        }
+        if self.uses >= self.max_uses {
+            return Err(Error::InviteCodeUsedUp(self.code.clone()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "api")]
+fn generate_code() -> String {
+    let mut rng = urandom::csprng();
+    let code: [u8; 12] = rng.next();
+    code.iter().map(|x| format!("{:02x}", x)).collect()
+}
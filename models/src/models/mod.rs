@@ -0,0 +1,17 @@
+pub mod bundle;
+pub mod comment;
+pub mod credentials;
+pub mod feed;
+pub mod invite;
+pub mod page_meta;
+pub mod password;
+pub mod profile;
+pub mod registration;
+pub mod resource_data;
+pub mod role;
+pub mod search;
+pub mod sitemap;
+pub mod upload;
+pub mod user;
+pub mod user_export;
+pub mod user_name;
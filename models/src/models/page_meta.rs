@@ -0,0 +1,217 @@
+use super::profile::is_http_url;
+use crate::error::{Error, PageMetaValidationError, ValidationError};
+use serde::{Deserialize, Serialize};
+
+/// Metadata of an editable page for the search engines and the link previews,
+/// set for every resource key and language.
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+    feature = "api",
+    derive(
+        diesel::Queryable,
+        diesel::Insertable,
+        diesel::AsChangeset,
+        utoipa::ToSchema
+    ),
+    diesel(
+        table_name = crate::schema::page_meta,
+        primary_key(key, lang),
+        treat_none_as_null = true
+    )
+)]
+pub struct PageMeta {
+    #[serde(default)]
+    pub key: String,
+    #[serde(default)]
+    pub lang: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Absolute URL the page should be indexed under.
+    pub canonical: Option<String>,
+    /// Absolute URL of the image shown in the link previews.
+    pub image: Option<String>,
+}
+
+impl PageMeta {
+    pub const SITE_NAME: &'static str = "PetoMPP.NET";
+    pub const MAX_TITLE_LENGTH: usize = 128;
+    pub const MAX_DESCRIPTION_LENGTH: usize = 320;
+    /// Limit of the `canonical` and `image` columns.
+    pub const MAX_URL_LENGTH: usize = 255;
+    const LANGS: [&'static str; 2] = ["en", "pl"];
+
+    /// Empty metadata, the pages without any use the site's defaults.
+    pub fn new(key: impl Into<String>, lang: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            lang: lang.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if !Self::LANGS.contains(&self.lang.as_str()) {
+            return Err(Error::ValidationError(ValidationError::PageMeta(
+                PageMetaValidationError::LangInvalid(self.lang.clone()),
+            )));
+        }
+        if let Some(title) = &self.title {
+            if title.chars().count() > Self::MAX_TITLE_LENGTH {
+                return Err(Error::ValidationError(ValidationError::PageMeta(
+                    PageMetaValidationError::TitleTooLong(Self::MAX_TITLE_LENGTH as i32),
+                )));
+            }
+        }
+        if let Some(description) = &self.description {
+            if description.chars().count() > Self::MAX_DESCRIPTION_LENGTH {
+                return Err(Error::ValidationError(ValidationError::PageMeta(
+                    PageMetaValidationError::DescriptionTooLong(
+                        Self::MAX_DESCRIPTION_LENGTH as i32,
+                    ),
+                )));
+            }
+        }
+        let urls = || [&self.canonical, &self.image].into_iter().flatten();
+        if urls().any(|url| url.chars().count() > Self::MAX_URL_LENGTH) {
+            return Err(Error::ValidationError(ValidationError::PageMeta(
+                PageMetaValidationError::UrlTooLong(Self::MAX_URL_LENGTH as i32),
+            )));
+        }
+        if let Some(url) = urls().find(|url| !is_http_url(url)) {
+            return Err(Error::ValidationError(ValidationError::PageMeta(
+                PageMetaValidationError::InvalidUrl(url.clone()),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Trims the values and replaces empty ones with `None`.
+    pub fn normalized(self) -> Self {
+        let normalize =
+            |v: Option<String>| v.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        Self {
+            key: self.key,
+            lang: self.lang,
+            title: normalize(self.title),
+            description: normalize(self.description),
+            canonical: normalize(self.canonical),
+            image: normalize(self.image),
+        }
+    }
+
+    /// Title of the document, just the site's name for the pages without one.
+    pub fn document_title(&self) -> String {
+        match &self.title {
+            Some(title) => format!("{} | {}", title, Self::SITE_NAME),
+            None => Self::SITE_NAME.to_string(),
+        }
+    }
+
+    /// Tags of the document head describing the page, Open Graph and Twitter card included.
+    pub fn head_tags(&self) -> Vec<HeadTag> {
+        let title = self.title.as_deref().unwrap_or(Self::SITE_NAME);
+        let locale = match self.lang.as_str() {
+            "pl" => "pl_PL",
+            _ => "en_GB",
+        };
+        let card = match self.image {
+            Some(_) => "summary_large_image",
+            None => "summary",
+        };
+        let mut tags = vec![
+            HeadTag::property("og:site_name", Self::SITE_NAME),
+            HeadTag::property("og:type", "website"),
+            HeadTag::property("og:locale", locale),
+            HeadTag::property("og:title", title),
+            HeadTag::name("twitter:card", card),
+            HeadTag::name("twitter:title", title),
+        ];
+        if let Some(description) = &self.description {
+            tags.push(HeadTag::name("description", description));
+            tags.push(HeadTag::property("og:description", description));
+            tags.push(HeadTag::name("twitter:description", description));
+        }
+        if let Some(canonical) = &self.canonical {
+            tags.push(HeadTag::Link {
+                rel: "canonical",
+                href: canonical.clone(),
+            });
+            tags.push(HeadTag::property("og:url", canonical));
+        }
+        if let Some(image) = &self.image {
+            tags.push(HeadTag::property("og:image", image));
+            tags.push(HeadTag::name("twitter:image", image));
+        }
+        tags
+    }
+}
+
+/// Tag of the document head made from a [`PageMeta`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeadTag {
+    /// `<meta>` with the `key` in its `attribute`, `name` or `property` for the Open Graph ones.
+    Meta {
+        attribute: &'static str,
+        key: &'static str,
+        content: String,
+    },
+    Link {
+        rel: &'static str,
+        href: String,
+    },
+}
+
+impl HeadTag {
+    /// Attribute marking the tags of the page, they are replaced on navigation.
+    pub const MARKER: &'static str = "data-page-meta";
+
+    fn name(key: &'static str, content: &str) -> Self {
+        Self::Meta {
+            attribute: "name",
+            key,
+            content: content.to_string(),
+        }
+    }
+
+    fn property(key: &'static str, content: &str) -> Self {
+        Self::Meta {
+            attribute: "property",
+            key,
+            content: content.to_string(),
+        }
+    }
+
+    /// Name of the element and its attributes.
+    pub fn attributes(&self) -> (&'static str, Vec<(&'static str, &str)>) {
+        match self {
+            Self::Meta {
+                attribute,
+                key,
+                content,
+            } => (
+                "meta",
+                vec![(*attribute, *key), ("content", content.as_str())],
+            ),
+            Self::Link { rel, href } => ("link", vec![("rel", *rel), ("href", href.as_str())]),
+        }
+    }
+
+    /// The tag as HTML, for the pages rendered on the server.
+    pub fn to_html(&self) -> String {
+        let (tag, attributes) = self.attributes();
+        let attributes = attributes
+            .iter()
+            .map(|(name, value)| format!(" {}=\"{}\"", name, escape(value)))
+            .collect::<String>();
+        format!("<{} {}{}>", tag, Self::MARKER, attributes)
+    }
+}
+
+/// Escapes the `value` for the HTML text and the quoted attributes.
+pub fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
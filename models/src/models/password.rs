@@ -0,0 +1,149 @@
+use crate::error::{Error, ValidationError};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "api")]
+pub use hash::Password;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct PasswordRequirements {
+    pub min_length: i32,
+    pub passes_required: i32,
+    pub numbers: bool,
+    pub uppercase: bool,
+    pub lowercase: bool,
+    pub special: bool,
+}
+
+impl PasswordRequirements {
+    pub fn validate(&self, password: &str) -> Result<(), Error> {
+        if password.len() < self.min_length as usize {
+            return Err(Error::ValidationError(ValidationError::Password(*self)));
+        }
+        #[allow(clippy::type_complexity)]
+        let checks: Vec<Box<dyn Fn(&str) -> bool>> = vec![
+            Box::new(|s: &str| self.numbers && s.chars().any(|c| c.is_numeric())),
+            Box::new(|s: &str| self.uppercase && s.chars().any(|c| c.is_uppercase())),
+            Box::new(|s: &str| self.lowercase && s.chars().any(|c| c.is_lowercase())),
+            Box::new(|s: &str| self.special && s.chars().any(|c| !c.is_alphanumeric())),
+        ];
+
+        let mut passed = 0;
+        for check in checks {
+            if check(password) {
+                passed += 1;
+            }
+            if passed >= self.passes_required as usize {
+                return Ok(());
+            }
+        }
+        Err(Error::ValidationError(ValidationError::Password(*self)))
+    }
+}
+
+impl Default for PasswordRequirements {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            passes_required: 3,
+            numbers: true,
+            uppercase: true,
+            lowercase: true,
+            special: true,
+        }
+    }
+}
+
+/// Salted password hash, only handled by the API.
+#[cfg(feature = "api")]
+mod hash {
+    use super::PasswordRequirements;
+    use crate::{error::Error, sql::OwnedText};
+    use diesel::{
+        backend::Backend, deserialize::FromSql, expression::AsExpression, serialize::ToSql,
+        sql_types::Text, FromSqlRow,
+    };
+    use serde::{Deserialize, Serialize};
+    use sha2::{Digest, Sha256};
+
+    #[derive(
+        Debug, Clone, PartialEq, Serialize, Deserialize, Default, AsExpression, FromSqlRow,
+    )]
+    #[diesel(sql_type = Text)]
+    pub struct Password {
+        hash: String,
+        salt: String,
+    }
+
+    impl Password {
+        pub fn new(password: String, requirements: &PasswordRequirements) -> Result<Self, Error> {
+            requirements.validate(&password)?;
+            let mut rng = urandom::csprng();
+            let salt: [u8; 16] = rng.next();
+            let salt = salt.iter().map(|x| format!("{:x}", x)).collect::<String>();
+            let salty_password = password + &salt;
+            let mut hasher = Sha256::new();
+            hasher.update(&salty_password);
+            let result = hasher.finalize();
+            let hash = format!("{:x}", result);
+            Ok(Self { hash, salt })
+        }
+
+        /// Random hash no password can be verified against.
+        pub fn unusable() -> Self {
+            let mut rng = urandom::csprng();
+            let random = |bytes: [u8; 32]| bytes.iter().map(|x| format!("{:x}", x)).collect();
+            Self {
+                hash: random(rng.next()),
+                salt: random(rng.next()),
+            }
+        }
+
+        pub fn verify(&self, password: String) -> bool {
+            let salty_password = password + &self.salt;
+            let mut hasher = Sha256::new();
+            hasher.update(&salty_password);
+            let result = hasher.finalize();
+            let hash = format!("{:x}", result);
+            self.hash == hash
+        }
+    }
+
+    impl<DB: OwnedText> ToSql<Text, DB> for Password {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            DB::write_text(out, self.hash.clone() + ":" + &self.salt)
+        }
+    }
+
+    impl<DB> FromSql<Text, DB> for Password
+    where
+        DB: Backend,
+        String: FromSql<Text, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            let all = <String as FromSql<Text, DB>>::from_sql(bytes)?;
+            let all = all.split(|x| x == ':').collect::<Vec<&str>>();
+
+            if all.len() != 2 {
+                return Err(Box::new(diesel::result::Error::DeserializationError(
+                    "Invalid password format".into(),
+                )));
+            }
+
+            let (hash, salt) = match (all[0], all[1]) {
+                ref x if x.0.is_empty() || x.1.is_empty() => {
+                    return Err(Box::new(diesel::result::Error::DeserializationError(
+                        "Invalid password format".into(),
+                    )))
+                }
+                _ => (all[0].to_string(), all[1].to_string()),
+            };
+
+            Ok(Self { hash, salt })
+        }
+    }
+}
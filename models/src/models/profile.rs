@@ -0,0 +1,172 @@
+use super::user_name::UserName;
+use crate::error::{Error, ProfileValidationError, ValidationError};
+use deref_derive::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+    feature = "api",
+    derive(
+        diesel::Queryable,
+        diesel::Insertable,
+        diesel::AsChangeset,
+        utoipa::ToSchema
+    ),
+    diesel(
+        table_name = crate::schema::user_profiles,
+        primary_key(user_id),
+        treat_none_as_null = true
+    )
+)]
+pub struct Profile {
+    #[serde(skip)]
+    pub user_id: i32,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar: Option<String>,
+    #[serde(default)]
+    pub links: ProfileLinks,
+}
+
+impl Profile {
+    /// Checks the limits of the columns, the avatar has to be uploaded to the `storage_url`.
+    pub fn validate(&self, storage_url: &str) -> Result<(), Error> {
+        const MAX_DISPLAY_NAME_LENGTH: usize = 64;
+        const MAX_BIO_LENGTH: usize = 1024;
+        const MAX_LINKS: usize = 5;
+        // Limit of the `avatar` column, applied to each link as well.
+        const MAX_URL_LENGTH: usize = 255;
+
+        if let Some(display_name) = &self.display_name {
+            if display_name.chars().count() > MAX_DISPLAY_NAME_LENGTH {
+                return Err(Error::ValidationError(ValidationError::Profile(
+                    ProfileValidationError::DisplayNameTooLong(MAX_DISPLAY_NAME_LENGTH as i32),
+                )));
+            }
+        }
+        if let Some(bio) = &self.bio {
+            if bio.chars().count() > MAX_BIO_LENGTH {
+                return Err(Error::ValidationError(ValidationError::Profile(
+                    ProfileValidationError::BioTooLong(MAX_BIO_LENGTH as i32),
+                )));
+            }
+        }
+        if let Some(avatar) = &self.avatar {
+            if avatar.chars().count() > MAX_URL_LENGTH {
+                return Err(Error::ValidationError(ValidationError::Profile(
+                    ProfileValidationError::AvatarTooLong(MAX_URL_LENGTH as i32),
+                )));
+            }
+            let uploaded = avatar
+                .strip_prefix(storage_url)
+                .is_some_and(|path| !path.is_empty() && !path.split('/').any(|p| p == ".."));
+            if !uploaded {
+                return Err(Error::ValidationError(ValidationError::Profile(
+                    ProfileValidationError::AvatarNotUploaded(avatar.clone()),
+                )));
+            }
+        }
+        if self.links.len() > MAX_LINKS {
+            return Err(Error::ValidationError(ValidationError::Profile(
+                ProfileValidationError::TooManyLinks(MAX_LINKS as i32),
+            )));
+        }
+        if self
+            .links
+            .iter()
+            .any(|l| l.chars().count() > MAX_URL_LENGTH)
+        {
+            return Err(Error::ValidationError(ValidationError::Profile(
+                ProfileValidationError::LinkTooLong(MAX_URL_LENGTH as i32),
+            )));
+        }
+        if let Some(link) = self.links.iter().find(|l| !is_http_url(l)) {
+            return Err(Error::ValidationError(ValidationError::Profile(
+                ProfileValidationError::InvalidLink(link.clone()),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Trims the values and replaces empty ones with `None`.
+    pub fn normalized(self) -> Self {
+        let normalize =
+            |v: Option<String>| v.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        Self {
+            user_id: self.user_id,
+            display_name: normalize(self.display_name),
+            bio: normalize(self.bio),
+            avatar: normalize(self.avatar),
+            links: ProfileLinks(
+                self.links
+                    .0
+                    .into_iter()
+                    .map(|l| l.trim().to_string())
+                    .filter(|l| !l.is_empty())
+                    .collect(),
+            ),
+        }
+    }
+}
+
+pub(crate) fn is_http_url(value: &str) -> bool {
+    (value.starts_with("https://") || value.starts_with("http://"))
+        && !value.chars().any(|c| c.is_whitespace())
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct PublicProfile {
+    pub name: UserName,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    #[serde(flatten)]
+    pub profile: Profile,
+}
+
+impl PublicProfile {
+    pub fn display_name(&self) -> &str {
+        self.profile.display_name.as_deref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, Deref, DerefMut)]
+#[cfg_attr(
+    feature = "api",
+    derive(diesel::AsExpression, diesel::FromSqlRow, utoipa::ToSchema),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct ProfileLinks(Vec<String>);
+
+impl From<Vec<String>> for ProfileLinks {
+    fn from(links: Vec<String>) -> Self {
+        Self(links)
+    }
+}
+
+/// Stored as a JSON array.
+#[cfg(feature = "api")]
+mod sql {
+    use super::ProfileLinks;
+    use crate::sql::OwnedText;
+    use diesel::{backend::Backend, deserialize::FromSql, serialize::ToSql, sql_types::Text};
+
+    impl<DB: OwnedText> ToSql<Text, DB> for ProfileLinks {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            DB::write_text(out, serde_json::to_string(&self.0)?)
+        }
+    }
+
+    impl<DB> FromSql<Text, DB> for ProfileLinks
+    where
+        DB: Backend,
+        String: FromSql<Text, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            let links = <String as FromSql<Text, DB>>::from_sql(bytes)?;
+            Ok(Self(serde_json::from_str(&links)?))
+        }
+    }
+}
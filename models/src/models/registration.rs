@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationPolicy {
+    /// Anyone can register, accounts need to be activated by an admin.
+    #[default]
+    Open,
+    /// Registration requires a valid invite code.
+    InviteOnly,
+    /// Registration is disabled.
+    Closed,
+}
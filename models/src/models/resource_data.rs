@@ -0,0 +1,41 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+    feature = "api",
+    derive(diesel::Queryable, diesel::Insertable, utoipa::ToSchema),
+    diesel(table_name = crate::schema::resources)
+)]
+pub struct ResourceData {
+    #[cfg_attr(feature = "api", diesel(deserialize_as = String))]
+    pub key: Option<String>,
+    #[cfg_attr(feature = "api", diesel(deserialize_as = String))]
+    pub en: Option<String>,
+    pub pl: Option<String>,
+    /// Set by the database on every change.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "api", diesel(deserialize_as = NaiveDateTime))]
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+impl ResourceData {
+    /// Value for a single language, `None` for an unknown one.
+    pub fn new_from_lang(
+        key: impl Into<String>,
+        lang: &str,
+        value: impl Into<String>,
+    ) -> Option<Self> {
+        let (en, pl) = match lang {
+            "en" => (Some(value.into()), None),
+            "pl" => (None, Some(value.into())),
+            _ => return None,
+        };
+        Some(Self {
+            key: Some(key.into()),
+            en,
+            pl,
+            updated_at: None,
+        })
+    }
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use strum::{EnumIter, EnumString};
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone, Copy, EnumString, EnumIter, PartialEq)]
+#[cfg_attr(
+    feature = "api",
+    derive(
+        diesel::AsExpression,
+        diesel::FromSqlRow,
+        num_derive::FromPrimitive,
+        utoipa::ToSchema
+    ),
+    diesel(sql_type = diesel::sql_types::Integer)
+)]
+pub enum Role {
+    #[default]
+    User,
+    Admin,
+}
+
+#[cfg(feature = "api")]
+mod sql {
+    use super::Role;
+    use diesel::{backend::Backend, deserialize::FromSql, serialize::ToSql, sql_types::Integer};
+    use num_traits::FromPrimitive;
+
+    impl<DB> ToSql<Integer, DB> for Role
+    where
+        DB: Backend,
+        i32: ToSql<Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            // Some backends bind by reference, so the value has to outlive the output.
+            let value: &'static i32 = match self {
+                Role::User => &0,
+                Role::Admin => &1,
+            };
+            <i32 as ToSql<Integer, DB>>::to_sql(value, out)
+        }
+    }
+
+    impl<DB> FromSql<Integer, DB> for Role
+    where
+        DB: Backend,
+        i32: FromSql<Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            let value = <i32 as FromSql<Integer, DB>>::from_sql(bytes)?;
+            FromPrimitive::from_i32(value).ok_or(Box::new(
+                diesel::result::Error::DeserializationError("Invalid role".into()),
+            ))
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self))
+    }
+}
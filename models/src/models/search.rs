@@ -0,0 +1,140 @@
+use crate::error::{Error, SearchValidationError, ValidationError};
+use serde::{Deserialize, Serialize};
+
+/// Content matching a search query.
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "api", derive(diesel::QueryableByName, utoipa::ToSchema))]
+pub struct SearchHit {
+    /// What the content is, `resource` for the resources.
+    #[cfg_attr(feature = "api", diesel(sql_type = diesel::sql_types::Text))]
+    pub kind: String,
+    #[cfg_attr(feature = "api", diesel(sql_type = diesel::sql_types::Text))]
+    pub key: String,
+    /// Text around the matches, each of them between [`SearchHit::MARK_START`] and [`SearchHit::MARK_END`].
+    /// Nothing else in it is escaped.
+    #[cfg_attr(feature = "api", diesel(sql_type = diesel::sql_types::Text))]
+    pub snippet: String,
+    /// Higher for the better matches, only comparable within one search.
+    #[cfg_attr(feature = "api", diesel(sql_type = diesel::sql_types::Float))]
+    pub rank: f32,
+}
+
+impl SearchHit {
+    pub const MARK_START: &'static str = "<mark>";
+    pub const MARK_END: &'static str = "</mark>";
+
+    /// The snippet split into its parts, `true` for the matches.
+    pub fn snippet_parts(&self) -> Vec<(bool, &str)> {
+        let mut parts = vec![];
+        let mut rest = self.snippet.as_str();
+        while let Some((before, after)) = rest.split_once(Self::MARK_START) {
+            let Some((mark, after)) = after.split_once(Self::MARK_END) else {
+                break;
+            };
+            parts.push((false, before));
+            parts.push((true, mark));
+            rest = after;
+        }
+        parts.push((false, rest));
+        parts.retain(|(_, part)| !part.is_empty());
+        parts
+    }
+}
+
+/// Content of one language searched through by [`search`].
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "api", derive(diesel::QueryableByName))]
+pub struct SearchDocument {
+    #[cfg_attr(feature = "api", diesel(sql_type = diesel::sql_types::Text))]
+    pub kind: String,
+    #[cfg_attr(feature = "api", diesel(sql_type = diesel::sql_types::Text))]
+    pub key: String,
+    #[cfg_attr(feature = "api", diesel(sql_type = diesel::sql_types::Text))]
+    pub body: String,
+}
+
+pub const MAX_QUERY_LENGTH: i32 = 128;
+/// Words of a snippet shown before its first match.
+const SNIPPET_CONTEXT: usize = 8;
+const SNIPPET_WORDS: usize = 24;
+
+/// Lowercase words of the search `query`, fails when there are none or it's too long.
+pub fn query_terms(query: &str) -> Result<Vec<String>, Error> {
+    if query.chars().count() > MAX_QUERY_LENGTH as usize {
+        return Err(Error::ValidationError(ValidationError::Search(
+            SearchValidationError::QueryTooLong(MAX_QUERY_LENGTH),
+        )));
+    }
+    let mut terms = vec![];
+    for term in query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+    {
+        if !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    match terms.is_empty() {
+        true => Err(Error::ValidationError(ValidationError::Search(
+            SearchValidationError::QueryEmpty,
+        ))),
+        false => Ok(terms),
+    }
+}
+
+/// Searches the `documents` without a database, for the backends lacking full-text search.
+/// Words match the terms they start with and documents have to match all of them,
+/// more matches in a shorter document rank it higher.
+pub fn search(
+    documents: impl IntoIterator<Item = SearchDocument>,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SearchHit>, Error> {
+    let terms = query_terms(query)?;
+    let mut hits = documents
+        .into_iter()
+        .filter_map(|document| {
+            let words = document.body.split_whitespace().collect::<Vec<_>>();
+            let matches = words
+                .iter()
+                .map(|word| {
+                    let word = word.to_lowercase();
+                    let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+                    terms.iter().position(|t| word.starts_with(t.as_str()))
+                })
+                .collect::<Vec<_>>();
+            if !(0..terms.len()).all(|t| matches.contains(&Some(t))) {
+                return None;
+            }
+            let first = matches.iter().position(Option::is_some)?;
+            let start = first.saturating_sub(SNIPPET_CONTEXT);
+            let end = (start + SNIPPET_WORDS).min(words.len());
+            let mut snippet = words[start..end]
+                .iter()
+                .zip(&matches[start..end])
+                .map(|(word, matched)| match matched {
+                    Some(_) => format!("{}{}{}", SearchHit::MARK_START, word, SearchHit::MARK_END),
+                    None => word.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            if start > 0 {
+                snippet = format!("… {}", snippet);
+            }
+            if end < words.len() {
+                snippet = format!("{} …", snippet);
+            }
+            let count = matches.iter().flatten().count();
+            Some(SearchHit {
+                kind: document.kind,
+                key: document.key,
+                snippet,
+                rank: count as f32 / (words.len() as f32).sqrt(),
+            })
+        })
+        .collect::<Vec<_>>();
+    hits.sort_by(|a, b| b.rank.total_cmp(&a.rank).then_with(|| a.key.cmp(&b.key)));
+    hits.truncate(limit);
+    Ok(hits)
+}
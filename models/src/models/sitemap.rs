@@ -0,0 +1,112 @@
+//! Pages offered to the search engines, listed by the API and routed by the front.
+use super::page_meta::escape;
+use chrono::NaiveDateTime;
+
+/// Public page of the front, with the resource it shows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PublicPage {
+    pub path: &'static str,
+    pub reskey: Option<&'static str>,
+}
+
+/// Every page of the front anyone can see, without the ones made of a user's data.
+pub const PUBLIC_PAGES: [PublicPage; 4] = [
+    PublicPage {
+        path: "/",
+        reskey: Some("home-content"),
+    },
+    PublicPage {
+        path: "/projects",
+        reskey: None,
+    },
+    PublicPage {
+        path: "/about",
+        reskey: Some("about-content"),
+    },
+    PublicPage {
+        path: "/contact",
+        reskey: Some("contact-content"),
+    },
+];
+
+/// Routes never crawled, whatever is configured.
+pub const PRIVATE_PATHS: [&str; 2] = ["/admin", "/editor"];
+
+/// Languages of the front, the first one is the default.
+pub const LANGS: [&str; 2] = ["en", "pl"];
+
+/// Page of the sitemap, `lastmod` is the last change of its content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapEntry {
+    pub path: String,
+    pub lastmod: Option<NaiveDateTime>,
+}
+
+/// URL of the page at the `site_url`, in the `lang` when given.
+/// The front switches to the language of the `lang` query parameter.
+pub fn page_url(site_url: &str, path: &str, lang: Option<&str>) -> String {
+    let url = format!("{}{}", site_url.trim_end_matches('/'), path);
+    match lang {
+        Some(lang) => format!("{}?lang={}", url, lang),
+        None => url,
+    }
+}
+
+/// The `sitemap.xml` with every entry in each of the `langs`, linked to each other with `hreflang`.
+pub fn sitemap(site_url: &str, langs: &[String], entries: &[SitemapEntry]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\" xmlns:xhtml=\"http://www.w3.org/1999/xhtml\">\n",
+    );
+    for entry in entries {
+        let alternates = langs
+            .iter()
+            .map(|lang| (lang.as_str(), page_url(site_url, &entry.path, Some(lang))))
+            .chain([("x-default", page_url(site_url, &entry.path, None))])
+            .map(|(lang, url)| {
+                format!(
+                    "    <xhtml:link rel=\"alternate\" hreflang=\"{}\" href=\"{}\"/>\n",
+                    lang,
+                    escape(&url)
+                )
+            })
+            .collect::<String>();
+        let lastmod = entry
+            .lastmod
+            .map(|lastmod| {
+                format!(
+                    "    <lastmod>{}</lastmod>\n",
+                    lastmod.format("%Y-%m-%dT%H:%M:%S+00:00")
+                )
+            })
+            .unwrap_or_default();
+        for lang in langs {
+            xml.push_str(&format!(
+                "  <url>\n    <loc>{}</loc>\n{}{}  </url>\n",
+                escape(&page_url(site_url, &entry.path, Some(lang))),
+                lastmod,
+                alternates
+            ));
+        }
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// The `robots.txt` keeping the crawlers out of the [`PRIVATE_PATHS`] and the `disallow`ed ones,
+/// or out of the whole site when it shouldn't be `indexed`.
+pub fn robots(site_url: &str, disallow: &[String], indexed: bool) -> String {
+    let mut txt = String::from("User-agent: *\n");
+    match indexed {
+        true => PRIVATE_PATHS
+            .iter()
+            .copied()
+            .chain(disallow.iter().map(String::as_str))
+            .for_each(|path| txt.push_str(&format!("Disallow: {}\n", path))),
+        false => txt.push_str("Disallow: /\n"),
+    }
+    txt.push_str(&format!(
+        "\nSitemap: {}\n",
+        page_url(site_url, "/sitemap.xml", None)
+    ));
+    txt
+}
@@ -0,0 +1,21 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Metadata of a file uploaded to the blob storage.
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+    feature = "api",
+    derive(diesel::Queryable, diesel::Insertable, utoipa::ToSchema),
+    diesel(table_name = crate::schema::uploads)
+)]
+pub struct Upload {
+    #[cfg_attr(feature = "api", diesel(deserialize_as = i32))]
+    pub id: Option<i32>,
+    pub path: String,
+    pub content_type: String,
+    pub size: i32,
+    /// `None` once the uploader deleted the account.
+    pub user_id: Option<i32>,
+    #[cfg_attr(feature = "api", diesel(deserialize_as = chrono::NaiveDateTime))]
+    pub created_at: Option<NaiveDateTime>,
+}
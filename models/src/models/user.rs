@@ -0,0 +1,46 @@
+use super::{role::Role, user_name::UserName};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+    feature = "api",
+    derive(diesel::Queryable, diesel::Insertable, utoipa::ToSchema),
+    diesel(table_name = crate::schema::users)
+)]
+pub struct User {
+    #[cfg_attr(feature = "api", diesel(deserialize_as = i32))]
+    pub id: Option<i32>,
+    pub name: UserName,
+    pub normalized_name: String,
+    /// Never sent, only the API reads it from the database.
+    #[cfg(feature = "api")]
+    #[serde(skip_serializing, default)]
+    #[schema(value_type = String, write_only)]
+    pub password: super::password::Password,
+    pub role: Role,
+    pub confirmed: bool,
+    #[cfg_attr(feature = "api", diesel(deserialize_as = chrono::NaiveDateTime))]
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub deleted_at: Option<chrono::NaiveDateTime>,
+}
+
+#[cfg(feature = "api")]
+impl User {
+    pub fn new(
+        name: String,
+        password: String,
+        role: Role,
+        requirements: &super::password::PasswordRequirements,
+    ) -> Result<Self, crate::error::Error> {
+        let name = UserName::new(name)?;
+        let normalized_name = name.to_lowercase();
+        let password = super::password::Password::new(password, requirements)?;
+        Ok(Self {
+            name,
+            normalized_name,
+            password,
+            role,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,22 @@
+use super::{comment::Comment, invite::Invite, profile::Profile, upload::Upload, user::User};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Everything stored about a single account.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct UserExport {
+    pub exported_at: NaiveDateTime,
+    pub user: User,
+    pub profile: Profile,
+    pub invites: Vec<Invite>,
+    pub uploads: Vec<Upload>,
+    /// The content the user authored, the pages keep no authors or revisions of their own.
+    pub comments: Vec<Comment>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct DeleteAccountRequest {
+    pub password: String,
+}
@@ -0,0 +1,83 @@
+use crate::error::{Error, UsernameValidationError, ValidationError};
+use deref_derive::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, Deref, DerefMut)]
+#[cfg_attr(
+    feature = "api",
+    derive(diesel::AsExpression, diesel::FromSqlRow, utoipa::ToSchema),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct UserName(String);
+
+impl UserName {
+    pub fn new(name: String) -> Result<Self, Error> {
+        let name = name.trim();
+        validate_name(name)?;
+        Ok(Self(name.to_string()))
+    }
+
+    /// Name of the anonymized account with the `id`.
+    /// It fails the validation, so it never collides with a registered name.
+    pub fn anonymous(id: i32) -> Self {
+        Self(format!("~deleted-{}", id))
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), Error> {
+    const MIN_LENGTH: usize = 3;
+    const MAX_LENGTH: usize = 28;
+    const SPECIAL_CHARS: [char; 11] = ['-', '_', '.', '$', '@', '!', '#', '%', '^', '&', '*'];
+
+    if !(MIN_LENGTH..MAX_LENGTH).contains(&name.len()) {
+        return Err(Error::ValidationError(ValidationError::Username(
+            UsernameValidationError::InvalidLength(MIN_LENGTH as i32, MAX_LENGTH as i32),
+        )));
+    }
+
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || SPECIAL_CHARS.contains(&c))
+    {
+        return Err(Error::ValidationError(ValidationError::Username(
+            UsernameValidationError::InvalidCharacters(SPECIAL_CHARS.to_vec()),
+        )));
+    }
+    Ok(())
+}
+
+impl Display for UserName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(feature = "api")]
+mod sql {
+    use super::UserName;
+    use diesel::{backend::Backend, deserialize::FromSql, serialize::ToSql, sql_types::Text};
+
+    impl<DB> ToSql<Text, DB> for UserName
+    where
+        DB: Backend,
+        String: ToSql<Text, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            <String as ToSql<Text, DB>>::to_sql(&self.0, out)
+        }
+    }
+
+    impl<DB> FromSql<Text, DB> for UserName
+    where
+        DB: Backend,
+        String: FromSql<Text, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            Ok(Self(<String as FromSql<Text, DB>>::from_sql(bytes)?))
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use diesel::{
+    backend::Backend,
+    serialize::{IsNull, Output, Result},
+};
+
+/// Backend binding text computed while serializing a value,
+/// `ToSql` of `String` only binds text borrowed from the value itself.
+pub trait OwnedText: Backend {
+    fn write_text(out: &mut Output<'_, '_, Self>, text: String) -> Result;
+}
+
+#[cfg(feature = "postgres")]
+impl OwnedText for diesel::pg::Pg {
+    fn write_text(out: &mut Output<'_, '_, Self>, text: String) -> Result {
+        use std::io::Write;
+        out.write_all(text.as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl OwnedText for diesel::sqlite::Sqlite {
+    fn write_text(out: &mut Output<'_, '_, Self>, text: String) -> Result {
+        out.set_value(text);
+        Ok(IsNull::No)
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use petompp_web_models::{
+    error::{CommentValidationError, Error, ValidationError},
+    models::comment::{self, Comment, CommentRequest, CommentStatus, ThreadComment},
+};
+
+fn at(minute: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2023, 10, 29)
+        .unwrap()
+        .and_hms_opt(12, minute, 0)
+        .unwrap()
+}
+
+fn comment(id: i32, parent_id: Option<i32>, minute: u32) -> Comment {
+    Comment {
+        id: Some(id),
+        key: "home".to_string(),
+        parent_id,
+        user_id: 1,
+        body: format!("Comment {}", id),
+        status: CommentStatus::Approved,
+        created_at: Some(at(minute)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn markdown_is_rendered_without_html_and_images() {
+    let html = comment::render("Some *text* <script>alert(1)</script>\n\n![img](a.png)");
+    assert!(html.contains("<em>text</em>"), "{}", html);
+    assert!(html.contains("&lt;script&gt;"), "{}", html);
+    assert!(!html.contains("<img"), "{}", html);
+    assert_eq!(comment::render("# Title"), "<p># Title</p>");
+    assert!(!comment::render("[x](javascript:alert(1))").contains("javascript:"));
+    let html = comment::render("[x](https://a.b) <a href=\"https://c.d\">y</a>");
+    assert!(
+        html.contains("<a rel=\"nofollow ugc\" href=\"https://a.b\">x</a>"),
+        "{}",
+        html
+    );
+    assert_eq!(html.matches("<a ").count(), 1, "{}", html);
+}
+
+#[test]
+fn body_is_trimmed_and_limited() {
+    let request = |body: &str| CommentRequest {
+        body: body.to_string(),
+        parent_id: None,
+    };
+    assert_eq!(Comment::validate_body(&request("  Hi \n")).unwrap(), "Hi");
+    assert_eq!(
+        Comment::validate_body(&request(" \n ")),
+        Err(Error::ValidationError(ValidationError::Comment(
+            CommentValidationError::BodyEmpty
+        )))
+    );
+    let long = "a".repeat(Comment::MAX_BODY_LENGTH + 1);
+    assert_eq!(
+        Comment::validate_body(&request(&long)),
+        Err(Error::ValidationError(ValidationError::Comment(
+            CommentValidationError::BodyTooLong(Comment::MAX_BODY_LENGTH as i32)
+        )))
+    );
+}
+
+#[test]
+fn comments_are_editable_for_a_while() {
+    let comment = comment(1, None, 0);
+    let window = Duration::minutes(Comment::EDIT_WINDOW_MINUTES);
+    assert!(comment.is_editable(at(0) + window - Duration::seconds(1)));
+    assert!(!comment.is_editable(at(0) + window));
+    let deleted = Comment {
+        deleted_at: Some(at(1)),
+        ..comment
+    };
+    assert!(!deleted.is_editable(at(2)));
+}
+
+#[test]
+fn only_approved_comments_of_the_page_can_be_replied_to() {
+    let parent = comment(1, None, 0);
+    let reply = comment(2, Some(1), 1);
+    assert!(reply.can_reply_to(&parent));
+    let other_page = Comment {
+        key: "about".to_string(),
+        ..parent.clone()
+    };
+    assert!(!reply.can_reply_to(&other_page));
+    let pending = Comment {
+        status: CommentStatus::Pending,
+        ..parent.clone()
+    };
+    assert!(!reply.can_reply_to(&pending));
+    let deleted = Comment {
+        deleted_at: Some(at(2)),
+        ..parent
+    };
+    assert!(!reply.can_reply_to(&deleted));
+}
+
+#[test]
+fn replies_are_grouped_by_parent_oldest_first() {
+    let comments = [
+        comment(1, None, 0),
+        comment(2, Some(1), 5),
+        comment(3, Some(1), 2),
+        comment(4, None, 3),
+    ]
+    .map(|c| ThreadComment::new(c, Default::default()));
+    let ids = |parent| {
+        ThreadComment::replies(&comments, parent)
+            .iter()
+            .map(|c| c.comment.id.unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(None), vec![1, 4]);
+    assert_eq!(ids(Some(1)), vec![3, 2]);
+    assert!(ids(Some(2)).is_empty());
+}
+
+#[test]
+fn deleted_comments_lose_their_content() {
+    let deleted = ThreadComment::new(
+        Comment {
+            deleted_at: Some(at(1)),
+            ..comment(1, None, 0)
+        },
+        Default::default(),
+    );
+    assert_eq!(deleted.comment.body, "");
+    assert_eq!(deleted.html, "");
+    let shown = ThreadComment::new(comment(2, None, 0), Default::default());
+    assert_eq!(shown.html, "<p>Comment 2</p>");
+}
@@ -0,0 +1,60 @@
+use chrono::NaiveDate;
+use petompp_web_models::models::feed::{self, Feed, FeedEntry, FeedFormat};
+
+fn entry(path: &str, day: u32) -> FeedEntry {
+    FeedEntry {
+        path: path.to_string(),
+        title: path.to_string(),
+        summary: "<p>Hi</p>".to_string(),
+        updated: NaiveDate::from_ymd_opt(2023, 10, day)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap(),
+    }
+}
+
+#[test]
+fn summary_is_the_first_paragraph() {
+    let markdown = "# Title\r\n\r\n![img](a.png)\r\n\r\nSome **bold** <b>text</b>.\r\n\r\nRest.";
+    assert_eq!(feed::title(markdown).as_deref(), Some("Title"));
+    assert_eq!(
+        feed::summary(markdown),
+        "<p>Some <strong>bold</strong> &lt;b&gt;text&lt;/b&gt;.</p>"
+    );
+    assert_eq!(feed::title("No heading"), None);
+    assert_eq!(feed::summary(""), "");
+}
+
+#[test]
+fn newest_entries_come_first() {
+    let entries = (1..=25).map(|day| entry(&format!("/{}", day), day));
+    let feed = Feed::new(
+        "en",
+        "https://petompp.net/",
+        "https://api.petompp.net/",
+        entries.collect(),
+    );
+    assert_eq!(feed.entries.len(), Feed::MAX_ENTRIES);
+    assert_eq!(feed.entries[0].path, "/25");
+    assert_eq!(feed.updated(), entry("/", 25).updated);
+    assert!(feed
+        .render(FeedFormat::Atom)
+        .contains("<updated>2023-10-25T12:00:00Z</updated>"));
+    assert!(feed
+        .render(FeedFormat::Rss)
+        .contains("<pubDate>Wed, 25 Oct 2023 12:00:00 GMT</pubDate>"));
+    assert!(feed.render(FeedFormat::Atom).contains(
+        "<link rel=\"self\" href=\"https://api.petompp.net/api/v1/feeds/en/atom.xml\"/>"
+    ));
+}
+
+#[test]
+fn formats_are_found_by_their_file_names() {
+    for format in [FeedFormat::Atom, FeedFormat::Rss, FeedFormat::Json] {
+        assert_eq!(FeedFormat::from_file_name(format.file_name()), Some(format));
+    }
+    assert_eq!(
+        FeedFormat::Rss.link_html("https://api.petompp.net/", "pl"),
+        "<link data-feed rel=\"alternate\" type=\"application/rss+xml\" title=\"PetoMPP.NET (RSS)\" href=\"https://api.petompp.net/api/v1/feeds/pl/rss.xml\">"
+    );
+}
@@ -0,0 +1,81 @@
+use petompp_web_models::{
+    error::{Error, PageMetaValidationError, ValidationError},
+    models::page_meta::{HeadTag, PageMeta},
+};
+
+fn meta(title: &str, image: Option<&str>) -> PageMeta {
+    PageMeta {
+        title: Some(title.to_string()),
+        image: image.map(str::to_string),
+        ..PageMeta::new("home-content", "en")
+    }
+}
+
+#[test]
+fn empty_values_are_dropped() {
+    let meta = PageMeta {
+        description: Some("  ".to_string()),
+        ..meta(" Home ", Some(""))
+    }
+    .normalized();
+    assert_eq!(meta.title.as_deref(), Some("Home"));
+    assert_eq!((meta.description, meta.image), (None, None));
+    assert_eq!(meta.document_title(), "Home | PetoMPP.NET");
+    assert_eq!(
+        PageMeta::new("home-content", "en").document_title(),
+        PageMeta::SITE_NAME
+    );
+}
+
+#[test]
+fn meta_is_validated() {
+    let error = |e| Err(Error::ValidationError(ValidationError::PageMeta(e)));
+    assert_eq!(meta("Home", Some("https://a.b/c.png")).validate(), Ok(()));
+    assert_eq!(
+        meta(&"a".repeat(PageMeta::MAX_TITLE_LENGTH + 1), None).validate(),
+        error(PageMetaValidationError::TitleTooLong(128))
+    );
+    assert_eq!(
+        meta("Home", Some("/c.png")).validate(),
+        error(PageMetaValidationError::InvalidUrl("/c.png".to_string()))
+    );
+    let long_url = format!("https://a.b/{}", "c".repeat(PageMeta::MAX_URL_LENGTH));
+    assert_eq!(
+        meta("Home", Some(&long_url)).validate(),
+        error(PageMetaValidationError::UrlTooLong(255))
+    );
+    assert_eq!(
+        PageMeta::new("home-content", "de").validate(),
+        error(PageMetaValidationError::LangInvalid("de".to_string()))
+    );
+}
+
+#[test]
+fn head_tags_describe_the_page() {
+    let tags = meta("Home", Some("https://a.b/c.png")).head_tags();
+    let content = |key: &str| {
+        tags.iter().find_map(|tag| match tag {
+            HeadTag::Meta {
+                key: k, content, ..
+            } if *k == key => Some(content.as_str()),
+            _ => None,
+        })
+    };
+    assert_eq!(content("og:title"), Some("Home"));
+    assert_eq!(content("og:image"), Some("https://a.b/c.png"));
+    assert_eq!(content("twitter:card"), Some("summary_large_image"));
+    assert_eq!(content("description"), None);
+}
+
+#[test]
+fn head_tags_are_escaped() {
+    let html = meta("\"><script>alert(1)</script>", None)
+        .head_tags()
+        .iter()
+        .map(HeadTag::to_html)
+        .collect::<String>();
+    assert!(!html.contains("<script>"), "{}", html);
+    assert!(html.contains(
+        "<meta data-page-meta property=\"og:title\" content=\"&quot;&gt;&lt;script&gt;alert(1)&lt;/script&gt;\">"
+    ));
+}
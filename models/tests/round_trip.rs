@@ -0,0 +1,138 @@
+use chrono::NaiveDate;
+use petompp_web_models::{
+    error::{AuthError, Error, Problem, ValidationError},
+    models::{
+        bundle::{ConflictStrategy, ImportSummary},
+        invite::Invite,
+        password::PasswordRequirements,
+        profile::{Profile, PublicProfile},
+        registration::RegistrationPolicy,
+        resource_data::ResourceData,
+        role::Role,
+        user::User,
+        user_name::UserName,
+    },
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{collections::HashSet, fmt::Debug};
+
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T) {
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value);
+}
+
+fn created_at() -> chrono::NaiveDateTime {
+    NaiveDate::from_ymd_opt(2023, 9, 1)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+}
+
+#[test]
+fn user_round_trips() {
+    round_trip(User {
+        id: Some(1),
+        name: UserName::new("PetoMPP".to_string()).unwrap(),
+        normalized_name: "petompp".to_string(),
+        role: Role::Admin,
+        confirmed: true,
+        created_at: Some(created_at()),
+        ..Default::default()
+    });
+}
+
+#[test]
+fn profile_round_trips() {
+    round_trip(PublicProfile {
+        name: UserName::new("PetoMPP".to_string()).unwrap(),
+        created_at: Some(created_at()),
+        profile: Profile {
+            display_name: Some("Peto".to_string()),
+            links: vec!["https://petompp.net".to_string()].into(),
+            ..Default::default()
+        },
+    });
+}
+
+#[test]
+fn invite_and_content_round_trip() {
+    round_trip(Invite {
+        id: Some(3),
+        code: "0123456789ab".to_string(),
+        role: Role::User,
+        max_uses: 2,
+        created_by: 1,
+        created_at: Some(created_at()),
+        ..Default::default()
+    });
+    round_trip(ResourceData::new_from_lang("home", "pl", "Witaj").unwrap());
+    round_trip(ImportSummary {
+        dry_run: true,
+        created: vec!["home".to_string()],
+        ..Default::default()
+    });
+    round_trip(ConflictStrategy::Merge);
+    round_trip(RegistrationPolicy::InviteOnly);
+}
+
+#[test]
+fn errors_round_trip() {
+    round_trip(Error::InviteCodeExpired("code".to_string()));
+    round_trip(Error::ValidationError(ValidationError::Password(
+        PasswordRequirements::default(),
+    )));
+}
+
+#[test]
+fn error_codes_are_stable() {
+    let codes = Error::CODES.iter().collect::<HashSet<_>>();
+    assert_eq!(codes.len(), Error::CODES.len());
+    let errors = [
+        Error::AuthError(AuthError::TokenExpiredS(30)),
+        Error::InvalidCredentials,
+        Error::ValidationError(ValidationError::Password(Default::default())),
+        Error::Status(404, "404 Not Found".to_string()),
+    ];
+    for error in &errors {
+        assert!(Error::CODES.contains(&error.code()), "{}", error.code());
+    }
+    assert_eq!(errors[0].code(), "auth.token_expired");
+    assert_eq!(errors[0].params(), vec!["30".to_string()]);
+    assert_eq!(errors[1].status_code(), 401);
+}
+
+#[test]
+fn problem_round_trips() {
+    let problem = Problem::from(Error::InviteCodeUsedUp("code".to_string()));
+    let json = serde_json::to_value(&problem).unwrap();
+    assert_eq!(json["type"], "about:blank");
+    assert_eq!(json["code"], "invite.code_used_up");
+    round_trip(problem);
+}
+
+#[test]
+fn wire_format_is_stable() {
+    assert_eq!(
+        serde_json::to_string(&ConflictStrategy::Overwrite).unwrap(),
+        "\"overwrite\""
+    );
+    assert_eq!(ConflictStrategy::Overwrite.to_string(), "overwrite");
+    assert_eq!(
+        serde_json::to_string(&RegistrationPolicy::InviteOnly).unwrap(),
+        "\"invite_only\""
+    );
+    assert!(ResourceData::new_from_lang("home", "de", "Hallo").is_none());
+}
+
+#[cfg(feature = "api")]
+#[test]
+fn user_password_is_never_sent() {
+    use petompp_web_models::models::password::Password;
+
+    let user = User {
+        password: Password::new("Password1!".to_string(), &Default::default()).unwrap(),
+        ..Default::default()
+    };
+    let json = serde_json::to_value(&user).unwrap();
+    assert!(json.get("password").is_none());
+}
@@ -0,0 +1,64 @@
+use petompp_web_models::{
+    error::{Error, SearchValidationError, ValidationError},
+    models::search::{query_terms, search, SearchDocument, SearchHit},
+};
+
+fn document(key: &str, body: &str) -> SearchDocument {
+    SearchDocument {
+        kind: "resource".to_string(),
+        key: key.to_string(),
+        body: body.to_string(),
+    }
+}
+
+#[test]
+fn query_is_split_into_terms() {
+    assert_eq!(query_terms("Rust, rust & Yew!").unwrap(), ["rust", "yew"]);
+    assert_eq!(
+        query_terms(" ? "),
+        Err(Error::ValidationError(ValidationError::Search(
+            SearchValidationError::QueryEmpty
+        )))
+    );
+    assert!(query_terms(&"a".repeat(200)).is_err());
+}
+
+#[test]
+fn documents_match_every_term() {
+    let documents = [
+        document("rust", "Rust"),
+        document("both", "Rust with Yew, rusty and long enough to rank lower"),
+        document("none", "Nothing here"),
+    ];
+    let hits = search(documents.clone(), "rust yew", 10).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].key, "both");
+    let hits = search(documents, "rust", 10).unwrap();
+    let keys = hits.iter().map(|h| h.key.as_str()).collect::<Vec<_>>();
+    assert_eq!(keys, ["rust", "both"]);
+}
+
+#[test]
+fn snippet_is_cut_around_the_first_match() {
+    let body = (0..40)
+        .map(|i| format!("w{}", i))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let hits = search([document("long", &body)], "w20", 1).unwrap();
+    let snippet = &hits[0].snippet;
+    assert!(snippet.starts_with("… w12 "));
+    assert!(snippet.contains(" <mark>w20</mark> "));
+    assert!(snippet.ends_with(" w35 …"));
+}
+
+#[test]
+fn snippet_is_split_into_parts() {
+    let hit = SearchHit {
+        snippet: "I <mark>write</mark> <mark>Rust</mark>".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        hit.snippet_parts(),
+        [(false, "I "), (true, "write"), (false, " "), (true, "Rust")]
+    );
+}
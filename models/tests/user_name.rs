@@ -0,0 +1,9 @@
+use petompp_web_models::models::user_name::UserName;
+
+#[test]
+fn anonymous_names_cannot_be_registered() {
+    let anonymous = UserName::anonymous(42);
+    assert_eq!(anonymous.to_string(), "~deleted-42");
+    assert!(UserName::new(anonymous.to_string()).is_err());
+    assert!(UserName::new("deleted-42".to_string()).is_ok());
+}